@@ -16,7 +16,15 @@ function App() {
   const [loading, setLoading] = useState(true)
 
   const wsEndpoint = config.wsUrl ? `${config.wsUrl}/rollups/stream` : '/rollups/stream'
-  const { events, initialData, status: wsStatus, clearEvents } = useWebSocket(wsEndpoint)
+  const {
+    events,
+    initialData,
+    healthChanges,
+    sequencerChanges,
+    lastRetraction,
+    status: wsStatus,
+    clearEvents,
+  } = useWebSocket(wsEndpoint)
 
   useEffect(() => {
     const fetchStatus = async () => {
@@ -104,6 +112,50 @@ function App() {
     }
   }, [initialData])
 
+  // Apply health and sequencer transitions pushed over the stream
+  useEffect(() => {
+    setHealthData((prev) => {
+      const next = { ...prev }
+      for (const [rollup, change] of Object.entries(healthChanges)) {
+        next[rollup] = { ...next[rollup], rollup, status: change.status, issues: change.reasons }
+      }
+      return next
+    })
+  }, [healthChanges])
+
+  useEffect(() => {
+    setSequencerData((prev) => {
+      const next = { ...prev }
+      for (const [rollup, change] of Object.entries(sequencerChanges)) {
+        next[rollup] = change.status
+      }
+      return next
+    })
+  }, [sequencerChanges])
+
+  // A retracted event's status was rolled back on the server; reload it
+  useEffect(() => {
+    if (!lastRetraction?.rollup) return
+
+    const rollup = lastRetraction.rollup.toLowerCase()
+    const setters = {
+      arbitrum: setArbitrumStatus,
+      starknet: setStarknetStatus,
+      base: setBaseStatus,
+      optimism: setOptimismStatus,
+      zksync: setZksyncStatus,
+    }
+    const setStatus = setters[rollup]
+    if (!setStatus) return
+
+    fetch(`${config.apiUrl}/rollups/${rollup}/status`)
+      .then((res) => (res.ok ? res.json() : null))
+      .then((data) => {
+        if (data) setStatus(data)
+      })
+      .catch((err) => console.error(`Failed to reload ${rollup} status:`, err))
+  }, [lastRetraction])
+
   // Update status from WebSocket events
   useEffect(() => {
     if (events.length === 0) return
@@ -68,6 +68,11 @@ export function EventItem({ event }) {
           >
             {eventType.label}
           </span>
+          {event.status === 'pending' && (
+            <span className="px-2 py-0.5 rounded text-xs font-medium bg-gray-500/20 text-gray-400">
+              Pending
+            </span>
+          )}
         </div>
 
         <div className="flex items-center gap-2 sm:gap-4 text-sm flex-wrap">
@@ -50,7 +50,8 @@ export function RollupCard({ rollup, status, loading, health, sequencer }) {
     if (sequencer && !sequencer.is_producing) return 'seq-down'
     if (health?.status) {
       switch (health.status) {
-        case 'Delayed': return 'warning'
+        case 'Delayed':
+        case 'Maintenance': return 'warning'
         case 'Halted':
         case 'Disconnected': return 'error'
         case 'Healthy': return 'healthy'
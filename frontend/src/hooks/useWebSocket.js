@@ -3,6 +3,11 @@ import { useState, useEffect, useRef, useCallback } from 'react'
 export function useWebSocket(url) {
   const [events, setEvents] = useState([])
   const [initialData, setInitialData] = useState(null)
+  // Latest HealthChanged / SequencerChanged message per rollup
+  const [healthChanges, setHealthChanges] = useState({})
+  const [sequencerChanges, setSequencerChanges] = useState({})
+  // Latest EventRetracted message, so the status it wrote can be reloaded
+  const [lastRetraction, setLastRetraction] = useState(null)
   const [status, setStatus] = useState('disconnected')
   const wsRef = useRef(null)
   const reconnectTimeoutRef = useRef(null)
@@ -40,8 +45,28 @@ export function useWebSocket(url) {
         // Handle initial status message separately from events
         if (data.type === 'initial') {
           setInitialData(data)
-        } else if (data.rollup) {
-          // Only add actual rollup events to the events array
+        } else if (data.type === 'EventConfirmed' || data.type === 'EventRetracted') {
+          // Update or drop the matching event in place
+          const sameEvent = (e) =>
+            e.rollup === data.rollup &&
+            e.tx_hash === data.tx_hash &&
+            e.event_type === data.event_type &&
+            e.batch_number === data.batch_number
+          setEvents((prev) =>
+            data.type === 'EventConfirmed'
+              ? prev.map((e) => (sameEvent(e) ? { ...e, status: 'confirmed' } : e))
+              : prev.filter((e) => !sameEvent(e))
+          )
+          if (data.type === 'EventRetracted') {
+            setLastRetraction(data)
+          }
+        } else if (data.type === 'BatchGap') {
+          // Gaps are surfaced through health issues; keep them out of the event feed
+        } else if (data.type === 'HealthChanged') {
+          setHealthChanges((prev) => ({ ...prev, [data.rollup]: data }))
+        } else if (data.type === 'SequencerChanged') {
+          setSequencerChanges((prev) => ({ ...prev, [data.rollup]: data }))
+        } else if (data.type === 'Event') {
           setEvents((prev) => [data, ...prev].slice(0, 100))
         }
       } catch (e) {
@@ -95,5 +120,15 @@ export function useWebSocket(url) {
     }
   }, [connect])
 
-  return { events, initialData, status, connect, disconnect, clearEvents }
+  return {
+    events,
+    initialData,
+    healthChanges,
+    sequencerChanges,
+    lastRetraction,
+    status,
+    connect,
+    disconnect,
+    clearEvents,
+  }
 }
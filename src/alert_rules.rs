@@ -0,0 +1,216 @@
+use crate::alerts::{AlertKind, AlertSeverity, WebhookTarget};
+use crate::health::HealthStage;
+use serde::Deserialize;
+
+/// Who is notified of which problems, and how often.
+///
+/// A rule opens an alert when a transition of a matching rollup and kind is
+/// at least `min_severity`, notifies the `notify` targets, reminds them every
+/// `renotify_secs` while the problem lasts, pulls in the `escalate_to`
+/// targets once it has lasted `escalate_after_secs`, and sends every notified
+/// target a resolution when it clears. Problems that clear within
+/// `flap_window_secs` of opening are never notified.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AlertRule {
+    /// Name the rule's open alerts are tracked under
+    pub name: String,
+    /// Rollups covered, every rollup if empty
+    #[serde(default)]
+    pub rollups: Vec<String>,
+    /// Kinds of alert covered, both if empty
+    #[serde(default)]
+    pub kinds: Vec<AlertKind>,
+    /// Stages a problem must lie in to open an alert, any if empty
+    #[serde(default)]
+    pub stages: Vec<HealthStage>,
+    /// Least severe status alerted on
+    #[serde(default)]
+    pub min_severity: AlertSeverity,
+    /// Webhook targets notified
+    pub notify: Vec<String>,
+    /// Seconds between reminders while the problem lasts, none if unset
+    #[serde(default)]
+    pub renotify_secs: Option<u64>,
+    /// Seconds after opening the `escalate_to` targets are notified
+    #[serde(default)]
+    pub escalate_after_secs: Option<u64>,
+    /// Webhook targets notified on escalation
+    #[serde(default)]
+    pub escalate_to: Vec<String>,
+    /// Seconds a problem must last before it is notified
+    #[serde(default)]
+    pub flap_window_secs: u64,
+}
+
+impl AlertRule {
+    /// Rule alerting a webhook the way its own `rollups` and `min_severity`
+    /// ask, for webhooks no configured rule names
+    pub fn for_webhook(target: &WebhookTarget) -> Self {
+        Self {
+            name: target.name.clone(),
+            rollups: target.rollups.clone(),
+            kinds: Vec::new(),
+            stages: Vec::new(),
+            min_severity: target.min_severity,
+            notify: vec![target.name.clone()],
+            renotify_secs: None,
+            escalate_after_secs: None,
+            escalate_to: Vec::new(),
+            flap_window_secs: 0,
+        }
+    }
+
+    /// Whether the rule covers alerts of a kind about a rollup
+    pub fn matches(&self, rollup: &str, kind: AlertKind) -> bool {
+        (self.rollups.is_empty() || self.rollups.iter().any(|r| r == rollup))
+            && (self.kinds.is_empty() || self.kinds.contains(&kind))
+    }
+
+    /// Whether a problem in `stage` opens an alert under the rule
+    pub fn matches_stage(&self, stage: Option<HealthStage>) -> bool {
+        self.stages.is_empty() || stage.is_some_and(|s| self.stages.contains(&s))
+    }
+
+    /// Whether the rule names a webhook target
+    pub fn names(&self, target: &str) -> bool {
+        self.notify
+            .iter()
+            .chain(&self.escalate_to)
+            .any(|t| t == target)
+    }
+
+    /// Targets told of an alert, with the escalation targets once escalated
+    pub fn recipients(&self, escalated: bool) -> Vec<&str> {
+        let mut targets: Vec<&str> = self.notify.iter().map(String::as_str).collect();
+        if escalated {
+            for target in &self.escalate_to {
+                if !targets.contains(&target.as_str()) {
+                    targets.push(target);
+                }
+            }
+        }
+        targets
+    }
+
+    /// Check that the rule names known rollups and targets and that its
+    /// escalation is complete
+    pub fn validate(&self, rollups: &[String], webhooks: &[WebhookTarget]) -> eyre::Result<()> {
+        if webhooks.iter().any(|w| w.name == self.name) {
+            eyre::bail!("Alert rule {}: name is taken by a webhook", self.name);
+        }
+        if let Some(unknown) = self.rollups.iter().find(|r| !rollups.contains(r)) {
+            eyre::bail!("Alert rule {}: unknown rollup {}", self.name, unknown);
+        }
+        if self.notify.is_empty() {
+            eyre::bail!("Alert rule {}: notify names no webhook", self.name);
+        }
+        if let Some(unknown) = self
+            .notify
+            .iter()
+            .chain(&self.escalate_to)
+            .find(|t| !webhooks.iter().any(|w| &w.name == *t))
+        {
+            eyre::bail!("Alert rule {}: unknown webhook {}", self.name, unknown);
+        }
+        if self.escalate_after_secs.is_some() == self.escalate_to.is_empty() {
+            eyre::bail!(
+                "Alert rule {}: escalate_after_secs and escalate_to go together",
+                self.name
+            );
+        }
+        if self.renotify_secs == Some(0) {
+            eyre::bail!("Alert rule {}: renotify_secs must be positive", self.name);
+        }
+        Ok(())
+    }
+}
+
+/// Rules in effect: the configured ones, then one per webhook none of them
+/// names, so targets without rules keep being alerted on their own filters
+pub fn effective_rules(rules: &[AlertRule], webhooks: &[WebhookTarget]) -> Vec<AlertRule> {
+    let mut effective = rules.to_vec();
+    effective.extend(
+        webhooks
+            .iter()
+            .filter(|w| !rules.iter().any(|r| r.names(&w.name)))
+            .map(AlertRule::for_webhook),
+    );
+    effective
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notifiers::WebhookFormat;
+    use std::collections::BTreeMap;
+
+    fn webhook(name: &str) -> WebhookTarget {
+        WebhookTarget {
+            name: name.to_string(),
+            url: "http://127.0.0.1:1/hook".to_string(),
+            format: WebhookFormat::Json,
+            routing_key: None,
+            rollups: Vec::new(),
+            min_severity: AlertSeverity::Critical,
+            headers: BTreeMap::new(),
+        }
+    }
+
+    fn rule() -> AlertRule {
+        AlertRule {
+            name: "starknet-proofs".to_string(),
+            rollups: vec!["starknet".to_string()],
+            kinds: vec![AlertKind::Health],
+            stages: vec![HealthStage::Proving],
+            min_severity: AlertSeverity::Warning,
+            notify: vec!["chat".to_string()],
+            renotify_secs: Some(1800),
+            escalate_after_secs: Some(3600),
+            escalate_to: vec!["pager".to_string()],
+            flap_window_secs: 300,
+        }
+    }
+
+    #[test]
+    fn test_rule_matching() {
+        let rule = rule();
+        assert!(rule.matches("starknet", AlertKind::Health));
+        assert!(!rule.matches("starknet", AlertKind::Sequencer));
+        assert!(!rule.matches("base", AlertKind::Health));
+        assert!(rule.matches_stage(Some(HealthStage::Proving)));
+        assert!(!rule.matches_stage(Some(HealthStage::Batching)));
+        assert!(!rule.matches_stage(None));
+        assert!(AlertRule::for_webhook(&webhook("pager")).matches_stage(None));
+    }
+
+    #[test]
+    fn test_unnamed_webhooks_get_their_own_rule() {
+        let webhooks = vec![webhook("chat"), webhook("pager"), webhook("ops")];
+        let rules = effective_rules(&[rule()], &webhooks);
+        let names: Vec<&str> = rules.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["starknet-proofs", "ops"]);
+        assert_eq!(rules[1].notify, vec!["ops".to_string()]);
+        assert_eq!(rules[1].min_severity, AlertSeverity::Critical);
+    }
+
+    #[test]
+    fn test_rule_validated() {
+        let rollups = vec!["starknet".to_string()];
+        let webhooks = vec![webhook("chat"), webhook("pager")];
+        rule().validate(&rollups, &webhooks).unwrap();
+
+        let mut bad = rule();
+        bad.escalate_to = vec!["oncall".to_string()];
+        assert!(bad.validate(&rollups, &webhooks).is_err());
+        bad = rule();
+        bad.escalate_after_secs = None;
+        assert!(bad.validate(&rollups, &webhooks).is_err());
+        bad = rule();
+        bad.name = "chat".to_string();
+        assert!(bad.validate(&rollups, &webhooks).is_err());
+        bad = rule();
+        bad.notify.clear();
+        assert!(bad.validate(&rollups, &webhooks).is_err());
+    }
+}
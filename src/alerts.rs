@@ -0,0 +1,1216 @@
+use crate::alert_rules::AlertRule;
+use crate::config::AlertConfig;
+use crate::health::{HealthCheckResult, HealthMonitor, HealthStage};
+use crate::notifiers::{self, WebhookFormat};
+use crate::store::Store;
+use crate::types::{HealthStatus, SequencerStatus, StreamMessage, StreamNotice};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::{broadcast, mpsc};
+use tokio_util::sync::CancellationToken;
+
+/// How urgent an alert is
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertSeverity {
+    /// Degraded: a rollup is `Delayed`
+    #[default]
+    Warning,
+    /// Down: a rollup is `Halted` or unwatched, or its sequencer stopped
+    Critical,
+}
+
+impl AlertSeverity {
+    /// Severity of a rollup in the given status, `None` if nothing is wrong
+    pub fn for_status(status: &HealthStatus) -> Option<Self> {
+        match status {
+            HealthStatus::Delayed => Some(Self::Warning),
+            HealthStatus::Halted | HealthStatus::Disconnected => Some(Self::Critical),
+            HealthStatus::Healthy | HealthStatus::Maintenance => None,
+        }
+    }
+}
+
+/// What an alert is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertKind {
+    /// The rollup's overall health status
+    Health,
+    /// Its L2 sequencer producing blocks
+    Sequencer,
+}
+
+/// Whether an alert opens or closes a problem
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertState {
+    Triggered,
+    Resolved,
+}
+
+/// Why an alert is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertCause {
+    /// The status changed
+    Change,
+    /// The problem is still unresolved after the rule's `renotify_secs`
+    Reminder,
+    /// The problem outlasted the rule's `escalate_after_secs`
+    Escalation,
+}
+
+/// A health or sequencer status change alerts are raised for
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Transition {
+    pub rollup: String,
+    pub kind: AlertKind,
+    pub previous: String,
+    pub status: String,
+    /// Severity of the new status, `None` once nothing is wrong
+    pub severity: Option<AlertSeverity>,
+    pub reasons: Vec<String>,
+    /// Unix timestamp of the change
+    pub at: u64,
+    /// New sequencer status, for sequencer transitions
+    pub sequencer: Option<SequencerStatus>,
+}
+
+impl Transition {
+    /// Transition announced by a stream notice, if it is one alerts cover
+    pub fn from_notice(notice: &StreamNotice) -> Option<Self> {
+        match notice {
+            StreamNotice::HealthChanged(change) => Some(Self {
+                rollup: change.rollup.clone(),
+                kind: AlertKind::Health,
+                previous: format!("{:?}", change.previous),
+                status: format!("{:?}", change.status),
+                severity: AlertSeverity::for_status(&change.status),
+                reasons: change.reasons.clone(),
+                at: change.at,
+                sequencer: None,
+            }),
+            StreamNotice::SequencerChanged(change) => Some(Self {
+                rollup: change.rollup.clone(),
+                kind: AlertKind::Sequencer,
+                previous: producing(&change.previous).to_string(),
+                status: producing(&change.status).to_string(),
+                severity: (!change.status.is_producing).then_some(AlertSeverity::Critical),
+                reasons: change.reasons.clone(),
+                at: change.at,
+                sequencer: Some(change.status.clone()),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Stage the problem lies in: the sequencer's, or the stage that
+    /// degraded the rollup most, `None` if only the time since any event did
+    fn stage(&self, health: &HealthCheckResult) -> Option<HealthStage> {
+        match self.kind {
+            AlertKind::Health => health.incident.as_ref().and_then(|i| i.stage),
+            AlertKind::Sequencer => Some(HealthStage::Sequencer),
+        }
+    }
+
+    /// Key identifying a problem across its alerts, per rollup and stage
+    fn dedup_key(&self, stage: Option<HealthStage>) -> String {
+        match (self.kind, stage) {
+            (AlertKind::Sequencer, _) => format!("{}/sequencer", self.rollup),
+            (AlertKind::Health, Some(stage)) => {
+                format!("{}/health/{}", self.rollup, stage.name())
+            }
+            (AlertKind::Health, None) => format!("{}/health/overall", self.rollup),
+        }
+    }
+
+    /// One-line description of the change
+    fn summary(&self, state: AlertState, cause: AlertCause) -> String {
+        let summary = self.describe(state);
+        match cause {
+            AlertCause::Change => summary,
+            AlertCause::Reminder => format!("Still unresolved: {}", summary),
+            AlertCause::Escalation => format!("Escalated: {}", summary),
+        }
+    }
+
+    fn describe(&self, state: AlertState) -> String {
+        match (self.kind, state) {
+            (AlertKind::Health, AlertState::Triggered) => {
+                format!("{} is {} (was {})", self.rollup, self.status, self.previous)
+            }
+            (AlertKind::Health, AlertState::Resolved) => format!(
+                "{} recovered: {} (was {})",
+                self.rollup, self.status, self.previous
+            ),
+            (AlertKind::Sequencer, AlertState::Triggered) => {
+                format!("{} sequencer stopped producing blocks", self.rollup)
+            }
+            (AlertKind::Sequencer, AlertState::Resolved) => {
+                format!("{} sequencer producing blocks again", self.rollup)
+            }
+        }
+    }
+}
+
+fn producing(status: &SequencerStatus) -> &'static str {
+    if status.is_producing {
+        "producing"
+    } else {
+        "down"
+    }
+}
+
+/// JSON body posted to webhook targets
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Alert {
+    pub rollup: String,
+    pub kind: AlertKind,
+    pub state: AlertState,
+    pub cause: AlertCause,
+    /// Alert rule that routed it
+    pub rule: String,
+    /// Severity of the new status, or of the one resolved
+    pub severity: AlertSeverity,
+    pub summary: String,
+    /// Stage the problem lies in, if any one stage
+    pub stage: Option<HealthStage>,
+    /// Key shared by every alert about one problem, from its first trigger
+    /// to its resolution
+    pub dedup_key: String,
+    pub previous: String,
+    pub status: String,
+    pub reasons: Vec<String>,
+    /// Unix timestamp of the change
+    pub at: u64,
+    /// Rollup health when the alert was raised
+    pub health: HealthCheckResult,
+    /// Sequencer status, for sequencer alerts
+    pub sequencer: Option<SequencerStatus>,
+}
+
+/// An HTTP endpoint alerts are posted to
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookTarget {
+    /// Name identifying the target in the delivery log
+    pub name: String,
+    pub url: String,
+    /// Body the alert is rendered as
+    #[serde(default)]
+    pub format: WebhookFormat,
+    /// PagerDuty integration key, for the `pagerduty` format
+    #[serde(default)]
+    pub routing_key: Option<String>,
+    /// Rollups alerted on, every rollup if empty
+    #[serde(default)]
+    pub rollups: Vec<String>,
+    /// Least severe status alerted on
+    #[serde(default)]
+    pub min_severity: AlertSeverity,
+    /// Extra request headers, such as `Authorization`
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+}
+
+impl WebhookTarget {
+    /// Whether the target is alerted on a rollup
+    pub fn matches(&self, rollup: &str) -> bool {
+        self.rollups.is_empty() || self.rollups.iter().any(|r| r == rollup)
+    }
+
+    /// Check the URL and that only known rollups are listed
+    pub fn validate(&self, rollups: &[String]) -> eyre::Result<()> {
+        if !self.url.starts_with("http://") && !self.url.starts_with("https://") {
+            eyre::bail!("Webhook {}: url must be http(s)", self.name);
+        }
+        if let Some(unknown) = self.rollups.iter().find(|r| !rollups.contains(r)) {
+            eyre::bail!("Webhook {}: unknown rollup {}", self.name, unknown);
+        }
+        if self.format == WebhookFormat::PagerDuty && self.routing_key.is_none() {
+            eyre::bail!("Webhook {}: pagerduty needs a routing_key", self.name);
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of delivering one alert to one target
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AlertDelivery {
+    /// Row id, once persisted
+    pub id: Option<u64>,
+    pub target: String,
+    pub rollup: String,
+    pub kind: AlertKind,
+    pub state: AlertState,
+    pub severity: AlertSeverity,
+    pub summary: String,
+    /// Requests made, including the successful one
+    pub attempts: u32,
+    pub delivered: bool,
+    /// HTTP status of the last response
+    pub status_code: Option<u16>,
+    /// Error of the last failed attempt
+    pub error: Option<String>,
+    /// Unix timestamp of the change alerted on
+    pub at: u64,
+    /// Unix timestamp the last attempt finished
+    pub finished_at: u64,
+}
+
+/// A problem an alert rule opened that has not cleared yet; stored so a
+/// restart neither pages it again nor loses its resolution
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenAlert {
+    /// Alert rule it was opened under
+    pub rule: String,
+    /// Latest transition of the problem
+    pub transition: Transition,
+    /// Severity of the latest transition
+    pub severity: AlertSeverity,
+    /// Stage the problem last lay in
+    pub stage: Option<HealthStage>,
+    /// Key shared by the problem's alerts, fixed when it opened
+    pub dedup_key: String,
+    /// Unix timestamp the problem opened
+    pub opened_at: u64,
+    /// Unix timestamp targets were last notified, `None` while the trigger
+    /// is held back as possibly flapping
+    pub notified_at: Option<u64>,
+    /// Whether the rule's `escalate_to` targets were notified
+    pub escalated: bool,
+}
+
+impl OpenAlert {
+    /// Key an open alert is tracked and stored under
+    fn key(rule: &str, rollup: &str, kind: AlertKind) -> String {
+        let kind = match kind {
+            AlertKind::Health => "health",
+            AlertKind::Sequencer => "sequencer",
+        };
+        format!("{}/{}/{}", rule, rollup, kind)
+    }
+}
+
+/// Severity a rule alerts on for a transition, `None` if the problem is
+/// below its `min_severity` or, unless already `open`, outside its stages.
+/// Once open, a problem stays with the rule whichever stage it moves to.
+fn firing(
+    rule: &AlertRule,
+    transition: &Transition,
+    stage: Option<HealthStage>,
+    open: bool,
+) -> Option<AlertSeverity> {
+    transition
+        .severity
+        .filter(|severity| *severity >= rule.min_severity && (open || rule.matches_stage(stage)))
+}
+
+/// Sends one target's alerts about one problem to the task delivering them
+type DeliveryQueue = mpsc::UnboundedSender<(WebhookTarget, Alert)>;
+
+/// Posts alerts for health and sequencer transitions to the webhook targets
+/// the alert rules name, follows up on unresolved problems with reminders and
+/// escalations, and retries failed deliveries, logging each outcome
+#[derive(Clone)]
+pub struct AlertDispatcher {
+    monitor: HealthMonitor,
+    client: reqwest::Client,
+    config: AlertConfig,
+    store: Option<Store>,
+    /// Problems opened and not yet resolved, per rule, rollup and kind
+    open: Arc<Mutex<HashMap<String, OpenAlert>>>,
+    /// Delivery queues, per target and dedup key
+    queues: Arc<Mutex<HashMap<(String, String), DeliveryQueue>>>,
+}
+
+impl AlertDispatcher {
+    /// Alert on the targets configured in `monitor`'s health config
+    pub fn new(monitor: HealthMonitor, config: AlertConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .unwrap_or_default();
+        Self {
+            monitor,
+            client,
+            config,
+            store: None,
+            open: Arc::new(Mutex::new(HashMap::new())),
+            queues: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Record deliveries and open alerts in the given store
+    pub fn with_store(mut self, store: Store) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Take over the alerts left open by the previous run
+    pub fn restore(&self) -> eyre::Result<()> {
+        let Some(store) = &self.store else {
+            return Ok(());
+        };
+        *self.lock_open() = store.open_alerts()?;
+        Ok(())
+    }
+
+    /// Alerts to send for a transition, one per target notified.
+    ///
+    /// Each rule covering the rollup and kind opens an alert while the new
+    /// status is at least its `min_severity` and the problem lies in one of
+    /// its stages. Once the problem has outlasted the rule's flap window its
+    /// targets are notified of every change, and of the resolution when the
+    /// status drops back below; problems that clear sooner are never sent.
+    pub fn route(&self, transition: &Transition) -> Vec<(WebhookTarget, Alert)> {
+        let health = self.monitor.check_health(&transition.rollup);
+        let stage = transition.stage(&health);
+        let webhooks = self.monitor.webhooks();
+        let mut open = self.lock_open();
+
+        let mut alerts = Vec::new();
+        for rule in self.monitor.alert_rules() {
+            if rule.matches(&transition.rollup, transition.kind) {
+                alerts.extend(
+                    self.route_rule(&rule, transition, stage, &health, &webhooks, &mut open),
+                );
+            }
+        }
+        alerts
+    }
+
+    /// Alerts for the transitions the dispatcher missed, such as those
+    /// dropped while it lagged behind the stream or made while it was down.
+    ///
+    /// The current health and sequencer status of every rollup that has been
+    /// observed is compared with the open alerts: problems that cleared are
+    /// resolved and problems no rule has open yet are opened, as if their
+    /// transitions had been routed.
+    pub fn resync(&self) -> Vec<(WebhookTarget, Alert)> {
+        let rules = self.monitor.alert_rules();
+        let webhooks = self.monitor.webhooks();
+
+        let mut alerts = Vec::new();
+        for rollup in self.monitor.rollups() {
+            let health = self.monitor.check_health(rollup);
+            // Nothing was ever observed, so no transition was either
+            if health.last_event_age_secs.is_none() {
+                continue;
+            }
+            let mut open = self.lock_open();
+            for transition in self.current_transitions(&health, &open) {
+                let stage = transition.stage(&health);
+                for rule in &rules {
+                    if !rule.matches(rollup, transition.kind) {
+                        continue;
+                    }
+                    let key = OpenAlert::key(&rule.name, rollup, transition.kind);
+                    let is_open = open.contains_key(&key);
+                    if firing(rule, &transition, stage, is_open).is_some() == is_open {
+                        continue;
+                    }
+                    tracing::info!(
+                        rule = %rule.name,
+                        rollup = %rollup,
+                        kind = ?transition.kind,
+                        status = %transition.status,
+                        "Alert out of date with current status, resyncing"
+                    );
+                    alerts.extend(self.route_rule(
+                        rule,
+                        &transition,
+                        stage,
+                        &health,
+                        &webhooks,
+                        &mut open,
+                    ));
+                }
+            }
+        }
+        alerts
+    }
+
+    /// Transitions to a rollup's current health and sequencer status, from
+    /// the status its open alerts last saw or from a healthy one
+    fn current_transitions(
+        &self,
+        health: &HealthCheckResult,
+        open: &HashMap<String, OpenAlert>,
+    ) -> Vec<Transition> {
+        let now = self.monitor.now();
+        let last = |kind| {
+            open.values()
+                .find(|a| a.transition.rollup == health.rollup && a.transition.kind == kind)
+                .map(|a| a.transition.clone())
+        };
+
+        let mut transitions = vec![Transition {
+            rollup: health.rollup.clone(),
+            kind: AlertKind::Health,
+            previous: last(AlertKind::Health)
+                .map_or_else(|| format!("{:?}", HealthStatus::Healthy), |t| t.status),
+            status: format!("{:?}", health.status),
+            severity: AlertSeverity::for_status(&health.status),
+            reasons: health.issues.clone(),
+            at: now,
+            sequencer: None,
+        }];
+        // An unpolled sequencer is neither producing nor down
+        if let Some(down) = health.sequencer_down {
+            let last = last(AlertKind::Sequencer);
+            transitions.push(Transition {
+                rollup: health.rollup.clone(),
+                kind: AlertKind::Sequencer,
+                previous: last
+                    .as_ref()
+                    .map_or_else(|| "producing".to_string(), |t| t.status.clone()),
+                status: if down { "down" } else { "producing" }.to_string(),
+                severity: down.then_some(AlertSeverity::Critical),
+                reasons: health
+                    .issues
+                    .iter()
+                    .filter(|issue| issue.starts_with("Sequencer "))
+                    .cloned()
+                    .collect(),
+                at: now,
+                sequencer: last.and_then(|t| t.sequencer),
+            });
+        }
+        transitions
+    }
+
+    /// Alerts one rule sends for a transition it covers, opening, updating
+    /// or resolving its alert in `open`
+    fn route_rule(
+        &self,
+        rule: &AlertRule,
+        transition: &Transition,
+        stage: Option<HealthStage>,
+        health: &HealthCheckResult,
+        webhooks: &[WebhookTarget],
+        open: &mut HashMap<String, OpenAlert>,
+    ) -> Vec<(WebhookTarget, Alert)> {
+        let now = self.monitor.now();
+        let key = OpenAlert::key(&rule.name, &transition.rollup, transition.kind);
+        let Some(severity) = firing(rule, transition, stage, open.contains_key(&key)) else {
+            let Some(mut alert) = open.remove(&key) else {
+                return Vec::new();
+            };
+            self.forget(&key);
+            if alert.notified_at.is_none() {
+                tracing::info!(
+                    rule = %rule.name,
+                    rollup = %transition.rollup,
+                    "Problem cleared within the flap window, not alerted"
+                );
+                return Vec::new();
+            }
+            alert.transition = transition.clone();
+            let targets = rule.recipients(alert.escalated);
+            return self.alerts_for(
+                webhooks,
+                &targets,
+                &alert,
+                AlertState::Resolved,
+                AlertCause::Change,
+                health,
+            );
+        };
+
+        let alert = open.entry(key.clone()).or_insert_with(|| OpenAlert {
+            rule: rule.name.clone(),
+            transition: transition.clone(),
+            severity,
+            stage,
+            dedup_key: transition.dedup_key(stage),
+            opened_at: now,
+            notified_at: None,
+            escalated: false,
+        });
+        // Already notified of this status, e.g. by a resync ahead of its transition
+        if alert.notified_at.is_some()
+            && alert.transition.status == transition.status
+            && alert.severity == severity
+        {
+            return Vec::new();
+        }
+        alert.transition = transition.clone();
+        alert.severity = severity;
+        alert.stage = stage;
+        let mut alerts = Vec::new();
+        if alert.notified_at.is_some() || now >= alert.opened_at + rule.flap_window_secs {
+            alert.notified_at = Some(now);
+            let targets = rule.recipients(alert.escalated);
+            alerts = self.alerts_for(
+                webhooks,
+                &targets,
+                alert,
+                AlertState::Triggered,
+                AlertCause::Change,
+                health,
+            );
+        }
+        self.save(&key, alert);
+        alerts
+    }
+
+    /// Alerts due on open problems: triggers held back that outlasted the
+    /// flap window, escalations and reminders.
+    ///
+    /// Open alerts are first resynced with the current status, so problems
+    /// that cleared unnoticed are resolved rather than reminded of.
+    pub fn tick(&self) -> Vec<(WebhookTarget, Alert)> {
+        let mut alerts = self.resync();
+        let now = self.monitor.now();
+        let rules = self.monitor.alert_rules();
+        let webhooks = self.monitor.webhooks();
+        let mut open = self.lock_open();
+
+        // Problems of rules dropped from the config would never resolve
+        open.retain(|key, alert| {
+            let kept = rules.iter().any(|r| r.name == alert.rule);
+            if !kept {
+                self.forget(key);
+            }
+            kept
+        });
+
+        for (key, alert) in open.iter_mut() {
+            let Some(rule) = rules.iter().find(|r| r.name == alert.rule) else {
+                continue;
+            };
+            let escalation_due = rule
+                .escalate_after_secs
+                .is_some_and(|after| !alert.escalated && now >= alert.opened_at + after);
+            let (targets, cause) = match alert.notified_at {
+                None if now >= alert.opened_at + rule.flap_window_secs => {
+                    (rule.recipients(false), AlertCause::Change)
+                }
+                Some(_) if escalation_due => {
+                    alert.escalated = true;
+                    let targets = rule.escalate_to.iter().map(String::as_str).collect();
+                    (targets, AlertCause::Escalation)
+                }
+                Some(notified_at)
+                    if rule
+                        .renotify_secs
+                        .is_some_and(|every| now >= notified_at + every) =>
+                {
+                    (rule.recipients(alert.escalated), AlertCause::Reminder)
+                }
+                _ => continue,
+            };
+            alert.notified_at = Some(now);
+            let health = self.monitor.check_health(&alert.transition.rollup);
+            alerts.extend(self.alerts_for(
+                &webhooks,
+                &targets,
+                alert,
+                AlertState::Triggered,
+                cause,
+                &health,
+            ));
+            self.save(key, alert);
+        }
+        alerts
+    }
+
+    /// An open problem's alert for each of the named targets
+    fn alerts_for(
+        &self,
+        webhooks: &[WebhookTarget],
+        targets: &[&str],
+        open: &OpenAlert,
+        state: AlertState,
+        cause: AlertCause,
+        health: &HealthCheckResult,
+    ) -> Vec<(WebhookTarget, Alert)> {
+        let transition = &open.transition;
+        let alert = Alert {
+            rollup: transition.rollup.clone(),
+            kind: transition.kind,
+            state,
+            cause,
+            rule: open.rule.clone(),
+            severity: open.severity,
+            summary: transition.summary(state, cause),
+            stage: open.stage,
+            dedup_key: open.dedup_key.clone(),
+            previous: transition.previous.clone(),
+            status: transition.status.clone(),
+            reasons: transition.reasons.clone(),
+            at: transition.at,
+            health: health.clone(),
+            sequencer: transition.sequencer.clone(),
+        };
+        targets
+            .iter()
+            .filter_map(|name| webhooks.iter().find(|w| w.name == *name))
+            .map(|target| (target.clone(), alert.clone()))
+            .collect()
+    }
+
+    /// Persist an open alert so a restart picks it up
+    fn save(&self, key: &str, alert: &OpenAlert) {
+        if let Some(store) = &self.store {
+            if let Err(e) = store.save_open_alert(key, alert) {
+                tracing::error!(error = ?e, "Failed to persist open alert");
+            }
+        }
+    }
+
+    /// Drop a resolved alert from the store
+    fn forget(&self, key: &str) {
+        if let Some(store) = &self.store {
+            if let Err(e) = store.delete_open_alert(key) {
+                tracing::error!(error = ?e, "Failed to delete open alert");
+            }
+        }
+    }
+
+    fn lock_open(&self) -> MutexGuard<'_, HashMap<String, OpenAlert>> {
+        match self.open.lock() {
+            Ok(open) => open,
+            Err(poisoned) => {
+                tracing::error!("Mutex poisoned in alert routing, recovering");
+                poisoned.into_inner()
+            }
+        }
+    }
+
+    /// Deliver an alert in the background, after the alerts queued before it
+    /// for the same target and problem, so a resolution never overtakes the
+    /// trigger it closes while that is being retried
+    pub fn enqueue(&self, target: WebhookTarget, alert: Alert) {
+        let key = (target.name.clone(), alert.dedup_key.clone());
+        let mut queues = match self.queues.lock() {
+            Ok(queues) => queues,
+            Err(poisoned) => {
+                tracing::error!("Mutex poisoned in alert queues, recovering");
+                poisoned.into_inner()
+            }
+        };
+        let mut item = (target, alert);
+        if let Some(queue) = queues.get(&key) {
+            match queue.send(item) {
+                Ok(()) => return,
+                Err(mpsc::error::SendError(returned)) => item = returned,
+            }
+        }
+        let (queue, mut rx) = mpsc::unbounded_channel();
+        let _ = queue.send(item);
+        queues.insert(key, queue);
+        let dispatcher = self.clone();
+        tokio::spawn(async move {
+            while let Some((target, alert)) = rx.recv().await {
+                dispatcher.deliver(&target, &alert).await;
+            }
+        });
+    }
+
+    /// Post an alert to a target, retrying with backoff on network errors,
+    /// 429 and 5xx responses, then record the outcome
+    pub async fn deliver(&self, target: &WebhookTarget, alert: &Alert) -> AlertDelivery {
+        let mut delivery = AlertDelivery {
+            id: None,
+            target: target.name.clone(),
+            rollup: alert.rollup.clone(),
+            kind: alert.kind,
+            state: alert.state,
+            severity: alert.severity,
+            summary: alert.summary.clone(),
+            attempts: 0,
+            delivered: false,
+            status_code: None,
+            error: None,
+            at: alert.at,
+            finished_at: alert.at,
+        };
+
+        while delivery.attempts < self.config.max_attempts.max(1) {
+            if delivery.attempts > 0 {
+                tokio::time::sleep(self.config.backoff_for_attempt(delivery.attempts)).await;
+            }
+            delivery.attempts += 1;
+
+            let mut request = self
+                .client
+                .post(&target.url)
+                .json(&notifiers::render(target, alert));
+            for (name, value) in &target.headers {
+                request = request.header(name, value);
+            }
+            let retry = match request.send().await {
+                Ok(response) => {
+                    let status = response.status();
+                    delivery.status_code = Some(status.as_u16());
+                    if status.is_success() {
+                        delivery.delivered = true;
+                        delivery.error = None;
+                        break;
+                    }
+                    delivery.error = Some(format!("HTTP {}", status));
+                    status.is_server_error() || status.as_u16() == 429
+                }
+                Err(e) => {
+                    delivery.status_code = None;
+                    // The URL is the credential of Slack and Discord webhooks
+                    delivery.error = Some(e.without_url().to_string());
+                    true
+                }
+            };
+            if !retry {
+                break;
+            }
+            tracing::warn!(
+                target = %target.name,
+                rollup = %alert.rollup,
+                attempt = delivery.attempts,
+                error = ?delivery.error,
+                "Alert delivery failed"
+            );
+        }
+        delivery.finished_at = self.monitor.now();
+
+        if delivery.delivered {
+            tracing::info!(
+                target = %target.name,
+                rollup = %alert.rollup,
+                summary = %alert.summary,
+                attempts = delivery.attempts,
+                "Alert delivered"
+            );
+        } else {
+            tracing::error!(
+                target = %target.name,
+                rollup = %alert.rollup,
+                summary = %alert.summary,
+                attempts = delivery.attempts,
+                error = ?delivery.error,
+                "Giving up on alert delivery"
+            );
+        }
+        if let Some(store) = &self.store {
+            match store.insert_delivery(&delivery) {
+                Ok(id) => delivery.id = Some(id),
+                Err(e) => tracing::error!(error = ?e, "Failed to persist alert delivery"),
+            }
+        }
+        delivery
+    }
+}
+
+/// Raise alerts for the health and sequencer transitions published on the
+/// stream and follow up on open problems every `policy_interval`, delivering
+/// alerts in the background so retries only hold up those about the same
+/// problem. Transitions dropped while lagging behind the stream are made up
+/// for by resyncing with the current status.
+pub async fn start_alerting(
+    dispatcher: AlertDispatcher,
+    mut rx: broadcast::Receiver<StreamMessage>,
+    cancel_token: CancellationToken,
+) {
+    tracing::info!("Alerting started");
+    let mut policy = tokio::time::interval(dispatcher.config.policy_interval);
+    policy.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        let alerts = tokio::select! {
+            message = rx.recv() => {
+                let notice = match message {
+                    Ok(StreamMessage::Notice(notice)) => notice,
+                    Ok(StreamMessage::Event(_)) => continue,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!(skipped = skipped, "Alerting lagged, resyncing with current status");
+                        for (target, alert) in dispatcher.resync() {
+                            dispatcher.enqueue(target, alert);
+                        }
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return,
+                };
+                let Some(transition) = Transition::from_notice(&notice) else {
+                    continue;
+                };
+                dispatcher.route(&transition)
+            }
+            _ = policy.tick() => dispatcher.tick(),
+            _ = cancel_token.cancelled() => {
+                tracing::info!("Alerting shutting down");
+                return;
+            }
+        };
+
+        for (target, alert) in alerts {
+            dispatcher.enqueue(target, alert);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::Clock;
+    use crate::health::HealthConfig;
+    use crate::types::{EventStatus, HealthChange, RollupEvent};
+
+    fn target(name: &str, min_severity: AlertSeverity) -> WebhookTarget {
+        WebhookTarget {
+            name: name.to_string(),
+            url: "http://127.0.0.1:1/hook".to_string(),
+            format: WebhookFormat::Json,
+            routing_key: None,
+            rollups: vec!["arbitrum".to_string()],
+            min_severity,
+            headers: BTreeMap::new(),
+        }
+    }
+
+    fn event(event_type: &str, timestamp: u64) -> RollupEvent {
+        RollupEvent {
+            rollup: "arbitrum".to_string(),
+            event_type: event_type.to_string(),
+            block_number: 1,
+            tx_hash: "0xabc".to_string(),
+            batch_number: None,
+            timestamp: Some(timestamp),
+            observed_at: None,
+            status: EventStatus::Confirmed,
+        }
+    }
+
+    fn health_change(previous: HealthStatus, status: HealthStatus) -> Transition {
+        Transition::from_notice(&StreamNotice::HealthChanged(HealthChange {
+            rollup: "arbitrum".to_string(),
+            previous,
+            status,
+            reasons: vec!["No batch for 900 seconds".to_string()],
+            at: 1_700_000_000,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_routing_by_severity() {
+        let monitor = HealthMonitor::new();
+        monitor.set_config(HealthConfig {
+            webhooks: vec![
+                target("chat", AlertSeverity::Warning),
+                target("pager", AlertSeverity::Critical),
+            ],
+            ..HealthConfig::default()
+        });
+        let dispatcher = AlertDispatcher::new(monitor, AlertConfig::default());
+        let routed = |previous, status| {
+            dispatcher
+                .route(&health_change(previous, status))
+                .into_iter()
+                .map(|(target, alert)| (target.name, alert.state, alert.severity))
+                .collect::<Vec<_>>()
+        };
+
+        // Nothing was wrong, so a startup transition alerts nobody
+        assert!(routed(HealthStatus::Disconnected, HealthStatus::Healthy).is_empty());
+        assert_eq!(
+            routed(HealthStatus::Healthy, HealthStatus::Delayed),
+            vec![(
+                "chat".to_string(),
+                AlertState::Triggered,
+                AlertSeverity::Warning
+            )]
+        );
+        assert_eq!(
+            routed(HealthStatus::Delayed, HealthStatus::Halted),
+            vec![
+                (
+                    "chat".to_string(),
+                    AlertState::Triggered,
+                    AlertSeverity::Critical
+                ),
+                (
+                    "pager".to_string(),
+                    AlertState::Triggered,
+                    AlertSeverity::Critical
+                ),
+            ]
+        );
+        // Back under the pager's threshold: resolved there, still open in chat
+        assert_eq!(
+            routed(HealthStatus::Halted, HealthStatus::Delayed),
+            vec![
+                (
+                    "chat".to_string(),
+                    AlertState::Triggered,
+                    AlertSeverity::Warning
+                ),
+                (
+                    "pager".to_string(),
+                    AlertState::Resolved,
+                    AlertSeverity::Critical
+                ),
+            ]
+        );
+        let (_, alert) = dispatcher
+            .route(&health_change(HealthStatus::Delayed, HealthStatus::Healthy))
+            .remove(0);
+        assert_eq!(alert.state, AlertState::Resolved);
+        assert_eq!(alert.summary, "arbitrum recovered: Healthy (was Delayed)");
+        assert_eq!(alert.dedup_key, "arbitrum/health/overall");
+        assert_eq!(alert.health.rollup, "arbitrum");
+
+        let mut other = health_change(HealthStatus::Healthy, HealthStatus::Halted);
+        other.rollup = "base".to_string();
+        assert!(dispatcher.route(&other).is_empty());
+    }
+
+    /// Dispatcher whose only rule notifies `chat`, reminding every 30
+    /// minutes and paging after an hour, with a 5 minute flap window
+    fn escalating(clock: &Clock, store: &Store) -> AlertDispatcher {
+        let monitor = HealthMonitor::new().with_clock(clock.clone());
+        monitor.set_config(HealthConfig {
+            webhooks: vec![
+                target("chat", AlertSeverity::Warning),
+                target("pager", AlertSeverity::Warning),
+            ],
+            alert_rules: vec![AlertRule {
+                name: "arbitrum-health".to_string(),
+                rollups: vec!["arbitrum".to_string()],
+                kinds: vec![AlertKind::Health],
+                stages: Vec::new(),
+                min_severity: AlertSeverity::Warning,
+                notify: vec!["chat".to_string()],
+                renotify_secs: Some(1800),
+                escalate_after_secs: Some(3600),
+                escalate_to: vec!["pager".to_string()],
+                flap_window_secs: 300,
+            }],
+            ..HealthConfig::default()
+        });
+        AlertDispatcher::new(monitor, AlertConfig::default()).with_store(store.clone())
+    }
+
+    fn sent(alerts: Vec<(WebhookTarget, Alert)>) -> Vec<(String, AlertState, AlertCause)> {
+        alerts
+            .into_iter()
+            .map(|(target, alert)| (target.name, alert.state, alert.cause))
+            .collect()
+    }
+
+    #[test]
+    fn test_flapping_problem_not_alerted() {
+        let start = 1_700_000_000;
+        let clock = Clock::manual(start);
+        let store = Store::open_in_memory().unwrap();
+        let dispatcher = escalating(&clock, &store);
+
+        // Cleared within the flap window: nobody hears of it
+        assert!(dispatcher
+            .route(&health_change(HealthStatus::Healthy, HealthStatus::Delayed))
+            .is_empty());
+        clock.set(start + 120);
+        assert!(dispatcher
+            .route(&health_change(HealthStatus::Delayed, HealthStatus::Healthy))
+            .is_empty());
+        assert!(store.open_alerts().unwrap().is_empty());
+
+        // Still delayed once the window has passed: the held trigger goes out
+        assert!(dispatcher
+            .route(&health_change(HealthStatus::Healthy, HealthStatus::Delayed))
+            .is_empty());
+        clock.set(start + 419);
+        assert!(dispatcher.tick().is_empty());
+        clock.set(start + 420);
+        assert_eq!(
+            sent(dispatcher.tick()),
+            vec![(
+                "chat".to_string(),
+                AlertState::Triggered,
+                AlertCause::Change
+            )]
+        );
+        assert!(dispatcher.tick().is_empty());
+    }
+
+    #[test]
+    fn test_reminders_escalation_and_restart() {
+        let start = 1_700_000_000;
+        let clock = Clock::manual(start);
+        let store = Store::open_in_memory().unwrap();
+        let dispatcher = escalating(&clock, &store);
+
+        dispatcher.route(&health_change(HealthStatus::Healthy, HealthStatus::Halted));
+        clock.set(start + 300);
+        assert_eq!(sent(dispatcher.tick()).len(), 1);
+        clock.set(start + 2099);
+        assert!(dispatcher.tick().is_empty());
+        clock.set(start + 2100);
+        let reminder = dispatcher.tick();
+        assert_eq!(
+            reminder[0].1.summary,
+            "Still unresolved: arbitrum is Halted (was Healthy)"
+        );
+        assert_eq!(
+            sent(reminder),
+            vec![(
+                "chat".to_string(),
+                AlertState::Triggered,
+                AlertCause::Reminder
+            )]
+        );
+        clock.set(start + 3600);
+        assert_eq!(
+            sent(dispatcher.tick()),
+            vec![(
+                "pager".to_string(),
+                AlertState::Triggered,
+                AlertCause::Escalation
+            )]
+        );
+        clock.set(start + 5400);
+        assert_eq!(
+            sent(dispatcher.tick()),
+            vec![
+                (
+                    "chat".to_string(),
+                    AlertState::Triggered,
+                    AlertCause::Reminder
+                ),
+                (
+                    "pager".to_string(),
+                    AlertState::Triggered,
+                    AlertCause::Reminder
+                ),
+            ]
+        );
+
+        // A restarted dispatcher neither pages again nor forgets to resolve
+        let restarted = escalating(&clock, &store);
+        restarted.restore().unwrap();
+        assert!(restarted.tick().is_empty());
+        let resolved = restarted.route(&health_change(HealthStatus::Halted, HealthStatus::Healthy));
+        assert_eq!(
+            sent(resolved.clone()),
+            vec![
+                ("chat".to_string(), AlertState::Resolved, AlertCause::Change),
+                (
+                    "pager".to_string(),
+                    AlertState::Resolved,
+                    AlertCause::Change
+                ),
+            ]
+        );
+        assert_eq!(resolved[0].1.dedup_key, "arbitrum/health/overall");
+        assert_eq!(resolved[0].1.rule, "arbitrum-health");
+        assert!(store.open_alerts().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_missed_resolution_resolved_instead_of_reminded() {
+        let start = 1_700_000_000;
+        let clock = Clock::manual(start);
+        let store = Store::open_in_memory().unwrap();
+        let dispatcher = escalating(&clock, &store);
+        let monitor = dispatcher.monitor.clone();
+        monitor.record_event(&event("BatchDelivered", start));
+
+        clock.set(start + 2000);
+        monitor.reevaluate();
+        assert_eq!(monitor.get_status("arbitrum"), HealthStatus::Halted);
+        dispatcher.route(&health_change(HealthStatus::Healthy, HealthStatus::Halted));
+        clock.set(start + 2300);
+        assert_eq!(sent(dispatcher.tick()).len(), 1);
+
+        // The recovery's transition never reaches the dispatcher, and the
+        // reminder falls due
+        clock.set(start + 4100);
+        monitor.record_event(&event("BatchDelivered", clock.now()));
+        let alerts = dispatcher.tick();
+        assert_eq!(
+            alerts[0].1.summary,
+            "arbitrum recovered: Healthy (was Halted)"
+        );
+        assert_eq!(
+            sent(alerts),
+            vec![("chat".to_string(), AlertState::Resolved, AlertCause::Change)]
+        );
+        assert!(store.open_alerts().unwrap().is_empty());
+        assert!(dispatcher.tick().is_empty());
+    }
+
+    #[test]
+    fn test_missed_trigger_opened_on_resync() {
+        let start = 1_700_000_000;
+        let clock = Clock::manual(start);
+        let store = Store::open_in_memory().unwrap();
+        let dispatcher = escalating(&clock, &store);
+        let monitor = dispatcher.monitor.clone();
+        monitor.record_event(&event("BatchDelivered", start));
+        assert!(dispatcher.resync().is_empty());
+
+        // Halted without the dispatcher seeing the transition
+        clock.set(start + 2000);
+        monitor.reevaluate();
+        assert!(dispatcher.resync().is_empty());
+        assert_eq!(store.open_alerts().unwrap().len(), 1);
+
+        // Held back for the flap window like any trigger, then sent once
+        clock.set(start + 2300);
+        let alerts = dispatcher.tick();
+        assert_eq!(alerts[0].1.summary, "arbitrum is Halted (was Healthy)");
+        assert_eq!(
+            sent(alerts),
+            vec![(
+                "chat".to_string(),
+                AlertState::Triggered,
+                AlertCause::Change
+            )]
+        );
+        let halted = health_change(HealthStatus::Delayed, HealthStatus::Halted);
+        assert!(dispatcher.route(&halted).is_empty());
+        assert!(dispatcher.resync().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delivery_error_omits_webhook_url() {
+        let mut secret = target("chat", AlertSeverity::Warning);
+        secret.url = "http://127.0.0.1:1/services/T0/B0/s3cr3t".to_string();
+        let config = AlertConfig {
+            max_attempts: 1,
+            ..AlertConfig::default()
+        };
+        let monitor = HealthMonitor::new();
+        monitor.set_config(HealthConfig {
+            webhooks: vec![secret.clone()],
+            ..HealthConfig::default()
+        });
+        let dispatcher = AlertDispatcher::new(monitor, config);
+        let (_, alert) = dispatcher
+            .route(&health_change(HealthStatus::Healthy, HealthStatus::Delayed))
+            .remove(0);
+
+        let delivery = dispatcher.deliver(&secret, &alert).await;
+        assert!(!delivery.delivered);
+        let error = delivery.error.unwrap();
+        assert!(!error.contains("s3cr3t"), "{}", error);
+    }
+
+    #[test]
+    fn test_webhook_target_validated() {
+        let rollups = vec!["arbitrum".to_string()];
+        target("chat", AlertSeverity::Warning)
+            .validate(&rollups)
+            .unwrap();
+
+        let mut bad = target("chat", AlertSeverity::Warning);
+        bad.url = "ftp://example.com".to_string();
+        assert!(bad.validate(&rollups).is_err());
+        bad = target("chat", AlertSeverity::Warning);
+        bad.rollups = vec!["polygon".to_string()];
+        assert!(bad.validate(&rollups).is_err());
+    }
+}
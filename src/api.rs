@@ -0,0 +1,574 @@
+use crate::endpoints::{self, EndpointPool};
+use crate::health::{HealthMonitor, HealthStage};
+use crate::maintenance::MaintenanceWindow;
+use crate::metrics;
+use crate::registry::RollupRegistry;
+use crate::types::{AppState, EventStatus, RollupEvent};
+use crate::uptime::{UptimeWindow, ROLLING_WINDOWS};
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::http::{header, HeaderMap, Method};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{delete, get, post},
+    Json, Router,
+};
+use tokio::sync::broadcast;
+use tower_http::cors::{Any, CorsLayer};
+
+/// Combined state for API handlers
+#[derive(Clone)]
+pub struct ApiState {
+    pub app: AppState,
+    pub health: HealthMonitor,
+    pub registry: RollupRegistry,
+    pub endpoints: EndpointPool,
+    /// Bearer token guarding the write routes, which refuse every request if unset
+    pub admin_token: Option<String>,
+}
+
+/// Build the REST and WebSocket routes over the given state
+pub fn router(state: ApiState) -> Router {
+    // CORS configuration for cross-origin requests from frontend
+    let cors = CorsLayer::new()
+        .allow_origin(Any)
+        .allow_methods([Method::GET, Method::POST, Method::DELETE, Method::OPTIONS])
+        .allow_headers([header::CONTENT_TYPE, header::AUTHORIZATION]);
+
+    Router::new()
+        .route("/", get(root))
+        .route("/health", get(service_health))
+        .route("/rollups", get(list_rollups))
+        .route("/rollups/health", get(get_all_health))
+        .route("/rollups/sequencer", get(get_all_sequencer))
+        .route("/rollups/{name}/status", get(get_rollup_status))
+        .route("/rollups/{name}/health", get(get_rollup_health))
+        .route("/rollups/{name}/sequencer", get(get_rollup_sequencer))
+        .route("/rollups/{name}/events", get(get_rollup_events))
+        .route(
+            "/rollups/{name}/health/history",
+            get(get_rollup_health_history),
+        )
+        .route("/rollups/{name}/incidents", get(get_rollup_incidents))
+        .route("/rollups/{name}/uptime", get(get_rollup_uptime))
+        .route("/rollups/{name}/maintenance", post(post_rollup_maintenance))
+        .route("/incidents", get(get_incidents))
+        .route("/maintenance", get(get_maintenance))
+        .route("/maintenance/{id}", delete(delete_maintenance))
+        .route("/alerts/deliveries", get(get_alert_deliveries))
+        .route("/alerts/open", get(get_open_alerts))
+        .route("/l1/endpoints", get(get_l1_endpoints))
+        .route("/watchers", get(get_watchers))
+        .route("/metrics", get(get_metrics))
+        .route("/rollups/stream", get(ws_handler))
+        .route("/test/event", post(post_test_event))
+        .layer(cors)
+        .with_state(state)
+}
+
+// ------------------------------------------
+// REST Endpoints
+// ------------------------------------------
+
+async fn root() -> &'static str {
+    "Rollup Proof Status API - Track L2 rollup commitments on Ethereum L1"
+}
+
+async fn service_health() -> impl IntoResponse {
+    Json(serde_json::json!({
+        "status": "ok",
+        "service": "rollup-proof-status"
+    }))
+}
+
+async fn list_rollups(State(state): State<ApiState>) -> impl IntoResponse {
+    let rollups: Vec<_> = state
+        .registry
+        .iter()
+        .map(|rollup| {
+            let name = rollup.name();
+            serde_json::json!({
+                "name": name,
+                "status_endpoint": format!("/rollups/{}/status", name),
+                "health_endpoint": format!("/rollups/{}/health", name),
+                "sequencer_endpoint": format!("/rollups/{}/sequencer", name),
+                "events": rollup.event_types()
+            })
+        })
+        .collect();
+
+    Json(serde_json::json!({ "rollups": rollups }))
+}
+
+/// 404 response for rollups not in the registry
+fn unknown_rollup(name: &str) -> axum::response::Response {
+    (
+        StatusCode::NOT_FOUND,
+        Json(serde_json::json!({ "error": format!("Unknown rollup: {}", name) })),
+    )
+        .into_response()
+}
+
+async fn get_rollup_status(
+    State(state): State<ApiState>,
+    Path(name): Path<String>,
+) -> axum::response::Response {
+    if !state.registry.contains(&name) {
+        return unknown_rollup(&name);
+    }
+    Json(state.app.get_status(&name)).into_response()
+}
+
+async fn get_rollup_health(
+    State(state): State<ApiState>,
+    Path(name): Path<String>,
+) -> axum::response::Response {
+    if !state.registry.contains(&name) {
+        return unknown_rollup(&name);
+    }
+    Json(state.health.check_health(&name)).into_response()
+}
+
+async fn get_rollup_sequencer(
+    State(state): State<ApiState>,
+    Path(name): Path<String>,
+) -> axum::response::Response {
+    if !state.registry.contains(&name) {
+        return unknown_rollup(&name);
+    }
+    Json(state.app.get_sequencer_status(&name)).into_response()
+}
+
+/// Default and maximum number of rows returned by history endpoints
+const DEFAULT_HISTORY_LIMIT: usize = 100;
+const MAX_HISTORY_LIMIT: usize = 1000;
+
+/// Query parameters for history endpoints
+#[derive(serde::Deserialize)]
+struct HistoryQuery {
+    limit: Option<usize>,
+    /// Only events emitted before this L1 block (for paging)
+    before_block: Option<u64>,
+    /// Only incidents still open or resolved at or after this unix timestamp
+    since: Option<u64>,
+    /// Only alert deliveries for this rollup
+    rollup: Option<String>,
+}
+
+impl HistoryQuery {
+    fn limit(&self) -> usize {
+        self.limit
+            .unwrap_or(DEFAULT_HISTORY_LIMIT)
+            .min(MAX_HISTORY_LIMIT)
+    }
+}
+
+/// 503 response when persistence is disabled
+fn store_disabled() -> axum::response::Response {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(serde_json::json!({ "error": "Persistence is disabled" })),
+    )
+        .into_response()
+}
+
+/// 500 response for store failures
+fn store_error(e: eyre::Report) -> axum::response::Response {
+    tracing::error!(error = ?e, "Store query failed");
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(serde_json::json!({ "error": "Store query failed" })),
+    )
+        .into_response()
+}
+
+/// Rejection of a write request whose `Authorization: Bearer` header does not
+/// carry the admin token, `None` if it does; without a configured token the
+/// write routes are disabled
+fn reject_unauthorized(state: &ApiState, headers: &HeaderMap) -> Option<axum::response::Response> {
+    let Some(expected) = &state.admin_token else {
+        return Some(
+            (
+                StatusCode::FORBIDDEN,
+                Json(serde_json::json!({ "error": "Write routes are disabled, set ADMIN_TOKEN" })),
+            )
+                .into_response(),
+        );
+    };
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    match token {
+        Some(token) if constant_time_eq(token.as_bytes(), expected.as_bytes()) => None,
+        _ => Some(
+            (
+                StatusCode::UNAUTHORIZED,
+                [(header::WWW_AUTHENTICATE, "Bearer")],
+                Json(serde_json::json!({ "error": "Missing or invalid admin token" })),
+            )
+                .into_response(),
+        ),
+    }
+}
+
+/// Compare secrets without returning early on the first differing byte
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+async fn get_rollup_events(
+    State(state): State<ApiState>,
+    Path(name): Path<String>,
+    Query(query): Query<HistoryQuery>,
+) -> axum::response::Response {
+    if !state.registry.contains(&name) {
+        return unknown_rollup(&name);
+    }
+    let Some(store) = &state.app.store else {
+        return store_disabled();
+    };
+    match store.events(&name, query.before_block, query.limit()) {
+        Ok(events) => Json(serde_json::json!({ "rollup": name, "events": events })).into_response(),
+        Err(e) => store_error(e),
+    }
+}
+
+async fn get_rollup_health_history(
+    State(state): State<ApiState>,
+    Path(name): Path<String>,
+    Query(query): Query<HistoryQuery>,
+) -> axum::response::Response {
+    if !state.registry.contains(&name) {
+        return unknown_rollup(&name);
+    }
+    let Some(store) = &state.app.store else {
+        return store_disabled();
+    };
+    match store.transitions(&name, query.limit()) {
+        Ok(transitions) => {
+            Json(serde_json::json!({ "rollup": name, "transitions": transitions })).into_response()
+        }
+        Err(e) => store_error(e),
+    }
+}
+
+async fn get_rollup_incidents(
+    State(state): State<ApiState>,
+    Path(name): Path<String>,
+    Query(query): Query<HistoryQuery>,
+) -> axum::response::Response {
+    if !state.registry.contains(&name) {
+        return unknown_rollup(&name);
+    }
+    let Some(store) = &state.app.store else {
+        return store_disabled();
+    };
+    match store.incidents(Some(&name), query.since, query.limit()) {
+        Ok(incidents) => {
+            Json(serde_json::json!({ "rollup": name, "incidents": incidents })).into_response()
+        }
+        Err(e) => store_error(e),
+    }
+}
+
+async fn get_incidents(
+    State(state): State<ApiState>,
+    Query(query): Query<HistoryQuery>,
+) -> axum::response::Response {
+    let Some(store) = &state.app.store else {
+        return store_disabled();
+    };
+    match store.incidents(None, query.since, query.limit()) {
+        Ok(incidents) => Json(serde_json::json!({ "incidents": incidents })).into_response(),
+        Err(e) => store_error(e),
+    }
+}
+
+async fn get_alert_deliveries(
+    State(state): State<ApiState>,
+    Query(query): Query<HistoryQuery>,
+) -> axum::response::Response {
+    let Some(store) = &state.app.store else {
+        return store_disabled();
+    };
+    match store.deliveries(query.rollup.as_deref(), query.limit()) {
+        Ok(deliveries) => Json(serde_json::json!({ "deliveries": deliveries })).into_response(),
+        Err(e) => store_error(e),
+    }
+}
+
+async fn get_open_alerts(State(state): State<ApiState>) -> axum::response::Response {
+    let Some(store) = &state.app.store else {
+        return store_disabled();
+    };
+    match store.open_alerts() {
+        Ok(alerts) => {
+            let mut alerts: Vec<_> = alerts.into_values().collect();
+            alerts.sort_by_key(|a| a.opened_at);
+            Json(serde_json::json!({ "alerts": alerts })).into_response()
+        }
+        Err(e) => store_error(e),
+    }
+}
+
+/// Query parameters for the uptime endpoint
+#[derive(serde::Deserialize)]
+struct UptimeQuery {
+    /// Start of a custom window (unix timestamp)
+    from: Option<u64>,
+    /// End of a custom window, defaults to now
+    to: Option<u64>,
+}
+
+async fn get_rollup_uptime(
+    State(state): State<ApiState>,
+    Path(name): Path<String>,
+    Query(query): Query<UptimeQuery>,
+) -> axum::response::Response {
+    if !state.registry.contains(&name) {
+        return unknown_rollup(&name);
+    }
+    let Some(store) = &state.app.store else {
+        return store_disabled();
+    };
+
+    let now = state.health.now();
+    let windows: Vec<(&str, u64, u64)> = match query.from {
+        Some(from) => vec![("custom", from, query.to.unwrap_or(now))],
+        None => ROLLING_WINDOWS
+            .iter()
+            .map(|(label, secs)| (*label, now.saturating_sub(*secs), now))
+            .collect(),
+    };
+    if windows.iter().any(|(_, from, to)| from >= to) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": "Window must end after it starts" })),
+        )
+            .into_response();
+    }
+
+    let windows: eyre::Result<Vec<UptimeWindow>> = windows
+        .into_iter()
+        .map(|(label, from, to)| UptimeWindow::compute(store, &name, label, from, to))
+        .collect();
+    match windows {
+        Ok(windows) => {
+            Json(serde_json::json!({ "rollup": name, "windows": windows })).into_response()
+        }
+        Err(e) => store_error(e),
+    }
+}
+
+/// Request body for declaring a maintenance window
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MaintenanceRequest {
+    /// Stage under maintenance, the whole rollup if omitted
+    stage: Option<HealthStage>,
+    /// Unix timestamp the window opens, defaults to now
+    starts_at: Option<u64>,
+    /// Unix timestamp the window closes
+    ends_at: u64,
+    reason: Option<String>,
+}
+
+async fn post_rollup_maintenance(
+    State(state): State<ApiState>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+    Json(req): Json<MaintenanceRequest>,
+) -> axum::response::Response {
+    if let Some(response) = reject_unauthorized(&state, &headers) {
+        return response;
+    }
+    if !state.registry.contains(&name) {
+        return unknown_rollup(&name);
+    }
+    let window = MaintenanceWindow {
+        id: None,
+        rollup: name,
+        stage: req.stage,
+        starts_at: req.starts_at.unwrap_or_else(|| state.health.now()),
+        ends_at: req.ends_at,
+        reason: req.reason,
+    };
+    if let Err(e) = window.validate() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response();
+    }
+    match state.health.declare_maintenance(window) {
+        Ok(window) => (StatusCode::CREATED, Json(window)).into_response(),
+        Err(e) => store_error(e),
+    }
+}
+
+async fn get_maintenance(State(state): State<ApiState>) -> impl IntoResponse {
+    Json(serde_json::json!({
+        "maintenance": state.health.maintenance_windows()
+    }))
+}
+
+async fn delete_maintenance(
+    State(state): State<ApiState>,
+    Path(id): Path<u64>,
+    headers: HeaderMap,
+) -> axum::response::Response {
+    if let Some(response) = reject_unauthorized(&state, &headers) {
+        return response;
+    }
+    match state.health.cancel_maintenance(id) {
+        Ok(true) => StatusCode::NO_CONTENT.into_response(),
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": format!("Unknown maintenance window: {}", id) })),
+        )
+            .into_response(),
+        Err(e) => store_error(e),
+    }
+}
+
+async fn get_all_health(State(state): State<ApiState>) -> impl IntoResponse {
+    Json(serde_json::json!({
+        "rollups": state.health.evaluate_all()
+    }))
+}
+
+async fn get_all_sequencer(State(state): State<ApiState>) -> impl IntoResponse {
+    Json(serde_json::json!({
+        "sequencer": state.app.get_all_sequencer_statuses()
+    }))
+}
+
+async fn get_l1_endpoints(State(state): State<ApiState>) -> impl IntoResponse {
+    Json(serde_json::json!({
+        "active": state.endpoints.active().map(|url| endpoints::redact_url(&url)),
+        "endpoints": state.endpoints.statuses()
+    }))
+}
+
+async fn get_watchers(State(state): State<ApiState>) -> impl IntoResponse {
+    Json(serde_json::json!({
+        "watchers": state.app.watchers.streams()
+    }))
+}
+
+// ------------------------------------------
+// Metrics Endpoint
+// ------------------------------------------
+
+async fn get_metrics(State(state): State<ApiState>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, metrics::CONTENT_TYPE)],
+        metrics::render(&state.app, &state.health),
+    )
+}
+
+// ------------------------------------------
+// WebSocket Endpoint
+// ------------------------------------------
+
+async fn ws_handler(ws: WebSocketUpgrade, State(state): State<ApiState>) -> impl IntoResponse {
+    ws.on_upgrade(|socket| handle_ws(socket, state))
+}
+
+async fn handle_ws(mut socket: WebSocket, state: ApiState) {
+    let mut rx = state.app.tx.subscribe();
+    let _client = state.app.metrics.ws_client();
+
+    tracing::info!("New WebSocket client connected");
+
+    // Send initial status to the client (including health and sequencer)
+    let statuses = state.app.get_all_statuses();
+    let health = state.health.evaluate_all();
+    let sequencer = state.app.get_all_sequencer_statuses();
+    let initial = serde_json::json!({
+        "type": "initial",
+        "statuses": statuses,
+        "health": health,
+        "sequencer": sequencer
+    });
+    if let Ok(json_msg) = serde_json::to_string(&initial) {
+        let _ = socket.send(Message::Text(json_msg.into())).await;
+    }
+
+    // Stream events as they arrive; a client that falls behind skips ahead
+    loop {
+        let event = match rx.recv().await {
+            Ok(event) => event,
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                tracing::warn!(
+                    skipped = skipped,
+                    "WebSocket client lagged, messages dropped"
+                );
+                state.app.metrics.record_lagged(skipped);
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+        if let Ok(json_msg) = serde_json::to_string(&event) {
+            if socket.send(Message::Text(json_msg.into())).await.is_err() {
+                break;
+            }
+        }
+    }
+
+    tracing::info!("WebSocket client disconnected");
+}
+
+// ------------------------------------------
+// Test Endpoint (for development only)
+// ------------------------------------------
+
+/// Request body for test event endpoint
+#[derive(serde::Deserialize)]
+struct TestEventRequest {
+    rollup: Option<String>,
+    event_type: Option<String>,
+    block_number: Option<u64>,
+    batch_number: Option<String>,
+    tx_hash: Option<String>,
+}
+
+/// POST /test/event - Broadcast a test event to all WebSocket clients
+async fn post_test_event(
+    State(state): State<ApiState>,
+    Json(req): Json<TestEventRequest>,
+) -> impl IntoResponse {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let event = RollupEvent {
+        rollup: req.rollup.unwrap_or_else(|| "arbitrum".to_string()),
+        event_type: req
+            .event_type
+            .unwrap_or_else(|| "BatchDelivered".to_string()),
+        block_number: req.block_number.unwrap_or(19_000_000),
+        tx_hash: req
+            .tx_hash
+            .unwrap_or_else(|| format!("0x{:064x}", rand::random::<u64>())),
+        batch_number: req.batch_number.or_else(|| Some("12345".to_string())),
+        timestamp: Some(now),
+        observed_at: Some(now),
+        status: EventStatus::Confirmed,
+    };
+
+    tracing::info!(
+        rollup = %event.rollup,
+        event_type = %event.event_type,
+        "Broadcasting test event"
+    );
+
+    state.app.broadcast(event.clone());
+
+    Json(serde_json::json!({
+        "status": "ok",
+        "event": event
+    }))
+}
@@ -0,0 +1,1091 @@
+use crate::block_times::BlockTimes;
+use crate::capture::CaptureRecord;
+use crate::config::BackfillConfig;
+use crate::gaps::{BatchGap, BatchSequences, GapKind, SequenceCheck};
+use crate::health::HealthMonitor;
+use crate::l1::LogRouter;
+use crate::liveness::WatcherRegistry;
+use crate::reorg::PendingEvents;
+use crate::types::{AppState, EventStatus, RollupEvent, StreamMessage, StreamNotice};
+use crate::watcher::EventStream;
+use ethers::prelude::*;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Number of recent logs remembered for de-duplication
+const SEEN_LOGS_CAPACITY: usize = 4096;
+
+/// Cursor key for a rollup event stream (e.g., "arbitrum/batch")
+pub fn stream_key(rollup: &str, stream: &str) -> String {
+    format!("{}/{}", rollup, stream)
+}
+
+/// Per-stream record of processed L1 logs.
+///
+/// Each stream remembers the last L1 block it has processed up to, so restarts
+/// and reconnects can backfill exactly the gap. Logs are additionally
+/// identified by (transaction hash, log index) so re-fetching the cursor block
+/// itself, or a live log already seen during backfill, is skipped.
+#[derive(Debug, Default)]
+pub struct LogCursor {
+    /// Last processed L1 block keyed by stream key
+    blocks: HashMap<String, u64>,
+    /// Recently processed log ids, oldest first
+    order: VecDeque<(H256, U256)>,
+    seen: HashSet<(H256, U256)>,
+    /// Whether stream blocks changed since the last save
+    dirty: bool,
+}
+
+impl LogCursor {
+    /// Create an empty cursor
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a cursor resuming from previously saved stream blocks
+    pub fn with_blocks(blocks: HashMap<String, u64>) -> Self {
+        Self {
+            blocks,
+            ..Self::default()
+        }
+    }
+
+    /// Create a cursor resuming from the blocks saved in the state's store
+    pub fn restore(state: &AppState) -> Self {
+        let Some(store) = &state.store else {
+            return Self::new();
+        };
+        match store.cursors() {
+            Ok(blocks) => Self::with_blocks(blocks),
+            Err(e) => {
+                tracing::error!(error = ?e, "Failed to load backfill cursors, starting fresh");
+                Self::new()
+            }
+        }
+    }
+
+    /// Save every stream's block to the state's store, if enabled and any
+    /// moved since the last save.
+    ///
+    /// A stream is saved no further than its earliest pending event, so after
+    /// a restart the backfill fetches the event's log again and can still
+    /// confirm or retract it.
+    pub fn save(&mut self, state: &AppState, pending: &PendingEvents) {
+        let Some(store) = &state.store else {
+            return;
+        };
+        if !self.dirty {
+            return;
+        }
+        let blocks: HashMap<String, u64> = self
+            .blocks
+            .iter()
+            .map(|(key, block)| {
+                let saved = pending
+                    .first_block(key)
+                    .map_or(*block, |first| first.min(*block));
+                (key.clone(), saved)
+            })
+            .collect();
+        match store.save_cursors(&blocks) {
+            Ok(()) => self.dirty = false,
+            Err(e) => tracing::error!(error = ?e, "Failed to persist backfill cursors"),
+        }
+    }
+
+    /// Forget a log so it is accepted again if re-included after a reorg, and
+    /// move the stream's cursor back to the log's block if it is past it
+    pub fn forget(&mut self, key: &str, log: &Log) {
+        if let (Some(tx_hash), Some(log_index)) = (log.transaction_hash, log.log_index) {
+            let id = (tx_hash, log_index);
+            if self.seen.remove(&id) {
+                self.order.retain(|seen| *seen != id);
+            }
+        }
+        if let (Some(block), Some(cursor)) = (log.block_number, self.blocks.get_mut(key)) {
+            if block.as_u64() < *cursor {
+                *cursor = block.as_u64();
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// Last processed L1 block of a stream
+    pub fn stream_block(&self, key: &str) -> Option<u64> {
+        self.blocks.get(key).copied()
+    }
+
+    /// Move a stream's cursor forward to `block` (never backward)
+    pub fn advance(&mut self, key: &str, block: u64) {
+        match self.blocks.get_mut(key) {
+            Some(cursor) if *cursor >= block => return,
+            Some(cursor) => *cursor = block,
+            None => {
+                self.blocks.insert(key.to_string(), block);
+            }
+        }
+        self.dirty = true;
+    }
+
+    /// Move every routed stream's cursor to the head less its rollup's
+    /// confirmations, so a stream without recent logs does not hold the next
+    /// backfill back at its last log
+    pub fn advance_confirmed(&mut self, router: &LogRouter, head: u64) {
+        for (key, confirmations) in router.stream_confirmations() {
+            self.advance(&key, head.saturating_sub(confirmations));
+        }
+    }
+
+    /// Mark a log as processed by a stream; returns false if it was already
+    /// processed (seen before, or older than the stream's cursor)
+    pub fn accept(&mut self, key: &str, log: &Log) -> bool {
+        let block = log.block_number.map(|b| b.as_u64());
+        if let (Some(block), Some(cursor)) = (block, self.stream_block(key)) {
+            if block < cursor {
+                return false;
+            }
+        }
+        if !self.remember(log) {
+            return false;
+        }
+
+        if let Some(block) = block {
+            self.advance(key, block);
+        }
+        true
+    }
+
+    /// Remember a log's id; returns false if it was seen recently
+    pub fn remember(&mut self, log: &Log) -> bool {
+        let (Some(tx_hash), Some(log_index)) = (log.transaction_hash, log.log_index) else {
+            return true;
+        };
+        let id = (tx_hash, log_index);
+        if !self.seen.insert(id) {
+            return false;
+        }
+        self.order.push_back(id);
+        if self.order.len() > SEEN_LOGS_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+/// L1 log processing state carried across reconnects
+#[derive(Debug, Default)]
+pub struct LogTracker {
+    /// Per-stream progress and recently seen logs
+    pub cursor: LogCursor,
+    /// Events awaiting their confirmation depth
+    pub pending: PendingEvents,
+    /// Timestamps of recent L1 blocks
+    pub block_times: BlockTimes,
+    /// Expected next numbers of sequential streams
+    pub sequences: BatchSequences,
+    /// Skipped batch ranges waiting for a targeted backfill
+    pub gap_backfills: Vec<BatchGap>,
+    /// Liveness registry noting when each stream last received a log
+    pub watchers: Option<WatcherRegistry>,
+}
+
+impl LogTracker {
+    /// Create a tracker resuming from the cursors saved in the state's store
+    pub fn restore(state: &AppState) -> Self {
+        Self {
+            cursor: LogCursor::restore(state),
+            ..Self::default()
+        }
+    }
+
+    /// Fetch the timestamp of a log's block into the cache, so the event is
+    /// stamped with chain time when it is processed
+    pub async fn fetch_block_time<M: Middleware>(&mut self, provider: &M, log: &Log) {
+        if log.removed == Some(true) {
+            return;
+        }
+        if let Some(block) = log.block_number {
+            self.block_times.fetch(provider, block.as_u64()).await;
+        }
+    }
+
+    /// Check a sequential stream's event number against the expected next
+    /// one, reporting gaps and queueing skipped ranges for a targeted backfill
+    fn check_sequence(
+        &mut self,
+        event: &RollupEvent,
+        stream: &EventStream,
+        refill: bool,
+        state: &AppState,
+        health: &HealthMonitor,
+    ) {
+        let Some(number) = event.batch_number.as_deref().and_then(|n| n.parse().ok()) else {
+            return;
+        };
+        let key = stream_key(&event.rollup, &stream.name);
+        let gap = |kind, expected, from_block| BatchGap {
+            rollup: event.rollup.clone(),
+            stream: stream.name.clone(),
+            kind,
+            expected,
+            found: number,
+            from_block,
+            to_block: event.block_number,
+            detected_at: health.now(),
+        };
+
+        let detected = match self.sequences.observe(&key, number, event.block_number) {
+            SequenceCheck::InOrder => {
+                health.clear_batch_gaps(&event.rollup, &stream.name, GapKind::Repeated);
+                return;
+            }
+            SequenceCheck::Filled { resolved } => {
+                if resolved {
+                    health.clear_batch_gaps(&event.rollup, &stream.name, GapKind::Skipped);
+                    tracing::info!(rollup = %event.rollup, stream = %stream.name, "Batch gap filled");
+                }
+                return;
+            }
+            // Refilled ranges overlap the batches on either side of the gap
+            SequenceCheck::Repeated { .. } if refill => return,
+            SequenceCheck::Repeated { expected } => {
+                gap(GapKind::Repeated, expected, event.block_number)
+            }
+            SequenceCheck::Skipped {
+                expected,
+                from_block,
+            } => {
+                let gap = gap(GapKind::Skipped, expected, from_block);
+                self.gap_backfills.push(gap.clone());
+                gap
+            }
+        };
+
+        tracing::warn!(
+            rollup = %detected.rollup,
+            stream = %detected.stream,
+            expected = detected.expected,
+            found = detected.found,
+            block = detected.to_block,
+            "{}",
+            detected.describe()
+        );
+        health.record_batch_gap(detected.clone());
+        state.publish(StreamMessage::Notice(StreamNotice::BatchGap(detected)));
+    }
+}
+
+/// Apply a log to its rollup stream unless that stream already processed it.
+///
+/// Events younger than the rollup's confirmation depth are applied as pending
+/// and tracked; logs flagged `removed` by a reorg retract their pending event.
+/// Events are stamped with their block's cached timestamp when available, and
+/// numbers of sequential streams are checked for gaps. Cursors are left for
+/// the caller to save. Returns whether the log was applied.
+pub fn process_log(
+    log: &Log,
+    router: &LogRouter,
+    tracker: &mut LogTracker,
+    state: &AppState,
+    health: &HealthMonitor,
+) -> bool {
+    apply_log(log, router, tracker, false, state, health)
+}
+
+/// Apply a log; `refill` logs come from a targeted gap backfill behind the
+/// stream's cursor and leave the status slots (holding newer batches) intact
+pub fn apply_log(
+    log: &Log,
+    router: &LogRouter,
+    tracker: &mut LogTracker,
+    refill: bool,
+    state: &AppState,
+    health: &HealthMonitor,
+) -> bool {
+    let Some((rollup, stream)) = router.route(log) else {
+        return false;
+    };
+    let key = stream_key(rollup.name(), &stream.name);
+    let block = log.block_number.map(|b| b.as_u64()).unwrap_or_default();
+    state.record(|| CaptureRecord::L1Log {
+        log: Box::new(log.clone()),
+        block_timestamp: tracker.block_times.get(block),
+        refill,
+    });
+
+    if log.removed == Some(true) {
+        tracker.cursor.forget(&key, log);
+        tracker.pending.retract(log, state);
+        if stream.sequential {
+            if let Some(number) = rollup.decode(stream, log).ok().and_then(|n| n.parse().ok()) {
+                tracker.sequences.rewind(&key, number);
+            }
+        }
+        return false;
+    }
+    let accepted = if refill {
+        tracker.cursor.remember(log)
+    } else {
+        tracker.cursor.accept(&key, log)
+    };
+    if !accepted {
+        return false;
+    }
+    if let Some(watchers) = &tracker.watchers {
+        watchers.record_message(&key);
+    }
+
+    tracker.pending.observe_head(block);
+    let confirmations = rollup.confirmations();
+    let status = tracker.pending.status_for(block, confirmations);
+    let previous = state.get_status(rollup.name());
+    let block_timestamp = tracker.block_times.get(block);
+
+    if let Some(event) = router.dispatch(log, block_timestamp, status, state, health) {
+        if refill {
+            state.update_status(rollup.name(), |status| {
+                for slot in &stream.slots {
+                    slot.copy_from(status, &previous);
+                }
+            });
+        }
+        if stream.sequential {
+            tracker.check_sequence(&event, stream, refill, state, health);
+        }
+        if status == EventStatus::Pending {
+            tracker.pending.track(
+                &key,
+                log,
+                event,
+                stream.slots.clone(),
+                previous,
+                confirmations,
+            );
+        }
+    }
+    true
+}
+
+/// Fetch logs matching `filter` in `from..=to`
+async fn get_logs<M: Middleware>(
+    provider: &M,
+    filter: &Filter,
+    from: u64,
+    to: u64,
+) -> eyre::Result<Vec<Log>> {
+    provider
+        .get_logs(&filter.clone().from_block(from).to_block(to))
+        .await
+        .map_err(|e| eyre::eyre!("eth_getLogs failed for blocks {}..={}: {}", from, to, e))
+}
+
+/// Backfill every routed stream from its cursor up to the current L1 head.
+///
+/// Streams without a cursor (cold start) start `lookback_blocks` behind the
+/// head. The range is fetched with the router's combined filter in chunks of
+/// `chunk_size` blocks; cursors advance after each chunk so a failure part-way
+/// resumes where it stopped. Block headers of matching logs are fetched so
+/// backfilled events carry their L1 time, and batch gaps found along the way
+/// are filled. Returns the head block backfilled up to.
+pub async fn backfill<M: Middleware>(
+    provider: &M,
+    router: &LogRouter,
+    tracker: &mut LogTracker,
+    config: &BackfillConfig,
+    state: &AppState,
+    health: &HealthMonitor,
+) -> eyre::Result<u64> {
+    let head = provider
+        .get_block_number()
+        .await
+        .map_err(|e| eyre::eyre!("eth_blockNumber failed: {}", e))?
+        .as_u64();
+    state.record(|| CaptureRecord::L1Head {
+        block: head,
+        timestamp: None,
+    });
+    tracker.pending.observe_head(head);
+
+    let keys = router.stream_keys();
+    let cold_start = (head + 1).saturating_sub(config.lookback_blocks);
+    let Some(from) = keys
+        .iter()
+        .map(|key| tracker.cursor.stream_block(key).unwrap_or(cold_start))
+        .min()
+    else {
+        return Ok(head);
+    };
+
+    let filter = router.filter();
+    let chunk_size = config.chunk_size.max(1);
+    let mut applied = 0;
+    let mut chunk_start = from;
+
+    while chunk_start <= head {
+        let chunk_end = chunk_start.saturating_add(chunk_size - 1).min(head);
+        let logs = get_logs(provider, &filter, chunk_start, chunk_end).await?;
+
+        for log in &logs {
+            tracker.fetch_block_time(provider, log).await;
+            if process_log(log, router, tracker, state, health) {
+                applied += 1;
+            }
+        }
+        for key in &keys {
+            tracker.cursor.advance(key, chunk_end);
+        }
+        tracker.cursor.save(state, &tracker.pending);
+
+        tracing::debug!(
+            stream = "l1",
+            from_block = chunk_start,
+            to_block = chunk_end,
+            logs = logs.len(),
+            "Backfilled chunk"
+        );
+        chunk_start = chunk_end + 1;
+    }
+
+    // Streams with no lookback start live from the current head
+    for key in &keys {
+        tracker.cursor.advance(key, head);
+    }
+    tracker.cursor.save(state, &tracker.pending);
+
+    // Gaps found while catching up are filled before going live
+    if let Err(e) = fill_gaps(provider, router, tracker, config, state, health).await {
+        tracing::warn!(stream = "l1", error = ?e, "Gap backfill failed, will retry");
+    }
+
+    // Polling backfills every interval; only report ranges that applied events
+    if applied > 0 {
+        tracing::info!(
+            stream = "l1",
+            from_block = from,
+            to_block = head,
+            applied = applied,
+            "Backfill complete"
+        );
+    } else if from <= head {
+        tracing::debug!(
+            stream = "l1",
+            from_block = from,
+            to_block = head,
+            "Backfill complete, no new events"
+        );
+    }
+    Ok(head)
+}
+
+/// Re-check the logs of pending events due for confirmation at `head`
+/// against their transaction receipts, for transports that do not report
+/// logs removed by reorgs.
+///
+/// A log whose transaction is missing or now in another block is handled as
+/// if it had been removed: its event is retracted and the stream's cursor
+/// moved back so the re-included log, if any, is fetched again. Returns the
+/// number of events retracted.
+pub async fn recheck_pending<M: Middleware>(
+    provider: &M,
+    router: &LogRouter,
+    tracker: &mut LogTracker,
+    head: u64,
+    state: &AppState,
+    health: &HealthMonitor,
+) -> eyre::Result<usize> {
+    let mut retracted = 0;
+    for log in tracker.pending.due(head) {
+        let Some(tx_hash) = log.transaction_hash else {
+            continue;
+        };
+        let receipt = provider
+            .get_transaction_receipt(tx_hash)
+            .await
+            .map_err(|e| {
+                eyre::eyre!("eth_getTransactionReceipt failed for {:?}: {}", tx_hash, e)
+            })?;
+        if receipt
+            .as_ref()
+            .is_some_and(|r| r.block_hash.is_some() && r.block_hash == log.block_hash)
+        {
+            continue;
+        }
+
+        // Rewind to the new block too if the transaction landed earlier
+        let block_number = match (log.block_number, receipt.and_then(|r| r.block_number)) {
+            (Some(original), Some(included)) => Some(original.min(included)),
+            (original, _) => original,
+        };
+        let removed = Log {
+            removed: Some(true),
+            block_number,
+            ..log
+        };
+        apply_log(&removed, router, tracker, false, state, health);
+        retracted += 1;
+    }
+    if retracted > 0 {
+        tracker.cursor.save(state, &tracker.pending);
+    }
+    Ok(retracted)
+}
+
+/// Re-fetch the L1 block ranges of batch gaps detected since the last call.
+///
+/// Only the gapped stream's contract and topic are queried, in chunks of
+/// `chunk_size` blocks. Missing batches found are recorded, persisted and
+/// broadcast like any other event. Gaps whose range fails to fetch are kept
+/// and retried on the next call.
+pub async fn fill_gaps<M: Middleware>(
+    provider: &M,
+    router: &LogRouter,
+    tracker: &mut LogTracker,
+    config: &BackfillConfig,
+    state: &AppState,
+    health: &HealthMonitor,
+) -> eyre::Result<()> {
+    let mut gaps = std::mem::take(&mut tracker.gap_backfills).into_iter();
+    let chunk_size = config.chunk_size.max(1);
+
+    while let Some(gap) = gaps.next() {
+        let key = stream_key(&gap.rollup, &gap.stream);
+        let Some(filter) = router.stream_filter(&key) else {
+            continue;
+        };
+
+        let mut applied = 0;
+        let mut chunk_start = gap.from_block;
+        while chunk_start <= gap.to_block {
+            let chunk_end = chunk_start.saturating_add(chunk_size - 1).min(gap.to_block);
+            let logs = match get_logs(provider, &filter, chunk_start, chunk_end).await {
+                Ok(logs) => logs,
+                Err(e) => {
+                    tracker.gap_backfills.push(gap);
+                    tracker.gap_backfills.extend(gaps);
+                    return Err(e);
+                }
+            };
+            for log in &logs {
+                tracker.fetch_block_time(provider, log).await;
+                if apply_log(log, router, tracker, true, state, health) {
+                    applied += 1;
+                }
+            }
+            chunk_start = chunk_end + 1;
+        }
+
+        tracing::info!(
+            rollup = %gap.rollup,
+            stream = %gap.stream,
+            from_block = gap.from_block,
+            to_block = gap.to_block,
+            applied = applied,
+            "Gap backfill complete"
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::health::HealthCategory;
+    use crate::registry::RollupRegistry;
+    use crate::store::Store;
+    use crate::types::StreamMessage;
+    use crate::watcher::{Contract, RollupWatcher, StatusSlot};
+
+    struct TestRollup;
+
+    impl RollupWatcher for TestRollup {
+        fn name(&self) -> &str {
+            "test"
+        }
+
+        fn contracts(&self) -> eyre::Result<Vec<Contract>> {
+            Ok(vec![Contract {
+                name: "inbox".to_string(),
+                address: Address::repeat_byte(0xaa),
+            }])
+        }
+
+        fn streams(&self) -> Vec<EventStream> {
+            vec![EventStream {
+                name: "batch".to_string(),
+                event_type: "BatchDelivered".to_string(),
+                contract: "inbox".to_string(),
+                topic: H256::repeat_byte(0x01),
+                slots: vec![StatusSlot::Batch],
+                category: HealthCategory::Batch,
+                sequential: false,
+            }]
+        }
+
+        fn decode(&self, _stream: &EventStream, log: &Log) -> eyre::Result<String> {
+            Ok(log.block_number.unwrap_or_default().to_string())
+        }
+
+        fn confirmations(&self) -> u64 {
+            2
+        }
+    }
+
+    /// Rollup whose batch numbers are the emitting block numbers
+    struct SequentialRollup;
+
+    impl RollupWatcher for SequentialRollup {
+        fn name(&self) -> &str {
+            "seq"
+        }
+
+        fn contracts(&self) -> eyre::Result<Vec<Contract>> {
+            Ok(vec![Contract {
+                name: "inbox".to_string(),
+                address: Address::repeat_byte(0xbb),
+            }])
+        }
+
+        fn streams(&self) -> Vec<EventStream> {
+            vec![EventStream {
+                name: "batch".to_string(),
+                event_type: "BatchDelivered".to_string(),
+                contract: "inbox".to_string(),
+                topic: H256::repeat_byte(0x02),
+                slots: vec![StatusSlot::Batch],
+                category: HealthCategory::Batch,
+                sequential: true,
+            }]
+        }
+
+        fn decode(&self, _stream: &EventStream, log: &Log) -> eyre::Result<String> {
+            Ok(log.block_number.unwrap_or_default().to_string())
+        }
+    }
+
+    fn router() -> LogRouter {
+        LogRouter::from_registry(&RollupRegistry::new().with(TestRollup))
+    }
+
+    fn log_at(block: u64, tx: u64, index: u64) -> Log {
+        Log {
+            address: Address::repeat_byte(0xaa),
+            topics: vec![H256::repeat_byte(0x01)],
+            block_number: Some(U64::from(block)),
+            transaction_hash: Some(H256::from_low_u64_be(tx)),
+            log_index: Some(U256::from(index)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_cursor_deduplicates_logs() {
+        let mut cursor = LogCursor::new();
+        assert_eq!(cursor.stream_block("a/batch"), None);
+
+        assert!(cursor.accept("a/batch", &log_at(100, 1, 0)));
+        assert!(cursor.accept("a/batch", &log_at(100, 1, 1)));
+        assert!(!cursor.accept("a/batch", &log_at(100, 1, 0)));
+        assert_eq!(cursor.stream_block("a/batch"), Some(100));
+        assert_eq!(cursor.stream_block("b/batch"), None);
+    }
+
+    #[test]
+    fn test_cursor_skips_logs_behind_stream() {
+        let mut cursor = LogCursor::with_blocks(HashMap::from([("a/batch".to_string(), 100)]));
+        assert!(!cursor.accept("a/batch", &log_at(99, 1, 0)));
+        assert!(cursor.accept("b/batch", &log_at(99, 2, 0)));
+        assert!(cursor.accept("a/batch", &log_at(100, 3, 0)));
+
+        cursor.advance("a/batch", 50);
+        assert_eq!(cursor.stream_block("a/batch"), Some(100));
+    }
+
+    #[test]
+    fn test_cursor_forgets_oldest_logs() {
+        let mut cursor = LogCursor::new();
+        for tx in 0..=SEEN_LOGS_CAPACITY as u64 {
+            assert!(cursor.accept("a/batch", &log_at(1, tx, 0)));
+        }
+        // The first log has been evicted, the latest is still remembered
+        assert!(cursor.accept("a/batch", &log_at(1, 0, 0)));
+        assert!(!cursor.accept("a/batch", &log_at(1, SEEN_LOGS_CAPACITY as u64, 0)));
+    }
+
+    #[test]
+    fn test_quiet_streams_follow_confirmed_head() {
+        let router = LogRouter::from_registry(
+            &RollupRegistry::new()
+                .with(TestRollup)
+                .with(SequentialRollup),
+        );
+        let mut cursor = LogCursor::with_blocks(HashMap::from([
+            ("test/batch".to_string(), 100),
+            ("seq/batch".to_string(), 205),
+        ]));
+
+        cursor.advance_confirmed(&router, 200);
+        assert_eq!(cursor.stream_block("test/batch"), Some(198));
+        assert_eq!(cursor.stream_block("seq/batch"), Some(205));
+    }
+
+    fn header(block: u64, timestamp: u64) -> Block<H256> {
+        Block {
+            number: Some(U64::from(block)),
+            timestamp: U256::from(timestamp),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_backfill_cold_start_in_chunks() {
+        let (provider, mock) = Provider::mocked();
+        let now = chrono::Utc::now().timestamp() as u64;
+        // Responses are popped last-in first-out
+        mock.push::<Block<H256>, _>(header(105, now)).unwrap();
+        mock.push::<Vec<Log>, _>(vec![log_at(105, 2, 0)]).unwrap();
+        mock.push::<Block<H256>, _>(header(98, now - 84)).unwrap();
+        mock.push::<Vec<Log>, _>(vec![log_at(98, 1, 0)]).unwrap();
+        mock.push::<U64, _>(U64::from(105)).unwrap();
+
+        let router = router();
+        let mut tracker = LogTracker::default();
+        let state = AppState::new();
+        let health = HealthMonitor::with_rollups(vec!["test".to_string()]);
+        let config = BackfillConfig {
+            lookback_blocks: 10,
+            chunk_size: 5,
+        };
+
+        let head = backfill(&provider, &router, &mut tracker, &config, &state, &health)
+            .await
+            .unwrap();
+        assert_eq!(head, 105);
+        assert_eq!(tracker.cursor.stream_block("test/batch"), Some(105));
+        assert_eq!(
+            state.get_status("test").latest_batch,
+            Some("105".to_string())
+        );
+        // Only the log within the confirmation depth is pending
+        assert_eq!(tracker.pending.len(), 1);
+        assert_eq!(tracker.block_times.get(98), Some(now - 84));
+
+        // Replaying a backfilled log from the live stream is a no-op
+        assert!(!process_log(
+            &log_at(105, 2, 0),
+            &router,
+            &mut tracker,
+            &state,
+            &health
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_backfill_stamps_events_with_block_time() {
+        let (provider, mock) = Provider::mocked();
+        mock.push::<Block<H256>, _>(header(100, 1_700_000_000))
+            .unwrap();
+        mock.push::<Vec<Log>, _>(vec![log_at(100, 1, 0)]).unwrap();
+        mock.push::<U64, _>(U64::from(100)).unwrap();
+
+        let state = AppState::new();
+        let mut rx = state.tx.subscribe();
+        let config = BackfillConfig {
+            lookback_blocks: 1,
+            chunk_size: 1000,
+        };
+        backfill(
+            &provider,
+            &router(),
+            &mut LogTracker::default(),
+            &config,
+            &state,
+            &HealthMonitor::new(),
+        )
+        .await
+        .unwrap();
+
+        let StreamMessage::Event(event) = rx.try_recv().unwrap() else {
+            panic!("Expected an event");
+        };
+        assert_eq!(event.timestamp, Some(1_700_000_000));
+        assert!(event.observed_at.unwrap() > 1_700_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_backfill_resumes_from_cursor() {
+        let (provider, mock) = Provider::mocked();
+        mock.push::<Vec<Log>, _>(Vec::new()).unwrap();
+        mock.push::<U64, _>(U64::from(205)).unwrap();
+
+        let mut tracker = LogTracker {
+            cursor: LogCursor::with_blocks(HashMap::from([("test/batch".to_string(), 200)])),
+            ..Default::default()
+        };
+        let config = BackfillConfig {
+            lookback_blocks: 0,
+            chunk_size: 1000,
+        };
+
+        let router = router();
+        backfill(
+            &provider,
+            &router,
+            &mut tracker,
+            &config,
+            &AppState::new(),
+            &HealthMonitor::new(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(tracker.cursor.stream_block("test/batch"), Some(205));
+
+        // A single eth_getLogs over 200..=205
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        mock.assert_request(
+            "eth_getLogs",
+            [router.filter().from_block(200).to_block(205)],
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_batch_gap_detected_and_filled() {
+        let router = LogRouter::from_registry(&RollupRegistry::new().with(SequentialRollup));
+        let state = AppState::new();
+        let mut rx = state.tx.subscribe();
+        let health = HealthMonitor::new();
+        let mut tracker = LogTracker::default();
+        let log = |block: u64| Log {
+            address: Address::repeat_byte(0xbb),
+            topics: vec![H256::repeat_byte(0x02)],
+            block_number: Some(U64::from(block)),
+            transaction_hash: Some(H256::from_low_u64_be(block)),
+            log_index: Some(U256::zero()),
+            ..Default::default()
+        };
+
+        for block in [10, 11, 14] {
+            assert!(process_log(
+                &log(block),
+                &router,
+                &mut tracker,
+                &state,
+                &health
+            ));
+        }
+        let gaps = health.check_health("seq").batch_gaps;
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(
+            (gaps[0].kind, gaps[0].expected, gaps[0].found),
+            (GapKind::Skipped, 12, 14)
+        );
+        assert_eq!((gaps[0].from_block, gaps[0].to_block), (11, 14));
+        assert_eq!(tracker.gap_backfills, gaps);
+        let notice = std::iter::from_fn(|| rx.try_recv().ok())
+            .find(|m| matches!(m, StreamMessage::Notice(StreamNotice::BatchGap(_))));
+        assert!(notice.is_some());
+
+        // The targeted backfill re-fetches only the gapped stream's range
+        let (provider, mock) = Provider::mocked();
+        mock.push::<Vec<Log>, _>(vec![log(11), log(12), log(13)])
+            .unwrap();
+        let config = BackfillConfig {
+            lookback_blocks: 0,
+            chunk_size: 1000,
+        };
+        fill_gaps(&provider, &router, &mut tracker, &config, &state, &health)
+            .await
+            .unwrap();
+        mock.assert_request(
+            "eth_getLogs",
+            [router
+                .stream_filter("seq/batch")
+                .unwrap()
+                .from_block(11)
+                .to_block(14)],
+        )
+        .unwrap();
+
+        assert!(tracker.gap_backfills.is_empty());
+        assert!(health.check_health("seq").batch_gaps.is_empty());
+        // Refilled batches do not replace the newer latest batch
+        assert_eq!(state.get_status("seq").latest_batch, Some("14".to_string()));
+    }
+
+    #[test]
+    fn test_cursor_saved_and_restored() {
+        let state = AppState::new().with_store(Store::open_in_memory().unwrap());
+        assert_eq!(LogCursor::restore(&state).stream_block("test/batch"), None);
+
+        let mut tracker = LogTracker::restore(&state);
+        assert!(process_log(
+            &log_at(300, 1, 0),
+            &router(),
+            &mut tracker,
+            &state,
+            &HealthMonitor::new()
+        ));
+        assert_eq!(LogCursor::restore(&state).stream_block("test/batch"), None);
+
+        tracker.cursor.save(&state, &tracker.pending);
+        assert_eq!(
+            LogCursor::restore(&state).stream_block("test/batch"),
+            Some(300)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pending_event_confirmed_after_restart() {
+        let store = Store::open_in_memory().unwrap();
+        let state = AppState::new().with_store(store.clone());
+        let mut tracker = LogTracker::restore(&state);
+        assert!(process_log(
+            &log_at(300, 1, 0),
+            &router(),
+            &mut tracker,
+            &state,
+            &HealthMonitor::new()
+        ));
+        tracker.cursor.advance("test/batch", 301);
+        tracker.cursor.save(&state, &tracker.pending);
+        assert_eq!(
+            store.events("test", None, 10).unwrap()[0].status,
+            EventStatus::Pending
+        );
+
+        // Restarting rescans from the pending event's block
+        let state = AppState::new().with_store(store.clone());
+        let mut tracker = LogTracker::restore(&state);
+        assert_eq!(tracker.cursor.stream_block("test/batch"), Some(300));
+
+        let (provider, mock) = Provider::mocked();
+        let now = chrono::Utc::now().timestamp() as u64;
+        mock.push::<Block<H256>, _>(header(300, now)).unwrap();
+        mock.push::<Vec<Log>, _>(vec![log_at(300, 1, 0)]).unwrap();
+        mock.push::<U64, _>(U64::from(305)).unwrap();
+        let config = BackfillConfig {
+            lookback_blocks: 0,
+            chunk_size: 1000,
+        };
+        backfill(
+            &provider,
+            &router(),
+            &mut tracker,
+            &config,
+            &state,
+            &HealthMonitor::new(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            store.events("test", None, 10).unwrap()[0].status,
+            EventStatus::Confirmed
+        );
+    }
+
+    #[test]
+    fn test_removed_log_retracts_and_allows_reinclusion() {
+        let router = router();
+        let state = AppState::new();
+        let health = HealthMonitor::new();
+        let mut tracker = LogTracker::default();
+
+        let log = log_at(300, 1, 0);
+        assert!(process_log(&log, &router, &mut tracker, &state, &health));
+        assert_eq!(tracker.pending.len(), 1);
+        assert_eq!(
+            state.get_status("test").latest_batch,
+            Some("300".to_string())
+        );
+
+        let removed = Log {
+            removed: Some(true),
+            ..log.clone()
+        };
+        assert!(!process_log(
+            &removed,
+            &router,
+            &mut tracker,
+            &state,
+            &health
+        ));
+        assert!(tracker.pending.is_empty());
+        assert_eq!(state.get_status("test").latest_batch, None);
+
+        // The same log re-included by the new chain is applied again
+        assert!(process_log(&log, &router, &mut tracker, &state, &health));
+    }
+
+    #[tokio::test]
+    async fn test_recheck_retracts_reorged_pending_events() {
+        let router = router();
+        let state = AppState::new();
+        let health = HealthMonitor::new();
+        let mut tracker = LogTracker::default();
+
+        let in_block = |block: u64, tx: u64| Log {
+            block_hash: Some(H256::from_low_u64_be(block)),
+            ..log_at(block, tx, 0)
+        };
+        let (kept, reorged) = (in_block(300, 1), in_block(301, 2));
+        assert!(process_log(&kept, &router, &mut tracker, &state, &health));
+        assert!(process_log(
+            &reorged,
+            &router,
+            &mut tracker,
+            &state,
+            &health
+        ));
+        assert!(process_log(
+            &in_block(302, 3),
+            &router,
+            &mut tracker,
+            &state,
+            &health
+        ));
+        assert_eq!(tracker.pending.len(), 3);
+
+        // Only events due at the head are checked; the second transaction
+        // moved to a block replacing 301
+        let (provider, mock) = Provider::mocked();
+        mock.push::<TransactionReceipt, _>(TransactionReceipt {
+            block_hash: Some(H256::repeat_byte(0xbb)),
+            block_number: Some(U64::from(301)),
+            ..Default::default()
+        })
+        .unwrap();
+        mock.push::<TransactionReceipt, _>(TransactionReceipt {
+            block_hash: kept.block_hash,
+            block_number: kept.block_number,
+            ..Default::default()
+        })
+        .unwrap();
+        let retracted = recheck_pending(&provider, &router, &mut tracker, 303, &state, &health)
+            .await
+            .unwrap();
+        assert_eq!(retracted, 1);
+        assert_eq!(tracker.pending.len(), 2);
+        assert_eq!(
+            state.get_status("test").latest_batch,
+            Some("302".to_string())
+        );
+
+        // The stream is re-fetched from the reorged block and takes the
+        // re-included log
+        assert_eq!(tracker.cursor.stream_block("test/batch"), Some(301));
+        let reincluded = Log {
+            block_hash: Some(H256::repeat_byte(0xbb)),
+            ..reorged
+        };
+        assert!(process_log(
+            &reincluded,
+            &router,
+            &mut tracker,
+            &state,
+            &health
+        ));
+    }
+}
@@ -0,0 +1,106 @@
+use ethers::providers::Middleware;
+use std::collections::BTreeMap;
+
+/// Default number of L1 block timestamps kept (~3.4 hours of blocks)
+pub const DEFAULT_CAPACITY: usize = 1024;
+
+/// Cache of L1 block timestamps keyed by block number.
+///
+/// Filled from `newHeads` notifications and, on a miss, from
+/// `eth_getBlockByNumber`. When full, the oldest blocks are evicted first.
+#[derive(Debug)]
+pub struct BlockTimes {
+    times: BTreeMap<u64, u64>,
+    capacity: usize,
+}
+
+impl Default for BlockTimes {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl BlockTimes {
+    /// Create a cache holding at most `capacity` blocks
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            times: BTreeMap::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Cached timestamp of a block
+    pub fn get(&self, block: u64) -> Option<u64> {
+        self.times.get(&block).copied()
+    }
+
+    /// Cache a block's timestamp
+    pub fn insert(&mut self, block: u64, timestamp: u64) {
+        self.times.insert(block, timestamp);
+        while self.times.len() > self.capacity {
+            self.times.pop_first();
+        }
+    }
+
+    /// Timestamp of a block, fetching its header on a cache miss.
+    ///
+    /// Returns `None` if the header cannot be fetched; callers fall back to
+    /// the time the log was observed.
+    pub async fn fetch<M: Middleware>(&mut self, provider: &M, block: u64) -> Option<u64> {
+        if let Some(timestamp) = self.get(block) {
+            return Some(timestamp);
+        }
+
+        match provider.get_block(block).await {
+            Ok(Some(header)) => {
+                let timestamp = header.timestamp.as_u64();
+                self.insert(block, timestamp);
+                Some(timestamp)
+            }
+            Ok(None) => {
+                tracing::warn!(block = block, "L1 block not found, using observed time");
+                None
+            }
+            Err(e) => {
+                tracing::warn!(block = block, error = %e, "Failed to fetch L1 block, using observed time");
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::providers::Provider;
+    use ethers::types::{Block, H256, U256, U64};
+
+    #[test]
+    fn test_evicts_oldest_blocks() {
+        let mut times = BlockTimes::new(2);
+        times.insert(10, 100);
+        times.insert(12, 124);
+        times.insert(11, 112);
+
+        assert_eq!(times.get(10), None);
+        assert_eq!(times.get(11), Some(112));
+        assert_eq!(times.get(12), Some(124));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_caches_headers() {
+        let (provider, mock) = Provider::mocked();
+        let header = Block::<H256> {
+            number: Some(U64::from(42)),
+            timestamp: U256::from(1_700_000_000u64),
+            ..Default::default()
+        };
+        mock.push::<Block<H256>, _>(header).unwrap();
+
+        let mut times = BlockTimes::default();
+        assert_eq!(times.fetch(&provider, 42).await, Some(1_700_000_000));
+        // Served from the cache; the mock has no responses left
+        assert_eq!(times.fetch(&provider, 42).await, Some(1_700_000_000));
+        assert_eq!(times.fetch(&provider, 43).await, None);
+    }
+}
@@ -0,0 +1,314 @@
+use crate::health::RollupHealthConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Inter-arrival samples kept per rollup and category
+const MAX_SAMPLES: usize = 256;
+
+/// Weight of the newest sample in the moving average
+const EWMA_ALPHA: f64 = 0.2;
+
+/// Rolling statistics of the seconds between consecutive events
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IntervalStats {
+    /// Most recent intervals, oldest first
+    samples: VecDeque<u64>,
+    /// Exponentially weighted moving average of the intervals
+    ewma: Option<f64>,
+}
+
+impl IntervalStats {
+    /// Record the seconds since the previous event
+    pub fn observe(&mut self, interval: u64) {
+        self.samples.push_back(interval);
+        if self.samples.len() > MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.ewma = Some(match self.ewma {
+            Some(ewma) => EWMA_ALPHA * interval as f64 + (1.0 - EWMA_ALPHA) * ewma,
+            None => interval as f64,
+        });
+    }
+
+    /// Number of intervals in the window
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Nearest-rank percentile (0-100) of the intervals in the window
+    pub fn percentile(&self, p: u8) -> Option<u64> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<u64> = self.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = (usize::from(p.min(100)) * sorted.len()).div_ceil(100);
+        Some(sorted[rank.saturating_sub(1)])
+    }
+
+    /// Summary for the health API
+    pub fn summary(&self) -> IntervalSummary {
+        IntervalSummary {
+            samples: self.len(),
+            ewma_secs: self.ewma.map(|ewma| ewma.round() as u64),
+            p50_secs: self.percentile(50),
+            p95_secs: self.percentile(95),
+            p99_secs: self.percentile(99),
+        }
+    }
+}
+
+/// Inter-arrival statistics of a rollup's events, overall and per category
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CadenceStats {
+    pub events: IntervalStats,
+    pub batch: IntervalStats,
+    pub proof: IntervalStats,
+    pub finalization: IntervalStats,
+}
+
+impl CadenceStats {
+    pub fn summary(&self) -> CadenceSummary {
+        CadenceSummary {
+            events: self.events.summary(),
+            batch: self.batch.summary(),
+            proof: self.proof.summary(),
+            finalization: self.finalization.summary(),
+        }
+    }
+}
+
+/// Interval statistics as reported by the health API
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IntervalSummary {
+    pub samples: usize,
+    pub ewma_secs: Option<u64>,
+    pub p50_secs: Option<u64>,
+    pub p95_secs: Option<u64>,
+    pub p99_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CadenceSummary {
+    pub events: IntervalSummary,
+    pub batch: IntervalSummary,
+    pub proof: IntervalSummary,
+    pub finalization: IntervalSummary,
+}
+
+/// Deriving thresholds from observed cadence instead of fixed constants.
+///
+/// Stage cadences become the p95 interval of their category and the delayed
+/// threshold the p99 interval of any event, with the halted threshold keeping
+/// its configured ratio to it. Each learned value is clamped between
+/// `min_factor` and `max_factor` times the configured one.
+#[derive(Debug, Clone)]
+pub struct AdaptiveThresholds {
+    /// Intervals needed before a category's learned value is used
+    pub min_samples: usize,
+    /// Floor, as a multiple of the configured threshold
+    pub min_factor: f64,
+    /// Ceiling, as a multiple of the configured threshold
+    pub max_factor: f64,
+}
+
+impl Default for AdaptiveThresholds {
+    fn default() -> Self {
+        Self {
+            min_samples: 20,
+            min_factor: 0.5,
+            max_factor: 2.0,
+        }
+    }
+}
+
+impl AdaptiveThresholds {
+    /// Percentile of `stats` once enough intervals were observed
+    fn learn(&self, stats: &IntervalStats, p: u8) -> Option<u64> {
+        if stats.len() < self.min_samples {
+            return None;
+        }
+        stats.percentile(p)
+    }
+
+    /// Threshold from a configured value and what was learned
+    fn threshold(&self, configured: u64, learned: Option<u64>) -> Threshold {
+        let floor = (configured as f64 * self.min_factor) as u64;
+        let ceiling = (configured as f64 * self.max_factor) as u64;
+        Threshold {
+            configured,
+            learned,
+            effective: learned.map_or(configured, |l| l.clamp(floor, ceiling.max(floor))),
+        }
+    }
+}
+
+/// A health threshold in seconds
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Threshold {
+    /// Static value from the health config
+    pub configured: u64,
+    /// Derived from observed cadence, once there are enough samples
+    pub learned: Option<u64>,
+    /// Value health is evaluated against
+    pub effective: u64,
+}
+
+impl Threshold {
+    fn fixed(configured: u64) -> Self {
+        Self {
+            configured,
+            learned: None,
+            effective: configured,
+        }
+    }
+}
+
+/// Thresholds a rollup's health is evaluated against
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HealthThresholds {
+    /// Whether learned values are applied
+    pub adaptive: bool,
+    pub delayed: Threshold,
+    pub halted: Threshold,
+    pub batch_cadence: Threshold,
+    pub proof_cadence: Threshold,
+    pub finalization_cadence: Threshold,
+}
+
+impl HealthThresholds {
+    /// Thresholds for `config` given the observed cadence
+    pub fn derive(config: &RollupHealthConfig, stats: &CadenceStats) -> Self {
+        let Some(adaptive) = &config.adaptive else {
+            return Self {
+                adaptive: false,
+                delayed: Threshold::fixed(config.delayed_threshold_secs),
+                halted: Threshold::fixed(config.halted_threshold_secs),
+                batch_cadence: Threshold::fixed(config.batch_cadence_secs),
+                proof_cadence: Threshold::fixed(config.proof_cadence_secs),
+                finalization_cadence: Threshold::fixed(config.finalization_cadence_secs),
+            };
+        };
+
+        let delayed = adaptive.learn(&stats.events, 99);
+        // Keep the configured halted/delayed ratio
+        let halted = delayed.map(|d| {
+            let ratio =
+                config.halted_threshold_secs as f64 / config.delayed_threshold_secs.max(1) as f64;
+            (d as f64 * ratio) as u64
+        });
+
+        Self {
+            adaptive: true,
+            delayed: adaptive.threshold(config.delayed_threshold_secs, delayed),
+            halted: adaptive.threshold(config.halted_threshold_secs, halted),
+            batch_cadence: adaptive
+                .threshold(config.batch_cadence_secs, adaptive.learn(&stats.batch, 95)),
+            proof_cadence: adaptive
+                .threshold(config.proof_cadence_secs, adaptive.learn(&stats.proof, 95)),
+            finalization_cadence: adaptive.threshold(
+                config.finalization_cadence_secs,
+                adaptive.learn(&stats.finalization, 95),
+            ),
+        }
+    }
+
+    /// Copy of `config` with the effective thresholds applied
+    pub fn apply(&self, config: &RollupHealthConfig) -> RollupHealthConfig {
+        RollupHealthConfig {
+            delayed_threshold_secs: self.delayed.effective,
+            halted_threshold_secs: self.halted.effective,
+            batch_cadence_secs: self.batch_cadence.effective,
+            proof_cadence_secs: self.proof_cadence.effective,
+            finalization_cadence_secs: self.finalization_cadence.effective,
+            ..config.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(intervals: impl IntoIterator<Item = u64>) -> IntervalStats {
+        let mut stats = IntervalStats::default();
+        for interval in intervals {
+            stats.observe(interval);
+        }
+        stats
+    }
+
+    #[test]
+    fn test_interval_percentiles_and_ewma() {
+        let summary = stats(1..=100).summary();
+        assert_eq!(summary.samples, 100);
+        assert_eq!(summary.p50_secs, Some(50));
+        assert_eq!(summary.p95_secs, Some(95));
+        assert_eq!(summary.p99_secs, Some(99));
+        assert!(summary.ewma_secs.unwrap() > 90);
+
+        assert_eq!(IntervalStats::default().percentile(50), None);
+        assert_eq!(stats([7]).percentile(99), Some(7));
+    }
+
+    #[test]
+    fn test_window_keeps_latest_samples() {
+        let stats = stats((0..MAX_SAMPLES as u64).map(|_| 10).chain([600]));
+        assert_eq!(stats.len(), MAX_SAMPLES);
+        assert_eq!(stats.percentile(100), Some(600));
+    }
+
+    #[test]
+    fn test_static_thresholds_without_adaptive_mode() {
+        let config = RollupHealthConfig::default();
+        let cadence = CadenceStats {
+            batch: stats((0..50).map(|_| 60)),
+            ..Default::default()
+        };
+        let thresholds = HealthThresholds::derive(&config, &cadence);
+        assert!(!thresholds.adaptive);
+        assert_eq!(thresholds.batch_cadence.learned, None);
+        assert_eq!(thresholds.batch_cadence.effective, 300);
+        assert_eq!(thresholds.apply(&config).batch_cadence_secs, 300);
+    }
+
+    #[test]
+    fn test_learned_thresholds_clamped_to_configured() {
+        let config = RollupHealthConfig {
+            adaptive: Some(AdaptiveThresholds::default()),
+            ..Default::default()
+        };
+        let cadence = CadenceStats {
+            // Batches every 4 minutes: within bounds
+            batch: stats((0..50).map(|_| 240)),
+            // Proofs far slower than configured: capped at the ceiling
+            proof: stats((0..50).map(|_| 6 * 3600)),
+            // Events every 2 minutes: delayed floored at half of 600
+            events: stats((0..50).map(|_| 120)),
+            // Too few samples to learn from
+            finalization: stats([60]),
+        };
+
+        let thresholds = HealthThresholds::derive(&config, &cadence);
+        assert_eq!(thresholds.batch_cadence.learned, Some(240));
+        assert_eq!(thresholds.batch_cadence.effective, 240);
+        assert_eq!(thresholds.proof_cadence.learned, Some(6 * 3600));
+        assert_eq!(thresholds.proof_cadence.effective, 7200);
+        assert_eq!(thresholds.delayed.learned, Some(120));
+        assert_eq!(thresholds.delayed.effective, 300);
+        assert_eq!(thresholds.halted.learned, Some(360));
+        assert_eq!(thresholds.halted.effective, 900);
+        assert_eq!(thresholds.finalization_cadence.learned, None);
+        assert_eq!(thresholds.finalization_cadence.effective, 7200);
+
+        let effective = thresholds.apply(&config);
+        assert_eq!(effective.proof_cadence_secs, 7200);
+        assert_eq!(effective.delayed_threshold_secs, 300);
+    }
+}
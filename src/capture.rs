@@ -0,0 +1,169 @@
+use crate::clock::Clock;
+use crate::sequencer::L2Block;
+use ethers::types::Log;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// One line of an NDJSON capture file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaptureEntry {
+    /// Unix timestamp when the input was received
+    pub at: u64,
+    #[serde(flatten)]
+    pub record: CaptureRecord,
+}
+
+/// A raw input to the watchers, as received
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CaptureRecord {
+    /// An L1 log, with its block's timestamp if it was known
+    L1Log {
+        log: Box<Log>,
+        block_timestamp: Option<u64>,
+        /// Fetched by a targeted gap backfill behind the stream's cursor
+        #[serde(default)]
+        refill: bool,
+    },
+    /// An L1 head seen by the subscription or a poll
+    L1Head { block: u64, timestamp: Option<u64> },
+    /// An L2 sequencer poll; `block` is `None` when the poll failed
+    L2Poll {
+        rollup: String,
+        block: Option<L2Block>,
+    },
+}
+
+/// Appends capture entries to an NDJSON file.
+///
+/// Each entry is flushed as it is written so a capture survives the process
+/// being killed.
+#[derive(Clone)]
+pub struct Recorder {
+    writer: Arc<Mutex<BufWriter<File>>>,
+    clock: Clock,
+}
+
+impl Recorder {
+    /// Open `path` for appending, creating it if needed
+    pub fn create(path: &Path) -> eyre::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| eyre::eyre!("Failed to open capture {}: {}", path.display(), e))?;
+        Ok(Self {
+            writer: Arc::new(Mutex::new(BufWriter::new(file))),
+            clock: Clock::system(),
+        })
+    }
+
+    /// Stamp entries with the given clock instead of the system clock
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Append a record stamped with the current time
+    pub fn record(&self, record: CaptureRecord) {
+        let entry = CaptureEntry {
+            at: self.clock.now(),
+            record,
+        };
+        let line = match serde_json::to_string(&entry) {
+            Ok(line) => line,
+            Err(e) => {
+                tracing::error!(error = ?e, "Failed to serialize capture entry");
+                return;
+            }
+        };
+
+        let mut writer = match self.writer.lock() {
+            Ok(writer) => writer,
+            Err(poisoned) => {
+                tracing::error!("Mutex poisoned in record, recovering");
+                poisoned.into_inner()
+            }
+        };
+        if let Err(e) = writeln!(writer, "{}", line).and_then(|_| writer.flush()) {
+            tracing::error!(error = ?e, "Failed to write capture entry");
+        }
+    }
+}
+
+/// Read every entry of a capture file, in order
+pub fn read_capture(path: &Path) -> eyre::Result<Vec<CaptureEntry>> {
+    let file = File::open(path)
+        .map_err(|e| eyre::eyre!("Failed to open capture {}: {}", path.display(), e))?;
+
+    let mut entries = Vec::new();
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = serde_json::from_str(&line).map_err(|e| {
+            eyre::eyre!(
+                "Invalid capture entry at {}:{}: {}",
+                path.display(),
+                index + 1,
+                e
+            )
+        })?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::{H256, U64};
+
+    #[test]
+    fn test_record_and_read_capture() {
+        let path = std::env::temp_dir().join(format!("capture-{}.ndjson", rand::random::<u64>()));
+        let recorder = Recorder::create(&path)
+            .unwrap()
+            .with_clock(Clock::manual(100));
+
+        let log = Log {
+            topics: vec![H256::repeat_byte(0x01)],
+            block_number: Some(U64::from(42)),
+            ..Default::default()
+        };
+        let records = vec![
+            CaptureRecord::L1Log {
+                log: Box::new(log),
+                block_timestamp: Some(90),
+                refill: false,
+            },
+            CaptureRecord::L1Head {
+                block: 43,
+                timestamp: None,
+            },
+            CaptureRecord::L2Poll {
+                rollup: "base".to_string(),
+                block: Some(L2Block {
+                    number: Some(7),
+                    timestamp: 95,
+                }),
+            },
+        ];
+        for record in &records {
+            recorder.record(record.clone());
+        }
+
+        let entries = read_capture(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(entries.len(), 3);
+        assert!(entries.iter().all(|e| e.at == 100));
+        assert_eq!(
+            entries.into_iter().map(|e| e.record).collect::<Vec<_>>(),
+            records
+        );
+    }
+}
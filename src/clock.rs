@@ -0,0 +1,76 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Source of the current unix time.
+///
+/// Live services read the system clock; replays drive a virtual clock forward
+/// to each captured entry's time so health ages match the recording.
+#[derive(Debug, Clone, Default)]
+pub struct Clock {
+    /// Virtual time in unix seconds, `None` for the system clock
+    virtual_now: Option<Arc<AtomicU64>>,
+}
+
+impl Clock {
+    /// The system clock
+    pub fn system() -> Self {
+        Self::default()
+    }
+
+    /// A virtual clock starting at `now`
+    pub fn manual(now: u64) -> Self {
+        Self {
+            virtual_now: Some(Arc::new(AtomicU64::new(now))),
+        }
+    }
+
+    /// Whether this is a virtual clock
+    pub fn is_manual(&self) -> bool {
+        self.virtual_now.is_some()
+    }
+
+    /// Current unix timestamp in seconds
+    pub fn now(&self) -> u64 {
+        match &self.virtual_now {
+            Some(now) => now.load(Ordering::SeqCst),
+            None => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        }
+    }
+
+    /// Move a virtual clock forward to `now` (never backward); no-op for the
+    /// system clock
+    pub fn set(&self, now: u64) {
+        if let Some(current) = &self.virtual_now {
+            current.fetch_max(now, Ordering::SeqCst);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manual_clock_moves_forward_only() {
+        let clock = Clock::manual(100);
+        let shared = clock.clone();
+        assert!(clock.is_manual());
+
+        shared.set(160);
+        assert_eq!(clock.now(), 160);
+        shared.set(120);
+        assert_eq!(clock.now(), 160);
+    }
+
+    #[test]
+    fn test_system_clock_ignores_set() {
+        let clock = Clock::system();
+        assert!(!clock.is_manual());
+        clock.set(1);
+        assert!(clock.now() > 1_600_000_000);
+    }
+}
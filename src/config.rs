@@ -1,4 +1,6 @@
+use std::collections::HashMap;
 use std::env;
+use std::path::PathBuf;
 use std::time::Duration;
 
 /// Server configuration
@@ -8,6 +10,8 @@ pub struct ServerConfig {
     pub host: String,
     /// Port to listen on
     pub port: u16,
+    /// Bearer token required by the write routes, which are disabled if unset
+    pub admin_token: Option<String>,
 }
 
 impl Default for ServerConfig {
@@ -19,6 +23,7 @@ impl Default for ServerConfig {
                 .and_then(|p| p.parse().ok())
                 .or_else(|| env::var("PORT").ok().and_then(|p| p.parse().ok()))
                 .unwrap_or(8080),
+            admin_token: env::var("ADMIN_TOKEN").ok().filter(|t| !t.is_empty()),
         }
     }
 }
@@ -30,6 +35,116 @@ impl ServerConfig {
     }
 }
 
+/// How L1 logs are ingested
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum L1Mode {
+    /// `eth_subscribe` over WebSocket, falling back to HTTP polling when the
+    /// connection keeps failing past `ReconnectConfig.max_retries`
+    #[default]
+    Ws,
+    /// Poll `eth_getLogs` over HTTP only
+    Http,
+}
+
+impl L1Mode {
+    /// Parse an `L1_MODE` value ("ws" or "http")
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "ws" | "websocket" => Some(Self::Ws),
+            "http" | "poll" => Some(Self::Http),
+            _ => None,
+        }
+    }
+}
+
+/// L1 RPC endpoint configuration
+#[derive(Debug, Clone)]
+pub struct L1Config {
+    /// Ingestion mode (`L1_MODE`: "ws" or "http")
+    pub mode: L1Mode,
+    /// Ethereum L1 WebSocket RPC URLs, in order of preference (`RPC_WS`, comma-separated)
+    pub ws_urls: Vec<String>,
+    /// Ethereum L1 HTTP RPC URLs, in order of preference (`RPC_HTTP`, comma-separated)
+    pub http_urls: Vec<String>,
+    /// Interval between endpoint health probes
+    pub probe_interval: Duration,
+    /// Interval between `eth_getLogs` polls in HTTP mode
+    pub poll_interval: Duration,
+    /// Log backfill on startup and after reconnects
+    pub backfill: BackfillConfig,
+}
+
+impl Default for L1Config {
+    fn default() -> Self {
+        let mode = match env::var("L1_MODE") {
+            Ok(value) => L1Mode::parse(&value).unwrap_or_else(|| {
+                tracing::warn!(value = %value, "Invalid L1_MODE, using ws");
+                L1Mode::Ws
+            }),
+            Err(_) => L1Mode::Ws,
+        };
+
+        Self {
+            mode,
+            ws_urls: url_list("RPC_WS"),
+            http_urls: url_list("RPC_HTTP"),
+            probe_interval: Duration::from_secs(
+                env::var("L1_PROBE_INTERVAL_SECS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(30),
+            ),
+            poll_interval: Duration::from_secs(
+                env::var("L1_POLL_INTERVAL_SECS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(12), // one L1 slot
+            ),
+            backfill: BackfillConfig::default(),
+        }
+    }
+}
+
+/// Read a comma-separated list of URLs from an environment variable
+fn url_list(var: &str) -> Vec<String> {
+    env::var(var)
+        .map(|v| parse_url_list(&v))
+        .unwrap_or_default()
+}
+
+/// Split a comma-separated list of URLs, dropping empty entries
+fn parse_url_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|u| u.trim().to_string())
+        .filter(|u| !u.is_empty())
+        .collect()
+}
+
+/// L1 log backfill configuration
+#[derive(Debug, Clone)]
+pub struct BackfillConfig {
+    /// Blocks to look back on a cold start, for streams with no saved cursor
+    pub lookback_blocks: u64,
+    /// Maximum block range per `eth_getLogs` request
+    pub chunk_size: u64,
+}
+
+impl Default for BackfillConfig {
+    fn default() -> Self {
+        Self {
+            lookback_blocks: env::var("BACKFILL_LOOKBACK_BLOCKS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(7200), // ~1 day of L1 blocks
+            chunk_size: env::var("BACKFILL_CHUNK_BLOCKS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(1000),
+        }
+    }
+}
+
 /// Broadcast channel configuration
 #[derive(Debug, Clone)]
 pub struct BroadcastConfig {
@@ -53,6 +168,12 @@ impl Default for BroadcastConfig {
 pub struct HealthCheckConfig {
     /// Interval between health checks
     pub check_interval: Duration,
+    /// Learn thresholds from observed event cadence
+    pub adaptive_thresholds: bool,
+    /// TOML file overriding the built-in thresholds
+    pub config_path: PathBuf,
+    /// Interval between checks of the thresholds file for changes
+    pub config_poll_interval: Duration,
 }
 
 impl Default for HealthCheckConfig {
@@ -64,10 +185,79 @@ impl Default for HealthCheckConfig {
                     .and_then(|s| s.parse().ok())
                     .unwrap_or(60),
             ),
+            adaptive_thresholds: env::var("HEALTH_ADAPTIVE_THRESHOLDS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(false),
+            config_path: env::var("HEALTH_CONFIG")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| PathBuf::from("health.toml")),
+            config_poll_interval: Duration::from_secs(
+                env::var("HEALTH_CONFIG_POLL_SECS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(10),
+            ),
         }
     }
 }
 
+/// Webhook alert delivery configuration
+#[derive(Debug, Clone)]
+pub struct AlertConfig {
+    /// Delivery attempts per alert and target before giving up
+    pub max_attempts: u32,
+    /// Wait after the first failed attempt, doubling on each retry
+    pub base_backoff: Duration,
+    /// Longest wait between attempts
+    pub max_backoff: Duration,
+    /// Timeout of each delivery request
+    pub timeout: Duration,
+    /// How often open alerts are checked for reminders, escalations and
+    /// triggers held back as possibly flapping
+    pub policy_interval: Duration,
+}
+
+impl Default for AlertConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: env::var("ALERT_MAX_ATTEMPTS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(5),
+            base_backoff: Duration::from_millis(
+                env::var("ALERT_BASE_BACKOFF_MS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(1000),
+            ),
+            max_backoff: Duration::from_secs(60),
+            timeout: Duration::from_secs(
+                env::var("ALERT_TIMEOUT_SECS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(10),
+            ),
+            policy_interval: Duration::from_secs(
+                env::var("ALERT_POLICY_INTERVAL_SECS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(15),
+            ),
+        }
+    }
+}
+
+impl AlertConfig {
+    /// Wait before retrying after `attempt` failed attempts (exponential backoff)
+    pub fn backoff_for_attempt(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
 /// Reconnection configuration for WebSocket streams
 #[derive(Debug, Clone)]
 pub struct ReconnectConfig {
@@ -116,68 +306,32 @@ impl ReconnectConfig {
 /// L2 sequencer monitoring configuration
 #[derive(Debug, Clone)]
 pub struct SequencerConfig {
-    /// Arbitrum L2 RPC URL (HTTP)
-    pub arbitrum_l2_rpc: Option<String>,
-    /// Base L2 RPC URL (HTTP)
-    pub base_l2_rpc: Option<String>,
-    /// Starknet L2 RPC URL (HTTP) - uses Starknet JSON-RPC (non-EVM)
-    pub starknet_l2_rpc: Option<String>,
-    /// Optimism L2 RPC URL (HTTP)
-    pub optimism_l2_rpc: Option<String>,
-    /// zkSync Era L2 RPC URL (HTTP)
-    pub zksync_l2_rpc: Option<String>,
-    /// Arbitrum L2 polling interval
-    pub arbitrum_poll_interval: Duration,
-    /// Base L2 polling interval
-    pub base_poll_interval: Duration,
-    /// Starknet L2 polling interval
-    pub starknet_poll_interval: Duration,
-    /// Optimism L2 polling interval
-    pub optimism_poll_interval: Duration,
-    /// zkSync Era L2 polling interval
-    pub zksync_poll_interval: Duration,
+    /// L2 RPC URLs (HTTP) keyed by rollup name, from `<ROLLUP>_L2_RPC`
+    pub l2_rpcs: HashMap<String, String>,
+    /// L2 polling intervals keyed by rollup name, from `<ROLLUP>_L2_POLL_MS`
+    pub poll_intervals: HashMap<String, Duration>,
     /// Threshold before declaring sequencer downtime
     pub downtime_threshold: Duration,
 }
 
 impl Default for SequencerConfig {
     fn default() -> Self {
+        let mut l2_rpcs = HashMap::new();
+        let mut poll_intervals = HashMap::new();
+
+        for (key, value) in env::vars() {
+            if let Some(rollup) = key.strip_suffix("_L2_RPC") {
+                l2_rpcs.insert(rollup.to_lowercase(), value);
+            } else if let Some(rollup) = key.strip_suffix("_L2_POLL_MS") {
+                if let Ok(ms) = value.parse() {
+                    poll_intervals.insert(rollup.to_lowercase(), Duration::from_millis(ms));
+                }
+            }
+        }
+
         Self {
-            arbitrum_l2_rpc: env::var("ARBITRUM_L2_RPC").ok(),
-            base_l2_rpc: env::var("BASE_L2_RPC").ok(),
-            starknet_l2_rpc: env::var("STARKNET_L2_RPC").ok(),
-            optimism_l2_rpc: env::var("OPTIMISM_L2_RPC").ok(),
-            zksync_l2_rpc: env::var("ZKSYNC_L2_RPC").ok(),
-            arbitrum_poll_interval: Duration::from_millis(
-                env::var("ARBITRUM_L2_POLL_MS")
-                    .ok()
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or(2000),
-            ),
-            base_poll_interval: Duration::from_millis(
-                env::var("BASE_L2_POLL_MS")
-                    .ok()
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or(5000),
-            ),
-            starknet_poll_interval: Duration::from_millis(
-                env::var("STARKNET_L2_POLL_MS")
-                    .ok()
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or(10000),
-            ),
-            optimism_poll_interval: Duration::from_millis(
-                env::var("OPTIMISM_L2_POLL_MS")
-                    .ok()
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or(5000),
-            ),
-            zksync_poll_interval: Duration::from_millis(
-                env::var("ZKSYNC_L2_POLL_MS")
-                    .ok()
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or(5000),
-            ),
+            l2_rpcs,
+            poll_intervals,
             downtime_threshold: Duration::from_secs(
                 env::var("SEQUENCER_DOWNTIME_THRESHOLD_SECS")
                     .ok()
@@ -188,14 +342,108 @@ impl Default for SequencerConfig {
     }
 }
 
+impl SequencerConfig {
+    /// Get the L2 RPC URL for a rollup, if sequencer monitoring is enabled for it
+    pub fn rpc_url(&self, rollup: &str) -> Option<&str> {
+        self.l2_rpcs.get(rollup).map(String::as_str)
+    }
+
+    /// Get the polling interval for a rollup, falling back to `default`
+    pub fn poll_interval(&self, rollup: &str, default: Duration) -> Duration {
+        self.poll_intervals.get(rollup).copied().unwrap_or(default)
+    }
+}
+
+/// Rollup definitions configuration
+#[derive(Debug, Clone)]
+pub struct RollupsConfig {
+    /// Path to the TOML file defining tracked rollups
+    pub path: PathBuf,
+}
+
+impl Default for RollupsConfig {
+    fn default() -> Self {
+        Self {
+            path: env::var("ROLLUPS_CONFIG")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| PathBuf::from("rollups.toml")),
+        }
+    }
+}
+
+/// Persistent storage configuration
+#[derive(Debug, Clone)]
+pub struct StoreConfig {
+    /// SQLite database file; `None` keeps all state in memory only
+    pub path: Option<PathBuf>,
+}
+
+impl Default for StoreConfig {
+    fn default() -> Self {
+        let path = env::var("DATABASE_PATH").unwrap_or_else(|_| "rollup-status.db".to_string());
+        Self {
+            path: (!path.is_empty()).then(|| PathBuf::from(path)),
+        }
+    }
+}
+
+/// Recording of raw watcher inputs
+#[derive(Debug, Clone)]
+pub struct CaptureConfig {
+    /// NDJSON capture file (`CAPTURE_PATH`); `None` disables recording
+    pub path: Option<PathBuf>,
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self {
+            path: env::var("CAPTURE_PATH")
+                .ok()
+                .filter(|p| !p.is_empty())
+                .map(PathBuf::from),
+        }
+    }
+}
+
+/// Replay of a capture instead of watching live chains
+#[derive(Debug, Clone)]
+pub struct ReplayConfig {
+    /// NDJSON capture to replay (`REPLAY_PATH`); `None` watches live chains
+    pub path: Option<PathBuf>,
+    /// Playback speed relative to the recording; 0 replays as fast as possible
+    pub speed: f64,
+}
+
+impl Default for ReplayConfig {
+    fn default() -> Self {
+        Self {
+            path: env::var("REPLAY_PATH")
+                .ok()
+                .filter(|p| !p.is_empty())
+                .map(PathBuf::from),
+            speed: env::var("REPLAY_SPEED")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .filter(|s: &f64| s.is_finite() && *s >= 0.0)
+                .unwrap_or(60.0), // a minute of recording per second
+        }
+    }
+}
+
 /// Main application configuration
 #[derive(Debug, Clone, Default)]
 pub struct Config {
     pub server: ServerConfig,
+    pub l1: L1Config,
     pub broadcast: BroadcastConfig,
     pub health: HealthCheckConfig,
+    pub alerts: AlertConfig,
     pub reconnect: ReconnectConfig,
     pub sequencer: SequencerConfig,
+    pub rollups: RollupsConfig,
+    pub store: StoreConfig,
+    pub capture: CaptureConfig,
+    pub replay: ReplayConfig,
 }
 
 impl Config {
@@ -214,9 +462,23 @@ mod tests {
         let config = Config::default();
         assert_eq!(config.server.host, "0.0.0.0");
         assert_eq!(config.server.port, 8080);
+        assert!(config.server.admin_token.is_none());
         assert_eq!(config.broadcast.channel_capacity, 1000);
         assert_eq!(config.health.check_interval, Duration::from_secs(60));
+        assert_eq!(config.health.config_path, PathBuf::from("health.toml"));
+        assert_eq!(config.health.config_poll_interval, Duration::from_secs(10));
+        assert_eq!(config.alerts.max_attempts, 5);
+        assert_eq!(config.alerts.base_backoff, Duration::from_secs(1));
         assert_eq!(config.reconnect.max_retries, 10);
+        assert_eq!(config.l1.mode, L1Mode::Ws);
+        assert_eq!(config.l1.poll_interval, Duration::from_secs(12));
+        assert_eq!(config.l1.backfill.lookback_blocks, 7200);
+        assert_eq!(config.l1.backfill.chunk_size, 1000);
+        assert_eq!(config.rollups.path, PathBuf::from("rollups.toml"));
+        assert_eq!(config.store.path, Some(PathBuf::from("rollup-status.db")));
+        assert!(config.capture.path.is_none());
+        assert!(config.replay.path.is_none());
+        assert_eq!(config.replay.speed, 60.0);
     }
 
     #[test]
@@ -236,11 +498,64 @@ mod tests {
         assert_eq!(config.backoff_for_attempt(5), Duration::from_secs(30)); // Capped at max
     }
 
+    #[test]
+    fn test_alert_backoff() {
+        let config = AlertConfig {
+            max_attempts: 5,
+            base_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(3),
+            timeout: Duration::from_secs(10),
+            policy_interval: Duration::from_secs(15),
+        };
+
+        assert_eq!(config.backoff_for_attempt(1), Duration::from_millis(500));
+        assert_eq!(config.backoff_for_attempt(2), Duration::from_secs(1));
+        assert_eq!(config.backoff_for_attempt(3), Duration::from_secs(2));
+        assert_eq!(config.backoff_for_attempt(4), Duration::from_secs(3)); // Capped at max
+    }
+
+    #[test]
+    fn test_sequencer_config_lookup() {
+        let config = SequencerConfig {
+            l2_rpcs: HashMap::from([("base".to_string(), "https://mainnet.base.org".to_string())]),
+            poll_intervals: HashMap::from([("base".to_string(), Duration::from_millis(1000))]),
+            downtime_threshold: Duration::from_secs(30),
+        };
+
+        assert_eq!(config.rpc_url("base"), Some("https://mainnet.base.org"));
+        assert_eq!(config.rpc_url("arbitrum"), None);
+        assert_eq!(
+            config.poll_interval("base", Duration::from_millis(5000)),
+            Duration::from_millis(1000)
+        );
+        assert_eq!(
+            config.poll_interval("arbitrum", Duration::from_millis(2000)),
+            Duration::from_millis(2000)
+        );
+    }
+
+    #[test]
+    fn test_parse_url_list() {
+        assert_eq!(
+            parse_url_list("wss://a.example/v2/key, wss://b.example/ws ,"),
+            vec!["wss://a.example/v2/key", "wss://b.example/ws"]
+        );
+        assert!(parse_url_list("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_l1_mode() {
+        assert_eq!(L1Mode::parse("ws"), Some(L1Mode::Ws));
+        assert_eq!(L1Mode::parse(" HTTP "), Some(L1Mode::Http));
+        assert_eq!(L1Mode::parse("grpc"), None);
+    }
+
     #[test]
     fn test_server_addr() {
         let config = ServerConfig {
             host: "127.0.0.1".to_string(),
             port: 3000,
+            admin_token: None,
         };
         assert_eq!(config.addr(), "127.0.0.1:3000");
     }
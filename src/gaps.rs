@@ -0,0 +1,240 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How a sequence number deviated from the expected next number
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GapKind {
+    /// Numbers between the expected and the received one never arrived
+    Skipped,
+    /// A number at or below the last one arrived again
+    Repeated,
+}
+
+/// A break in a strictly sequential batch stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchGap {
+    pub rollup: String,
+    pub stream: String,
+    pub kind: GapKind,
+    /// Number that was expected next
+    pub expected: u64,
+    /// Number that arrived instead
+    pub found: u64,
+    /// L1 block range that should contain the missing batches
+    pub from_block: u64,
+    pub to_block: u64,
+    /// Unix timestamp when the gap was detected
+    pub detected_at: u64,
+}
+
+impl BatchGap {
+    /// Human-readable issue for health reports
+    pub fn describe(&self) -> String {
+        match self.kind {
+            GapKind::Skipped => format!(
+                "Batch gap in {}: expected {}, got {} ({} missing)",
+                self.stream,
+                self.expected,
+                self.found,
+                self.found - self.expected
+            ),
+            GapKind::Repeated => format!(
+                "Repeated batch in {}: expected {}, got {}",
+                self.stream, self.expected, self.found
+            ),
+        }
+    }
+}
+
+/// Outcome of checking a sequence number against its stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceCheck {
+    /// The expected next number (or the first one seen)
+    InOrder,
+    /// Numbers were skipped; `from_block` is the L1 block of the last
+    /// in-order number
+    Skipped { expected: u64, from_block: u64 },
+    /// A number that was already passed and is not missing
+    Repeated { expected: u64 },
+    /// A missing number arrived; `resolved` once none are missing
+    Filled { resolved: bool },
+}
+
+/// Expected next number and missing ranges of one stream
+#[derive(Debug, Default)]
+struct StreamSequence {
+    next: u64,
+    /// L1 block of the highest number seen
+    last_block: u64,
+    /// Missing numbers as inclusive ranges
+    missing: Vec<(u64, u64)>,
+}
+
+impl StreamSequence {
+    /// Remove `number` from the missing ranges; returns whether it was missing
+    fn fill(&mut self, number: u64) -> bool {
+        let Some(index) = self
+            .missing
+            .iter()
+            .position(|&(from, to)| (from..=to).contains(&number))
+        else {
+            return false;
+        };
+        let (from, to) = self.missing.remove(index);
+        if from < number {
+            self.missing.push((from, number - 1));
+        }
+        if number < to {
+            self.missing.push((number + 1, to));
+        }
+        true
+    }
+}
+
+/// Tracks the expected next number of strictly sequential streams (e.g.
+/// Arbitrum batch sequence numbers, zkSync batch numbers) keyed by stream key.
+///
+/// Tracking starts at the first number seen after startup.
+#[derive(Debug, Default)]
+pub struct BatchSequences {
+    streams: HashMap<String, StreamSequence>,
+}
+
+impl BatchSequences {
+    /// Check a number emitted in L1 `block` and advance the stream
+    pub fn observe(&mut self, key: &str, number: u64, block: u64) -> SequenceCheck {
+        let Some(sequence) = self.streams.get_mut(key) else {
+            self.streams.insert(
+                key.to_string(),
+                StreamSequence {
+                    next: number.saturating_add(1),
+                    last_block: block,
+                    missing: Vec::new(),
+                },
+            );
+            return SequenceCheck::InOrder;
+        };
+
+        let expected = sequence.next;
+        if number == expected {
+            sequence.next = number.saturating_add(1);
+            sequence.last_block = block;
+            SequenceCheck::InOrder
+        } else if number > expected {
+            let from_block = sequence.last_block;
+            sequence.missing.push((expected, number - 1));
+            sequence.next = number.saturating_add(1);
+            sequence.last_block = block;
+            SequenceCheck::Skipped {
+                expected,
+                from_block,
+            }
+        } else if sequence.fill(number) {
+            SequenceCheck::Filled {
+                resolved: sequence.missing.is_empty(),
+            }
+        } else {
+            SequenceCheck::Repeated { expected }
+        }
+    }
+
+    /// Step back over a number whose log was removed by a reorg, so its
+    /// re-inclusion is in order again
+    pub fn rewind(&mut self, key: &str, number: u64) {
+        if let Some(sequence) = self.streams.get_mut(key) {
+            if sequence.next == number.saturating_add(1) {
+                sequence.next = number;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_order_and_skipped() {
+        let mut sequences = BatchSequences::default();
+        assert_eq!(
+            sequences.observe("a/batch", 10, 100),
+            SequenceCheck::InOrder
+        );
+        assert_eq!(
+            sequences.observe("a/batch", 11, 101),
+            SequenceCheck::InOrder
+        );
+        assert_eq!(
+            sequences.observe("a/batch", 14, 110),
+            SequenceCheck::Skipped {
+                expected: 12,
+                from_block: 101
+            }
+        );
+        assert_eq!(
+            sequences.observe("a/batch", 15, 111),
+            SequenceCheck::InOrder
+        );
+        // Streams are tracked independently
+        assert_eq!(sequences.observe("b/batch", 1, 100), SequenceCheck::InOrder);
+    }
+
+    #[test]
+    fn test_fill_and_repeat() {
+        let mut sequences = BatchSequences::default();
+        sequences.observe("a/batch", 10, 100);
+        sequences.observe("a/batch", 14, 110);
+
+        assert_eq!(
+            sequences.observe("a/batch", 12, 105),
+            SequenceCheck::Filled { resolved: false }
+        );
+        assert_eq!(
+            sequences.observe("a/batch", 12, 105),
+            SequenceCheck::Repeated { expected: 15 }
+        );
+        assert_eq!(
+            sequences.observe("a/batch", 11, 102),
+            SequenceCheck::Filled { resolved: false }
+        );
+        assert_eq!(
+            sequences.observe("a/batch", 13, 108),
+            SequenceCheck::Filled { resolved: true }
+        );
+        assert_eq!(
+            sequences.observe("a/batch", 14, 110),
+            SequenceCheck::Repeated { expected: 15 }
+        );
+    }
+
+    #[test]
+    fn test_rewind_after_reorg() {
+        let mut sequences = BatchSequences::default();
+        sequences.observe("a/batch", 10, 100);
+        sequences.observe("a/batch", 11, 101);
+        sequences.rewind("a/batch", 11);
+        assert_eq!(
+            sequences.observe("a/batch", 11, 102),
+            SequenceCheck::InOrder
+        );
+    }
+
+    #[test]
+    fn test_describe() {
+        let gap = BatchGap {
+            rollup: "arbitrum".to_string(),
+            stream: "batch".to_string(),
+            kind: GapKind::Skipped,
+            expected: 12,
+            found: 14,
+            from_block: 101,
+            to_block: 110,
+            detected_at: 0,
+        };
+        assert_eq!(
+            gap.describe(),
+            "Batch gap in batch: expected 12, got 14 (2 missing)"
+        );
+    }
+}
@@ -1,10 +1,213 @@
+use crate::alert_rules::{effective_rules, AlertRule};
+use crate::alerts::WebhookTarget;
+use crate::cadence::{
+    AdaptiveThresholds, CadenceStats, CadenceSummary, HealthThresholds, IntervalStats,
+};
+use crate::clock::Clock;
 use crate::config::HealthCheckConfig;
-use crate::types::{HealthStatus, RollupEvent};
-use std::collections::HashMap;
+use crate::gaps::{BatchGap, GapKind};
+use crate::incidents::{Incident, IncidentResolution};
+use crate::liveness::{WatcherDown, WatcherRegistry};
+use crate::maintenance::MaintenanceWindow;
+use crate::store::{HealthTransition, StageTransition, Store};
+use crate::types::{HealthChange, HealthStatus, RollupEvent, StreamMessage, StreamNotice};
+use std::collections::{BTreeMap, HashMap};
 use std::sync::{Arc, RwLock};
-use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast;
 use tokio_util::sync::CancellationToken;
 
+/// Health category an event counts toward
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthCategory {
+    /// Batch/state posting (refreshes `last_batch_time`)
+    Batch,
+    /// Proof/assertion posting (refreshes `last_proof_time`)
+    Proof,
+    /// Finalization of proven state (refreshes `last_finalization_time`)
+    Finalization,
+    /// State update proven and final on arrival (refreshes the batch, proof
+    /// and finalization times)
+    Settlement,
+    /// Other activity (only refreshes `last_event_time`)
+    #[default]
+    Activity,
+}
+
+impl HealthCategory {
+    /// Category for the built-in event type names
+    pub fn for_event_type(event_type: &str) -> Self {
+        match event_type {
+            "BatchDelivered" | "BlockCommit" => HealthCategory::Batch,
+            "StateUpdate" => HealthCategory::Settlement,
+            "ProofSubmitted" | "AssertionCreated" | "DisputeGameCreated" | "BlocksVerification" => {
+                HealthCategory::Proof
+            }
+            "ProofVerified" | "AssertionConfirmed" | "BlockExecution" => {
+                HealthCategory::Finalization
+            }
+            // Withdrawal proofs are user-initiated and say nothing about the prover
+            _ => HealthCategory::Activity,
+        }
+    }
+}
+
+/// Stage of a rollup's pipeline, each health-checked on its own
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStage {
+    /// Batches or state updates posted to L1
+    Batching,
+    /// Proofs, assertions or dispute games posted to L1
+    Proving,
+    /// Proven state finalized on L1
+    Finalization,
+    /// L2 block production
+    Sequencer,
+}
+
+impl HealthStage {
+    /// Every stage, in pipeline order
+    pub const ALL: [HealthStage; 4] = [
+        HealthStage::Batching,
+        HealthStage::Proving,
+        HealthStage::Finalization,
+        HealthStage::Sequencer,
+    ];
+
+    /// Name of the stage as written in config files and the API
+    pub fn name(&self) -> &'static str {
+        match self {
+            HealthStage::Batching => "batching",
+            HealthStage::Proving => "proving",
+            HealthStage::Finalization => "finalization",
+            HealthStage::Sequencer => "sequencer",
+        }
+    }
+}
+
+/// Health of one pipeline stage
+#[derive(Debug, Clone, serde::Serialize, PartialEq)]
+pub struct StageHealth {
+    pub stage: HealthStage,
+    /// `Disconnected` until the stage is first observed
+    pub status: HealthStatus,
+    /// Seconds since the stage last made progress
+    pub age_secs: Option<u64>,
+    /// Expected seconds between progress, for L1 stages
+    pub cadence_secs: Option<u64>,
+    /// Consecutive cadence windows closed without progress
+    pub missed_cadences: u32,
+}
+
+impl StageHealth {
+    /// When the first of the stage's missed cadence windows closed
+    fn missed_since(&self, now: u64) -> Option<u64> {
+        if self.missed_cadences == 0 {
+            return None;
+        }
+        let last = now.saturating_sub(self.age_secs?);
+        Some(last.saturating_add(self.cadence_secs?))
+    }
+}
+
+/// How missed cadence windows escalate an L1 stage's status
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EscalationPolicy {
+    /// Consecutive missed windows before the stage is `Delayed`
+    pub delayed_after: u32,
+    /// Consecutive missed windows before the stage is `Halted`
+    pub halted_after: u32,
+}
+
+impl Default for EscalationPolicy {
+    fn default() -> Self {
+        Self {
+            delayed_after: 2,
+            halted_after: 5,
+        }
+    }
+}
+
+impl EscalationPolicy {
+    /// Stage status after the given number of consecutive missed windows
+    pub fn status(&self, missed: u32) -> HealthStatus {
+        if missed >= self.halted_after {
+            HealthStatus::Halted
+        } else if missed >= self.delayed_after {
+            HealthStatus::Delayed
+        } else {
+            HealthStatus::Healthy
+        }
+    }
+}
+
+/// Rules deriving a rollup's overall status from its stages.
+///
+/// Each field is the worst overall status the stage can cause: a stage worse
+/// than its cap only degrades the rollup to the cap, and `Healthy` ignores the
+/// stage. Stages never observed are ignored.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HealthRules {
+    pub batching: HealthStatus,
+    pub proving: HealthStatus,
+    pub finalization: HealthStatus,
+    pub sequencer: HealthStatus,
+}
+
+impl Default for HealthRules {
+    fn default() -> Self {
+        Self {
+            batching: HealthStatus::Halted,
+            proving: HealthStatus::Halted,
+            // Finalization lags by design (challenge periods, execution delays)
+            finalization: HealthStatus::Delayed,
+            sequencer: HealthStatus::Halted,
+        }
+    }
+}
+
+impl HealthRules {
+    /// Worst overall status the given stage can cause
+    pub fn max_impact(&self, stage: HealthStage) -> &HealthStatus {
+        match stage {
+            HealthStage::Batching => &self.batching,
+            HealthStage::Proving => &self.proving,
+            HealthStage::Finalization => &self.finalization,
+            HealthStage::Sequencer => &self.sequencer,
+        }
+    }
+
+    /// Status a stage contributes to the overall status
+    pub(crate) fn impact(&self, stage: &StageHealth) -> HealthStatus {
+        if matches!(
+            stage.status,
+            HealthStatus::Disconnected | HealthStatus::Maintenance
+        ) {
+            return HealthStatus::Healthy;
+        }
+        let cap = self.max_impact(stage.stage);
+        if severity(&stage.status) > severity(cap) {
+            cap.clone()
+        } else {
+            stage.status.clone()
+        }
+    }
+}
+
+/// Ordering of observed statuses from best to worst
+fn severity(status: &HealthStatus) -> u8 {
+    match status {
+        HealthStatus::Healthy | HealthStatus::Maintenance => 0,
+        HealthStatus::Delayed => 1,
+        HealthStatus::Halted | HealthStatus::Disconnected => 2,
+    }
+}
+
 /// Configuration for health monitoring thresholds
 #[derive(Debug, Clone)]
 pub struct HealthConfig {
@@ -12,6 +215,12 @@ pub struct HealthConfig {
     pub rollups: HashMap<String, RollupHealthConfig>,
     /// Default thresholds for unknown rollups
     pub default: RollupHealthConfig,
+    /// Configured maintenance windows
+    pub maintenance: Vec<MaintenanceWindow>,
+    /// Endpoints alerted on health and sequencer transitions
+    pub webhooks: Vec<WebhookTarget>,
+    /// Rules routing alerts to the webhooks
+    pub alert_rules: Vec<AlertRule>,
 }
 
 /// Health thresholds for a specific rollup
@@ -25,19 +234,41 @@ pub struct RollupHealthConfig {
     pub batch_cadence_secs: u64,
     /// Maximum seconds between proof submissions
     pub proof_cadence_secs: u64,
+    /// Maximum seconds between finalizations
+    pub finalization_cadence_secs: u64,
+    /// How missed cadence windows escalate stage statuses
+    pub escalation: EscalationPolicy,
+    /// Learn thresholds from observed cadence, bounded by the ones above
+    pub adaptive: Option<AdaptiveThresholds>,
+    /// How stage statuses combine into the overall status
+    pub rules: HealthRules,
 }
 
 impl Default for RollupHealthConfig {
     fn default() -> Self {
         Self {
-            delayed_threshold_secs: 600, // 10 minutes
-            halted_threshold_secs: 1800, // 30 minutes
-            batch_cadence_secs: 300,     // 5 minutes
-            proof_cadence_secs: 3600,    // 1 hour
+            delayed_threshold_secs: 600,     // 10 minutes
+            halted_threshold_secs: 1800,     // 30 minutes
+            batch_cadence_secs: 300,         // 5 minutes
+            proof_cadence_secs: 3600,        // 1 hour
+            finalization_cadence_secs: 7200, // 2 hours
+            escalation: EscalationPolicy::default(),
+            adaptive: None,
+            rules: HealthRules::default(),
         }
     }
 }
 
+impl HealthConfig {
+    /// Learn every rollup's thresholds from its observed cadence
+    pub fn with_adaptive(mut self, adaptive: AdaptiveThresholds) -> Self {
+        for config in self.rollups.values_mut().chain([&mut self.default]) {
+            config.adaptive = Some(adaptive.clone());
+        }
+        self
+    }
+}
+
 impl Default for HealthConfig {
     fn default() -> Self {
         let mut rollups = HashMap::new();
@@ -46,10 +277,14 @@ impl Default for HealthConfig {
         rollups.insert(
             "arbitrum".to_string(),
             RollupHealthConfig {
-                delayed_threshold_secs: 600, // 10 minutes
-                halted_threshold_secs: 1800, // 30 minutes
-                batch_cadence_secs: 300,     // 5 minutes
-                proof_cadence_secs: 3600,    // 1 hour
+                delayed_threshold_secs: 600,     // 10 minutes
+                halted_threshold_secs: 1800,     // 30 minutes
+                batch_cadence_secs: 300,         // 5 minutes
+                proof_cadence_secs: 3600,        // 1 hour
+                finalization_cadence_secs: 7200, // 2 hours
+                escalation: EscalationPolicy::default(),
+                adaptive: None,
+                rules: HealthRules::default(),
             },
         );
 
@@ -57,10 +292,14 @@ impl Default for HealthConfig {
         rollups.insert(
             "starknet".to_string(),
             RollupHealthConfig {
-                delayed_threshold_secs: 7200, // 2 hours
-                halted_threshold_secs: 14400, // 4 hours
-                batch_cadence_secs: 3600,     // 1 hour
-                proof_cadence_secs: 7200,     // 2 hours
+                delayed_threshold_secs: 7200,    // 2 hours
+                halted_threshold_secs: 14400,    // 4 hours
+                batch_cadence_secs: 3600,        // 1 hour
+                proof_cadence_secs: 7200,        // 2 hours
+                finalization_cadence_secs: 7200, // state updates are final
+                escalation: EscalationPolicy::default(),
+                adaptive: None,
+                rules: HealthRules::default(),
             },
         );
 
@@ -68,10 +307,14 @@ impl Default for HealthConfig {
         rollups.insert(
             "base".to_string(),
             RollupHealthConfig {
-                delayed_threshold_secs: 3600, // 1 hour
-                halted_threshold_secs: 7200,  // 2 hours
-                batch_cadence_secs: 1800,     // 30 minutes
-                proof_cadence_secs: 3600,     // 1 hour
+                delayed_threshold_secs: 3600,    // 1 hour
+                halted_threshold_secs: 7200,     // 2 hours
+                batch_cadence_secs: 1800,        // 30 minutes
+                proof_cadence_secs: 3600,        // 1 hour
+                finalization_cadence_secs: 7200, // 2 hours
+                escalation: EscalationPolicy::default(),
+                adaptive: None,
+                rules: HealthRules::default(),
             },
         );
 
@@ -79,10 +322,14 @@ impl Default for HealthConfig {
         rollups.insert(
             "optimism".to_string(),
             RollupHealthConfig {
-                delayed_threshold_secs: 3600, // 1 hour
-                halted_threshold_secs: 7200,  // 2 hours
-                batch_cadence_secs: 1800,     // 30 minutes
-                proof_cadence_secs: 3600,     // 1 hour
+                delayed_threshold_secs: 3600,    // 1 hour
+                halted_threshold_secs: 7200,     // 2 hours
+                batch_cadence_secs: 1800,        // 30 minutes
+                proof_cadence_secs: 3600,        // 1 hour
+                finalization_cadence_secs: 7200, // 2 hours
+                escalation: EscalationPolicy::default(),
+                adaptive: None,
+                rules: HealthRules::default(),
             },
         );
 
@@ -90,16 +337,23 @@ impl Default for HealthConfig {
         rollups.insert(
             "zksync".to_string(),
             RollupHealthConfig {
-                delayed_threshold_secs: 7200, // 2 hours
-                halted_threshold_secs: 14400, // 4 hours
-                batch_cadence_secs: 1800,     // 30 minutes
-                proof_cadence_secs: 3600,     // 1 hour
+                delayed_threshold_secs: 7200,    // 2 hours
+                halted_threshold_secs: 14400,    // 4 hours
+                batch_cadence_secs: 1800,        // 30 minutes
+                proof_cadence_secs: 3600,        // 1 hour
+                finalization_cadence_secs: 3600, // 1 hour
+                escalation: EscalationPolicy::default(),
+                adaptive: None,
+                rules: HealthRules::default(),
             },
         );
 
         Self {
             rollups,
             default: RollupHealthConfig::default(),
+            maintenance: Vec::new(),
+            webhooks: Vec::new(),
+            alert_rules: Vec::new(),
         }
     }
 }
@@ -107,14 +361,33 @@ impl Default for HealthConfig {
 /// Tracks health state for all rollups
 #[derive(Clone)]
 pub struct HealthMonitor {
-    /// Health configuration
-    config: HealthConfig,
+    /// Health configuration, replaceable at runtime
+    config: Arc<RwLock<HealthConfig>>,
     /// Current health status for each rollup
     health_states: Arc<RwLock<HashMap<String, RollupHealthState>>>,
+    /// Rollups evaluated by `evaluate_all` and the background monitor
+    rollups: Arc<Vec<String>>,
+    /// Persistent store for health states and transitions, if enabled
+    store: Option<Store>,
+    /// Source of the current time (virtual during replays)
+    clock: Clock,
+    /// Stream receiving `HealthChanged` notices, if connected
+    stream: Option<broadcast::Sender<StreamMessage>>,
+    /// Liveness of the watchers feeding events, if tracked
+    watchers: Option<WatcherRegistry>,
+    /// Maintenance windows declared at runtime
+    maintenance: Arc<RwLock<Vec<MaintenanceWindow>>>,
 }
 
+/// Batch gaps kept per rollup, oldest dropped first
+const MAX_BATCH_GAPS: usize = 16;
+
+/// Rollups tracked when no registry is supplied
+const DEFAULT_ROLLUPS: [&str; 5] = ["arbitrum", "starknet", "base", "optimism", "zksync"];
+
 /// Internal health state tracking
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct RollupHealthState {
     /// Current health status
     pub status: HealthStatus,
@@ -122,14 +395,35 @@ pub struct RollupHealthState {
     pub last_batch_time: Option<u64>,
     /// Timestamp of last proof event
     pub last_proof_time: Option<u64>,
+    /// Timestamp of last finalization event
+    pub last_finalization_time: Option<u64>,
     /// Timestamp of last any event
     pub last_event_time: Option<u64>,
-    /// Count of consecutive missed cadences
+    /// Longest current streak of consecutive missed batch or proof windows
     pub missed_cadences: u32,
+    /// When the current missed-cadence streak started
+    pub missed_cadences_since: Option<u64>,
     /// Timestamp of last sequencer activity
     pub last_sequencer_activity: Option<u64>,
     /// Whether the L2 sequencer is producing blocks
     pub sequencer_producing: bool,
+    /// When the sequencer stopped producing blocks, while it is down
+    pub sequencer_down_since: Option<u64>,
+    /// Observed intervals between events, for adaptive thresholds
+    pub cadence: CadenceStats,
+    /// Incident open while the rollup is not healthy
+    pub incident: Option<Incident>,
+    /// Status of each stage as of the last evaluation
+    pub stage_statuses: BTreeMap<HealthStage, HealthStatus>,
+    /// Open gaps in sequential batch streams (tracking restarts with the process)
+    #[serde(skip)]
+    pub batch_gaps: Vec<BatchGap>,
+    /// Set while the watcher feeding the rollup's events has given up
+    #[serde(skip)]
+    pub watcher_down: Option<WatcherDown>,
+    /// Maintenance windows open as of the last evaluation
+    #[serde(skip)]
+    pub maintenance: Vec<MaintenanceWindow>,
 }
 
 impl Default for RollupHealthState {
@@ -138,14 +432,57 @@ impl Default for RollupHealthState {
             status: HealthStatus::Healthy,
             last_batch_time: None,
             last_proof_time: None,
+            last_finalization_time: None,
             last_event_time: None,
             missed_cadences: 0,
+            missed_cadences_since: None,
             last_sequencer_activity: None,
             sequencer_producing: false,
+            sequencer_down_since: None,
+            cadence: CadenceStats::default(),
+            incident: None,
+            stage_statuses: BTreeMap::new(),
+            batch_gaps: Vec::new(),
+            watcher_down: None,
+            maintenance: Vec::new(),
         }
     }
 }
 
+impl RollupHealthState {
+    /// Recount missed cadences and re-evaluate the status at `now`, returning
+    /// the stages whose status changed as `(stage, from, to)`
+    fn refresh(
+        &mut self,
+        config: &RollupHealthConfig,
+        now: u64,
+    ) -> Vec<(HealthStage, HealthStatus, HealthStatus)> {
+        let config = &HealthThresholds::derive(config, &self.cadence).apply(config);
+        let stages = HealthMonitor::stage_health(self, config, now);
+
+        let mut changes = Vec::new();
+        for stage in &stages {
+            let previous = self
+                .stage_statuses
+                .insert(stage.stage, stage.status.clone())
+                .unwrap_or(HealthStatus::Disconnected);
+            if previous != stage.status {
+                changes.push((stage.stage, previous, stage.status.clone()));
+            }
+        }
+
+        let streak = stages
+            .iter()
+            .filter(|s| matches!(s.stage, HealthStage::Batching | HealthStage::Proving))
+            .filter(|s| s.status != HealthStatus::Maintenance)
+            .max_by_key(|s| s.missed_cadences);
+        self.missed_cadences = streak.map_or(0, |s| s.missed_cadences);
+        self.missed_cadences_since = streak.and_then(|s| s.missed_since(now));
+        self.status = HealthMonitor::evaluate_health_static(self, config, now);
+        changes
+    }
+}
+
 /// Health check result with details
 #[derive(Debug, Clone, serde::Serialize, PartialEq)]
 pub struct HealthCheckResult {
@@ -154,8 +491,23 @@ pub struct HealthCheckResult {
     pub last_event_age_secs: Option<u64>,
     pub last_batch_age_secs: Option<u64>,
     pub last_proof_age_secs: Option<u64>,
+    pub last_finalization_age_secs: Option<u64>,
     pub sequencer_down: Option<bool>,
     pub sequencer_down_secs: Option<u64>,
+    /// Longest current streak of consecutive missed batch or proof windows
+    pub missed_cadences: u32,
+    pub missed_cadences_since: Option<u64>,
+    /// Configured, learned and effective thresholds
+    pub thresholds: HealthThresholds,
+    /// Observed intervals between events
+    pub cadence: CadenceSummary,
+    /// Incident open while the rollup is not healthy
+    pub incident: Option<Incident>,
+    /// Per-stage health the overall `status` is derived from
+    pub stages: Vec<StageHealth>,
+    pub batch_gaps: Vec<BatchGap>,
+    /// Maintenance windows currently open for the rollup
+    pub maintenance: Vec<MaintenanceWindow>,
     pub issues: Vec<String>,
 }
 
@@ -168,133 +520,610 @@ impl Default for HealthMonitor {
 impl HealthMonitor {
     /// Create a new health monitor
     pub fn new() -> Self {
+        Self::with_rollups(DEFAULT_ROLLUPS.iter().map(|r| r.to_string()).collect())
+    }
+
+    /// Create a health monitor evaluating the given rollups
+    pub fn with_rollups(rollups: Vec<String>) -> Self {
         Self {
-            config: HealthConfig::default(),
+            config: Arc::new(RwLock::new(HealthConfig::default())),
             health_states: Arc::new(RwLock::new(HashMap::new())),
+            rollups: Arc::new(rollups),
+            store: None,
+            clock: Clock::system(),
+            stream: None,
+            watchers: None,
+            maintenance: Arc::new(RwLock::new(Vec::new())),
         }
     }
 
-    /// Get config for a specific rollup
-    pub fn get_config(&self, rollup: &str) -> &RollupHealthConfig {
-        self.config
-            .rollups
-            .get(rollup)
-            .unwrap_or(&self.config.default)
+    /// Measure ages against the given clock instead of the system clock
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
     }
 
-    /// Get current unix timestamp
-    fn now() -> u64 {
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs()
+    /// Evaluate health with the given thresholds and stage rules
+    pub fn with_config(mut self, config: HealthConfig) -> Self {
+        self.config = Arc::new(RwLock::new(config));
+        self
     }
 
-    /// Record an event and update health state
-    pub fn record_event(&self, event: &RollupEvent) {
-        let now = Self::now();
+    /// Persist health states and status transitions to the given store
+    pub fn with_store(mut self, store: Store) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Report rollups as `Disconnected` while their watcher is dead
+    pub fn with_watchers(mut self, watchers: WatcherRegistry) -> Self {
+        self.watchers = Some(watchers);
+        self
+    }
+
+    /// Publish status transitions to the given stream
+    pub fn with_stream(mut self, stream: broadcast::Sender<StreamMessage>) -> Self {
+        self.stream = Some(stream);
+        self
+    }
+
+    /// Restore health states from the store
+    pub fn restore(&self) -> eyre::Result<()> {
+        let Some(store) = &self.store else {
+            return Ok(());
+        };
+        let restored = store.health_states()?;
+        let now = self.now();
+        let windows = store.maintenance_windows(now)?;
+        match self.maintenance.write() {
+            Ok(mut declared) => *declared = windows,
+            Err(poisoned) => {
+                tracing::error!("RwLock poisoned in restore, recovering");
+                *poisoned.into_inner() = windows;
+            }
+        }
 
         let mut states = match self.health_states.write() {
             Ok(states) => states,
             Err(poisoned) => {
-                tracing::error!(
-                    rollup = %event.rollup,
-                    "RwLock poisoned in record_event, recovering"
+                tracing::error!("RwLock poisoned in restore, recovering");
+                poisoned.into_inner()
+            }
+        };
+        for (rollup, mut state) in restored {
+            // Re-evaluate against the current time; the process may have been down a while
+            self.refresh(&rollup, &mut state, now);
+            states.insert(rollup, state);
+        }
+        Ok(())
+    }
+
+    /// Persist a rollup's health state, recording a transition if its status changed
+    fn persist(&self, rollup: &str, state: &RollupHealthState, previous: &HealthStatus) {
+        let Some(store) = &self.store else {
+            return;
+        };
+        if let Err(e) = store.save_health_state(rollup, state) {
+            tracing::error!(rollup = rollup, error = ?e, "Failed to persist health state");
+        }
+        if &state.status != previous {
+            let transition = HealthTransition {
+                rollup: rollup.to_string(),
+                from: previous.clone(),
+                to: state.status.clone(),
+                at: self.now(),
+            };
+            if let Err(e) = store.insert_transition(&transition) {
+                tracing::error!(rollup = rollup, error = ?e, "Failed to persist health transition");
+            }
+        }
+    }
+
+    /// Record a re-evaluated state: on a status change, track its incident and
+    /// publish a `HealthChanged` notice, then persist it with its stage changes
+    fn commit(
+        &self,
+        rollup: &str,
+        state: &mut RollupHealthState,
+        previous: &HealthStatus,
+        stage_changes: &[(HealthStage, HealthStatus, HealthStatus)],
+        now: u64,
+    ) {
+        if &state.status != previous {
+            let result = Self::check_state(rollup, Some(state), &self.get_config(rollup), now);
+            self.track_incident(state, &result, now);
+
+            if let Some(stream) = &self.stream {
+                let change = HealthChange {
+                    rollup: rollup.to_string(),
+                    previous: previous.clone(),
+                    status: state.status.clone(),
+                    reasons: result.issues,
+                    at: now,
+                };
+                let _ = stream.send(StreamMessage::Notice(StreamNotice::HealthChanged(change)));
+            }
+        }
+        self.persist(rollup, state, previous);
+
+        let Some(store) = &self.store else {
+            return;
+        };
+        for (stage, from, to) in stage_changes {
+            let transition = StageTransition {
+                rollup: rollup.to_string(),
+                stage: *stage,
+                from: from.clone(),
+                to: to.clone(),
+                at: now,
+            };
+            if let Err(e) = store.insert_stage_transition(&transition) {
+                tracing::error!(rollup = rollup, error = ?e, "Failed to persist stage transition");
+            }
+        }
+    }
+
+    /// Open, escalate or resolve the rollup's incident after a status change.
+    /// A dead watcher leaves the incident open, as the problem is not known
+    /// to be over.
+    fn track_incident(&self, state: &mut RollupHealthState, result: &HealthCheckResult, now: u64) {
+        let config = self.get_config(&result.rollup);
+        let rules = &config.rules;
+        let resolution = match result.status {
+            HealthStatus::Healthy => Some(IncidentResolution::Recovered),
+            HealthStatus::Maintenance => Some(IncidentResolution::Maintenance),
+            _ => None,
+        };
+        let changed = match (&mut state.incident, Incident::is_incident(&result.status)) {
+            (None, true) => {
+                let incident = Incident::open(result, rules, now);
+                tracing::warn!(
+                    rollup = %result.rollup,
+                    status = ?incident.peak,
+                    stage = ?incident.stage,
+                    "Incident opened"
                 );
+                state.incident = Some(incident);
+                true
+            }
+            (Some(incident), true) => incident.escalate(result, rules),
+            (Some(incident), false) => match resolution {
+                Some(resolution) => {
+                    incident.resolve(resolution, now);
+                    tracing::info!(
+                        rollup = %result.rollup,
+                        resolution = ?resolution,
+                        duration_secs = incident.duration_secs(now),
+                        "Incident resolved"
+                    );
+                    true
+                }
+                None => false,
+            },
+            (None, false) => false,
+        };
+        if !changed {
+            return;
+        }
+
+        let Some(incident) = &mut state.incident else {
+            return;
+        };
+        if let Some(store) = &self.store {
+            match store.save_incident(incident) {
+                Ok(id) => incident.id = Some(id),
+                Err(e) => {
+                    tracing::error!(rollup = %result.rollup, error = ?e, "Failed to persist incident")
+                }
+            }
+        }
+        if incident.ended_at.is_some() {
+            state.incident = None;
+        }
+    }
+
+    /// Names of the rollups this monitor evaluates
+    pub fn rollups(&self) -> &[String] {
+        &self.rollups
+    }
+
+    /// Get config for a specific rollup
+    pub fn get_config(&self, rollup: &str) -> RollupHealthConfig {
+        let config = match self.config.read() {
+            Ok(config) => config,
+            Err(poisoned) => {
+                tracing::error!(rollup = rollup, "RwLock poisoned in get_config, recovering");
                 poisoned.into_inner()
             }
         };
+        config
+            .rollups
+            .get(rollup)
+            .unwrap_or(&config.default)
+            .clone()
+    }
+
+    /// Replace the thresholds and stage rules of every rollup, then
+    /// re-evaluate health against them. Tracked state is kept.
+    pub fn set_config(&self, config: HealthConfig) {
+        match self.config.write() {
+            Ok(mut current) => *current = config,
+            Err(poisoned) => {
+                tracing::error!("RwLock poisoned in set_config, recovering");
+                *poisoned.into_inner() = config;
+            }
+        }
+        self.reevaluate();
+    }
 
-        let state = states.entry(event.rollup.clone()).or_default();
+    /// Configured and declared maintenance windows that have not yet closed
+    pub fn maintenance_windows(&self) -> Vec<MaintenanceWindow> {
+        let now = self.now();
+        let mut windows = match self.config.read() {
+            Ok(config) => config.maintenance.clone(),
+            Err(poisoned) => {
+                tracing::error!("RwLock poisoned in maintenance_windows, recovering");
+                poisoned.into_inner().maintenance.clone()
+            }
+        };
+        match self.maintenance.read() {
+            Ok(declared) => windows.extend(declared.iter().cloned()),
+            Err(poisoned) => {
+                tracing::error!("RwLock poisoned in maintenance_windows, recovering");
+                windows.extend(poisoned.into_inner().iter().cloned());
+            }
+        }
+        windows.retain(|w| w.ends_at > now);
+        windows.sort_by_key(|w| (w.starts_at, w.id));
+        windows
+    }
 
-        // Update timestamps based on event type
-        state.last_event_time = Some(now);
+    /// Webhook targets alerted on transitions
+    pub fn webhooks(&self) -> Vec<WebhookTarget> {
+        match self.config.read() {
+            Ok(config) => config.webhooks.clone(),
+            Err(poisoned) => {
+                tracing::error!("RwLock poisoned in webhooks, recovering");
+                poisoned.into_inner().webhooks.clone()
+            }
+        }
+    }
 
-        match event.event_type.as_str() {
-            "BatchDelivered" | "StateUpdate" | "BlockCommit" => {
-                state.last_batch_time = Some(now);
+    /// Alert rules in effect, including the implicit ones of webhooks no
+    /// configured rule names
+    pub fn alert_rules(&self) -> Vec<AlertRule> {
+        match self.config.read() {
+            Ok(config) => effective_rules(&config.alert_rules, &config.webhooks),
+            Err(poisoned) => {
+                tracing::error!("RwLock poisoned in alert_rules, recovering");
+                let config = poisoned.into_inner();
+                effective_rules(&config.alert_rules, &config.webhooks)
             }
-            "ProofSubmitted" | "ProofVerified" | "AssertionCreated" | "AssertionConfirmed"
-            | "DisputeGameCreated" | "BlocksVerification" | "BlockExecution" => {
-                state.last_proof_time = Some(now);
+        }
+    }
+
+    /// Maintenance windows of a rollup open at `now`
+    fn active_maintenance(&self, rollup: &str, now: u64) -> Vec<MaintenanceWindow> {
+        self.maintenance_windows()
+            .into_iter()
+            .filter(|w| w.rollup == rollup && w.is_active(now))
+            .collect()
+    }
+
+    /// Declare a maintenance window, persisting it if a store is configured,
+    /// then re-evaluate health. Returns the window with its assigned id.
+    pub fn declare_maintenance(
+        &self,
+        mut window: MaintenanceWindow,
+    ) -> eyre::Result<MaintenanceWindow> {
+        window.validate()?;
+        let mut declared = match self.maintenance.write() {
+            Ok(declared) => declared,
+            Err(poisoned) => {
+                tracing::error!("RwLock poisoned in declare_maintenance, recovering");
+                poisoned.into_inner()
             }
-            "WithdrawalProven" => {
-                // Withdrawal proofs indicate activity but are user-initiated
-                state.last_proof_time = Some(now);
+        };
+        window.id = Some(match &self.store {
+            Some(store) => store.insert_maintenance(&window)?,
+            None => declared.iter().filter_map(|w| w.id).max().unwrap_or(0) + 1,
+        });
+        declared.push(window.clone());
+        drop(declared);
+
+        tracing::info!(
+            rollup = %window.rollup,
+            stage = ?window.stage,
+            starts_at = window.starts_at,
+            ends_at = window.ends_at,
+            "Maintenance window declared"
+        );
+        self.reevaluate();
+        Ok(window)
+    }
+
+    /// Cancel a declared maintenance window, then re-evaluate health.
+    /// Returns whether a window with that id existed.
+    pub fn cancel_maintenance(&self, id: u64) -> eyre::Result<bool> {
+        let mut declared = match self.maintenance.write() {
+            Ok(declared) => declared,
+            Err(poisoned) => {
+                tracing::error!("RwLock poisoned in cancel_maintenance, recovering");
+                poisoned.into_inner()
             }
-            _ => {}
+        };
+        if let Some(store) = &self.store {
+            store.delete_maintenance(id)?;
+        }
+        let count = declared.len();
+        declared.retain(|w| w.id != Some(id));
+        let cancelled = declared.len() < count;
+        drop(declared);
+
+        if cancelled {
+            tracing::info!(id = id, "Maintenance window cancelled");
+            self.reevaluate();
         }
+        Ok(cancelled)
+    }
 
-        // Reset missed cadences on any event
-        state.missed_cadences = 0;
+    /// Get current unix timestamp from the monitor's clock
+    pub fn now(&self) -> u64 {
+        self.clock.now()
+    }
+
+    /// Record an event and update health state, categorized by its event type
+    pub fn record_event(&self, event: &RollupEvent) {
+        self.record_event_as(event, HealthCategory::for_event_type(&event.event_type));
+    }
+
+    /// Record an event counting toward the given health category
+    pub fn record_event_as(&self, event: &RollupEvent, category: HealthCategory) {
+        let now = self.now();
+
+        // Ages are measured in chain time: from the event's L1 block, capped at
+        // now for clock skew. Older backfilled events never move times back.
+        let at = event.timestamp.unwrap_or(now).min(now);
+
+        self.update(&event.rollup, |state| {
+            // Update timestamps based on event category
+            let cadence = &mut state.cadence;
+            advance(&mut state.last_event_time, &mut cadence.events, at);
+
+            let batch = (&mut state.last_batch_time, &mut cadence.batch);
+            let proof = (&mut state.last_proof_time, &mut cadence.proof);
+            let finalization = (&mut state.last_finalization_time, &mut cadence.finalization);
+            let advanced = match category {
+                HealthCategory::Batch => vec![batch],
+                HealthCategory::Proof => vec![proof],
+                HealthCategory::Finalization => vec![finalization],
+                HealthCategory::Settlement => vec![batch, proof, finalization],
+                HealthCategory::Activity => vec![],
+            };
+            for (time, stats) in advanced {
+                advance(time, stats, at);
+            }
+        });
+    }
+
+    /// Apply a change to a rollup's health state, then re-evaluate and persist it
+    fn update(&self, rollup: &str, change: impl FnOnce(&mut RollupHealthState)) {
+        let now = self.now();
+
+        let mut states = match self.health_states.write() {
+            Ok(states) => states,
+            Err(poisoned) => {
+                tracing::error!(rollup = rollup, "RwLock poisoned in update, recovering");
+                poisoned.into_inner()
+            }
+        };
+
+        let previous = states
+            .get(rollup)
+            .map(|s| s.status.clone())
+            .unwrap_or(HealthStatus::Disconnected);
+        let state = states.entry(rollup.to_string()).or_default();
+        change(state);
 
         // Re-evaluate health
-        let config = self.get_config(&event.rollup);
-        state.status = Self::evaluate_health_static(state, config);
+        let stage_changes = self.refresh(rollup, state, now);
+        self.commit(rollup, state, &previous, &stage_changes, now);
+    }
+
+    /// Re-evaluate a rollup's state against its thresholds and watcher
+    fn refresh(
+        &self,
+        rollup: &str,
+        state: &mut RollupHealthState,
+        now: u64,
+    ) -> Vec<(HealthStage, HealthStatus, HealthStatus)> {
+        state.watcher_down = self.watchers.as_ref().and_then(|w| w.down(rollup));
+        state.maintenance = self.active_maintenance(rollup, now);
+        state.refresh(&self.get_config(rollup), now)
+    }
+
+    /// Health of each pipeline stage at `now`
+    fn stage_health(
+        state: &RollupHealthState,
+        config: &RollupHealthConfig,
+        now: u64,
+    ) -> Vec<StageHealth> {
+        let l1_stage = |stage, last: Option<u64>, cadence: u64| {
+            let age = last.map(|t| now.saturating_sub(t));
+            // A window is missed once a full cadence closes without progress
+            let missed = age.map_or(0, |age| {
+                u32::try_from(age / cadence.max(1)).unwrap_or(u32::MAX)
+            });
+            let status = match age {
+                None => HealthStatus::Disconnected,
+                Some(_) => config.escalation.status(missed),
+            };
+            StageHealth {
+                stage,
+                status,
+                age_secs: age,
+                cadence_secs: Some(cadence),
+                missed_cadences: missed,
+            }
+        };
+
+        // Unmonitored sequencers are never polled, so stay Disconnected
+        let sequencer = StageHealth {
+            stage: HealthStage::Sequencer,
+            status: match (state.sequencer_producing, state.sequencer_down_since) {
+                (true, _) => HealthStatus::Healthy,
+                (false, Some(_)) => HealthStatus::Halted,
+                (false, None) => HealthStatus::Disconnected,
+            },
+            age_secs: state
+                .last_sequencer_activity
+                .or(state.sequencer_down_since)
+                .map(|t| now.saturating_sub(t)),
+            cadence_secs: None,
+            missed_cadences: 0,
+        };
+
+        vec![
+            l1_stage(
+                HealthStage::Batching,
+                state.last_batch_time,
+                config.batch_cadence_secs,
+            ),
+            l1_stage(
+                HealthStage::Proving,
+                state.last_proof_time,
+                config.proof_cadence_secs,
+            ),
+            l1_stage(
+                HealthStage::Finalization,
+                state.last_finalization_time,
+                config.finalization_cadence_secs,
+            ),
+            sequencer,
+        ]
+        .into_iter()
+        .map(|mut stage| {
+            // Stages under maintenance are expected to stall; never-seen ones stay unknown
+            if stage.status != HealthStatus::Disconnected
+                && state.maintenance.iter().any(|w| w.covers(stage.stage))
+            {
+                stage.status = HealthStatus::Maintenance;
+            }
+            stage
+        })
+        .collect()
     }
 
-    /// Evaluate health status based on current state (static version for internal use)
+    /// Evaluate health status based on current state (static version for internal use).
+    ///
+    /// The time since any event sets a baseline; each observed stage can then
+    /// degrade it, up to the stage's cap in `config.rules`.
     fn evaluate_health_static(
         state: &RollupHealthState,
         config: &RollupHealthConfig,
+        now: u64,
     ) -> HealthStatus {
-        let now = Self::now();
+        // Silence says nothing about the rollup if we stopped listening
+        if state.watcher_down.is_some() {
+            return HealthStatus::Disconnected;
+        }
+        if state.maintenance.iter().any(|w| w.stage.is_none()) {
+            return HealthStatus::Maintenance;
+        }
 
         // Check last event time
-        if let Some(last_event) = state.last_event_time {
-            let age = now.saturating_sub(last_event);
-
-            if age > config.halted_threshold_secs {
-                return HealthStatus::Halted;
-            }
-            if age > config.delayed_threshold_secs {
-                return HealthStatus::Delayed;
-            }
-        } else {
+        let Some(last_event) = state.last_event_time else {
             // No events ever received
             return HealthStatus::Disconnected;
+        };
+        let age = now.saturating_sub(last_event);
+        let stages = Self::stage_health(state, config, now);
+        // Silence is expected while every observed stage is under maintenance
+        let mut observed = stages
+            .iter()
+            .filter(|s| s.status != HealthStatus::Disconnected)
+            .peekable();
+        if observed.peek().is_some() && observed.all(|s| s.status == HealthStatus::Maintenance) {
+            return HealthStatus::Maintenance;
         }
 
-        HealthStatus::Healthy
+        let mut status = if age > config.halted_threshold_secs {
+            HealthStatus::Halted
+        } else if age > config.delayed_threshold_secs {
+            HealthStatus::Delayed
+        } else {
+            HealthStatus::Healthy
+        };
+
+        for stage in &stages {
+            let impact = config.rules.impact(stage);
+            if severity(&impact) > severity(&status) {
+                status = impact;
+            }
+        }
+        status
     }
 
     /// Record L2 sequencer activity (block production observed)
     pub fn record_sequencer_activity(&self, rollup: &str) {
-        let now = Self::now();
+        let now = self.now();
+        self.update(rollup, |state| {
+            state.last_sequencer_activity = Some(now);
+            state.sequencer_producing = true;
+            state.sequencer_down_since = None;
+        });
+    }
+
+    /// Record L2 sequencer downtime (no new blocks for given seconds)
+    pub fn record_sequencer_downtime(&self, rollup: &str, secs: u64) {
+        let since = self.now().saturating_sub(secs);
+        self.update(rollup, |state| {
+            state.sequencer_producing = false;
+            state.sequencer_down_since = Some(state.sequencer_down_since.unwrap_or(since));
+        });
+    }
+
+    /// Record a gap detected in a sequential batch stream
+    pub fn record_batch_gap(&self, gap: BatchGap) {
         let mut states = match self.health_states.write() {
             Ok(states) => states,
             Err(poisoned) => {
                 tracing::error!(
-                    rollup = rollup,
-                    "RwLock poisoned in record_sequencer_activity, recovering"
+                    rollup = %gap.rollup,
+                    "RwLock poisoned in record_batch_gap, recovering"
                 );
                 poisoned.into_inner()
             }
         };
-        let state = states.entry(rollup.to_string()).or_default();
-        state.last_sequencer_activity = Some(now);
-        state.sequencer_producing = true;
+        let state = states.entry(gap.rollup.clone()).or_default();
+        state.batch_gaps.push(gap);
+        if state.batch_gaps.len() > MAX_BATCH_GAPS {
+            state.batch_gaps.remove(0);
+        }
     }
 
-    /// Record L2 sequencer downtime (no new blocks for given seconds)
-    pub fn record_sequencer_downtime(&self, rollup: &str, _secs: u64) {
+    /// Clear a stream's gaps of the given kind
+    pub fn clear_batch_gaps(&self, rollup: &str, stream: &str, kind: GapKind) {
         let mut states = match self.health_states.write() {
             Ok(states) => states,
             Err(poisoned) => {
                 tracing::error!(
                     rollup = rollup,
-                    "RwLock poisoned in record_sequencer_downtime, recovering"
+                    "RwLock poisoned in clear_batch_gaps, recovering"
                 );
                 poisoned.into_inner()
             }
         };
-        let state = states.entry(rollup.to_string()).or_default();
-        state.sequencer_producing = false;
+        if let Some(state) = states.get_mut(rollup) {
+            state
+                .batch_gaps
+                .retain(|gap| gap.stream != stream || gap.kind != kind);
+        }
     }
 
     /// Run a health check for a specific rollup
     pub fn check_health(&self, rollup: &str) -> HealthCheckResult {
-        let now = Self::now();
+        let now = self.now();
 
         let states = match self.health_states.read() {
             Ok(states) => states,
@@ -307,100 +1136,195 @@ impl HealthMonitor {
             }
         };
 
-        let config = self.get_config(rollup);
-        let state = states.get(rollup);
+        Self::check_state(rollup, states.get(rollup), &self.get_config(rollup), now)
+    }
+
+    /// Health check result for a rollup's state at `now`
+    fn check_state(
+        rollup: &str,
+        state: Option<&RollupHealthState>,
+        config: &RollupHealthConfig,
+        now: u64,
+    ) -> HealthCheckResult {
         let mut issues = Vec::new();
 
-        let (status, last_event_age, last_batch_age, last_proof_age, seq_down, seq_down_secs) =
-            match state {
-                Some(s) => {
-                    let event_age = s.last_event_time.map(|t| now.saturating_sub(t));
-                    let batch_age = s.last_batch_time.map(|t| now.saturating_sub(t));
-                    let proof_age = s.last_proof_time.map(|t| now.saturating_sub(t));
-
-                    // Check for issues
-                    if let Some(age) = event_age {
-                        if age > config.halted_threshold_secs {
-                            issues.push(format!(
+        let default_state = RollupHealthState::default();
+        let maintenance = &state.unwrap_or(&default_state).maintenance;
+        let under_maintenance = state.is_some_and(|s| s.status == HealthStatus::Maintenance);
+        // Issues expected during maintenance are noted rather than escalated
+        let annotate = |stage: Option<HealthStage>, issue: String| {
+            let window = maintenance.iter().find(|w| match stage {
+                Some(stage) => w.covers(stage),
+                None => under_maintenance || w.stage.is_none(),
+            });
+            match window {
+                Some(window) => window.annotate(&issue),
+                None => issue,
+            }
+        };
+        let cadence = &state.unwrap_or(&default_state).cadence;
+        let thresholds = HealthThresholds::derive(config, cadence);
+        let config = &thresholds.apply(config);
+        let stages = Self::stage_health(state.unwrap_or(&default_state), config, now);
+        let stage_age = |stage: HealthStage| {
+            stages
+                .iter()
+                .find(|s| s.stage == stage)
+                .and_then(|s| s.age_secs)
+        };
+
+        let (status, last_event_age) = match state {
+            Some(s) => {
+                let event_age = s.last_event_time.map(|t| now.saturating_sub(t));
+
+                // Check for issues
+                if let Some(age) = event_age {
+                    if age > config.halted_threshold_secs {
+                        issues.push(annotate(
+                            None,
+                            format!(
                                 "No events for {} seconds (halted threshold: {})",
                                 age, config.halted_threshold_secs
-                            ));
-                        } else if age > config.delayed_threshold_secs {
-                            issues.push(format!(
+                            ),
+                        ));
+                    } else if age > config.delayed_threshold_secs {
+                        issues.push(annotate(
+                            None,
+                            format!(
                                 "No events for {} seconds (delayed threshold: {})",
                                 age, config.delayed_threshold_secs
-                            ));
-                        }
+                            ),
+                        ));
                     }
+                }
+                (s.status.clone(), event_age)
+            }
+            None => {
+                issues.push("No events received yet".to_string());
+                (HealthStatus::Disconnected, None)
+            }
+        };
 
-                    if let Some(age) = batch_age {
-                        if age > config.batch_cadence_secs {
-                            issues.push(format!(
-                                "No batch for {} seconds (expected cadence: {})",
-                                age, config.batch_cadence_secs
-                            ));
-                        }
-                    }
+        for stage in &stages {
+            let (Some(age), Some(cadence)) = (stage.age_secs, stage.cadence_secs) else {
+                continue;
+            };
+            if stage.missed_cadences > 0 {
+                let what = match stage.stage {
+                    HealthStage::Batching => "batch",
+                    HealthStage::Proving => "proof",
+                    HealthStage::Finalization => "finalization",
+                    HealthStage::Sequencer => continue,
+                };
+                issues.push(annotate(
+                    Some(stage.stage),
+                    format!(
+                        "No {} for {} seconds, {} missed cadences (expected cadence: {})",
+                        what, age, stage.missed_cadences, cadence
+                    ),
+                ));
+            }
+        }
 
-                    if let Some(age) = proof_age {
-                        if age > config.proof_cadence_secs {
-                            issues.push(format!(
-                                "No proof for {} seconds (expected cadence: {})",
-                                age, config.proof_cadence_secs
-                            ));
-                        }
-                    }
+        // Sequencer health
+        let sequencer = stages.iter().find(|s| s.stage == HealthStage::Sequencer);
+        let sequencer_down = sequencer.and_then(|s| match s.status {
+            HealthStatus::Healthy => Some(false),
+            HealthStatus::Disconnected => None,
+            HealthStatus::Maintenance => state.map(|s| !s.sequencer_producing),
+            _ => Some(true),
+        });
+        let sequencer_down_secs = sequencer_down.and(sequencer.and_then(|s| s.age_secs));
+        if sequencer_down == Some(true) {
+            issues.push(annotate(
+                Some(HealthStage::Sequencer),
+                format!(
+                    "Sequencer not producing blocks for {} seconds",
+                    sequencer_down_secs.unwrap_or_default()
+                ),
+            ));
+        }
 
-                    // Sequencer health
-                    let (seq_down, seq_secs) =
-                        if let Some(last_activity) = s.last_sequencer_activity {
-                            let age = now.saturating_sub(last_activity);
-                            if !s.sequencer_producing {
-                                issues.push(format!(
-                                    "Sequencer not producing blocks for {} seconds",
-                                    age
-                                ));
-                                (Some(true), Some(age))
-                            } else {
-                                (Some(false), Some(age))
-                            }
-                        } else {
-                            (None, None)
-                        };
-
-                    (
-                        s.status.clone(),
-                        event_age,
-                        batch_age,
-                        proof_age,
-                        seq_down,
-                        seq_secs,
-                    )
-                }
-                None => {
-                    issues.push("No events received yet".to_string());
-                    (HealthStatus::Disconnected, None, None, None, None, None)
-                }
-            };
+        let batch_gaps = state.map(|s| s.batch_gaps.clone()).unwrap_or_default();
+        issues.extend(
+            batch_gaps
+                .iter()
+                .map(|gap| annotate(Some(HealthStage::Batching), gap.describe())),
+        );
+
+        if let Some(down) = state.and_then(|s| s.watcher_down.as_ref()) {
+            issues.insert(0, down.describe(now));
+        }
 
         HealthCheckResult {
             rollup: rollup.to_string(),
             status,
             last_event_age_secs: last_event_age,
-            last_batch_age_secs: last_batch_age,
-            last_proof_age_secs: last_proof_age,
-            sequencer_down: seq_down,
-            sequencer_down_secs: seq_down_secs,
+            last_batch_age_secs: stage_age(HealthStage::Batching),
+            last_proof_age_secs: stage_age(HealthStage::Proving),
+            last_finalization_age_secs: stage_age(HealthStage::Finalization),
+            sequencer_down,
+            sequencer_down_secs,
+            missed_cadences: state.map_or(0, |s| s.missed_cadences),
+            missed_cadences_since: state.and_then(|s| s.missed_cadences_since),
+            thresholds,
+            cadence: cadence.summary(),
+            incident: state.and_then(|s| s.incident.clone()),
+            stages,
+            batch_gaps,
+            maintenance: maintenance.clone(),
             issues,
         }
     }
 
     /// Run periodic health evaluation for all rollups
     pub fn evaluate_all(&self) -> Vec<HealthCheckResult> {
-        ["arbitrum", "starknet", "base", "optimism", "zksync"]
-            .iter()
-            .map(|r| self.check_health(r))
-            .collect()
+        self.rollups.iter().map(|r| self.check_health(r)).collect()
+    }
+
+    /// Re-evaluate every rollup against the current time, logging issues and
+    /// persisting status transitions
+    pub fn reevaluate(&self) {
+        let results = self.evaluate_all();
+
+        for result in &results {
+            if !result.issues.is_empty() {
+                tracing::warn!(
+                    rollup = %result.rollup,
+                    status = ?result.status,
+                    issues = ?result.issues,
+                    "Health check issues detected"
+                );
+            }
+        }
+
+        // Update health states based on time passage
+        let now = self.now();
+        let mut states = match self.health_states.write() {
+            Ok(states) => states,
+            Err(poisoned) => {
+                tracing::error!("RwLock poisoned in reevaluate, recovering");
+                poisoned.into_inner()
+            }
+        };
+
+        for rollup in self.rollups.iter() {
+            if let Some(state) = states.get_mut(rollup) {
+                let previous = state.status.clone();
+                let previous_missed = state.missed_cadences;
+                let stage_changes = self.refresh(rollup, state, now);
+                if state.missed_cadences > previous_missed {
+                    tracing::warn!(
+                        rollup = %rollup,
+                        missed_cadences = state.missed_cadences,
+                        since = ?state.missed_cadences_since,
+                        status = ?state.status,
+                        "Missed batch/proof cadence"
+                    );
+                }
+                self.commit(rollup, state, &previous, &stage_changes, now);
+            }
+        }
     }
 
     /// Get current health status for a rollup
@@ -420,6 +1344,19 @@ impl HealthMonitor {
     }
 }
 
+/// Move a last-seen time forward to `at`, sampling the interval since the
+/// previous one. Older backfilled events never move times back.
+fn advance(last: &mut Option<u64>, stats: &mut IntervalStats, at: u64) {
+    match *last {
+        Some(previous) if at > previous => {
+            stats.observe(at - previous);
+            *last = Some(at);
+        }
+        Some(_) => {}
+        None => *last = Some(at),
+    }
+}
+
 /// Start the background health monitoring task
 pub async fn start_health_monitor(
     monitor: HealthMonitor,
@@ -442,41 +1379,14 @@ pub async fn start_health_monitor(
             }
         }
 
-        // Re-evaluate health for all rollups
-        let results = monitor.evaluate_all();
-
-        for result in &results {
-            if !result.issues.is_empty() {
-                tracing::warn!(
-                    rollup = %result.rollup,
-                    status = ?result.status,
-                    issues = ?result.issues,
-                    "Health check issues detected"
-                );
-            }
-        }
-
-        // Update health states based on time passage
-        let mut states = match monitor.health_states.write() {
-            Ok(states) => states,
-            Err(poisoned) => {
-                tracing::error!("RwLock poisoned in health monitor loop, recovering");
-                poisoned.into_inner()
-            }
-        };
-
-        for rollup in ["arbitrum", "starknet", "base", "optimism", "zksync"] {
-            let config = monitor.get_config(rollup);
-            if let Some(state) = states.get_mut(rollup) {
-                state.status = HealthMonitor::evaluate_health_static(state, config);
-            }
-        }
+        monitor.reevaluate();
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::EventStatus;
 
     #[test]
     fn test_health_monitor_new() {
@@ -495,7 +1405,9 @@ mod tests {
             block_number: 12345,
             tx_hash: "0xabc".to_string(),
             batch_number: Some("100".to_string()),
-            timestamp: Some(1234567890),
+            timestamp: Some(Clock::system().now()),
+            observed_at: None,
+            status: EventStatus::Confirmed,
         };
 
         monitor.record_event(&event);
@@ -514,7 +1426,9 @@ mod tests {
             block_number: 12345,
             tx_hash: "0xabc".to_string(),
             batch_number: Some("assertion_hash".to_string()),
-            timestamp: Some(1234567890),
+            timestamp: Some(Clock::system().now()),
+            observed_at: None,
+            status: EventStatus::Confirmed,
         };
 
         monitor.record_event(&event);
@@ -522,6 +1436,140 @@ mod tests {
         assert_eq!(monitor.get_status("arbitrum"), HealthStatus::Healthy);
     }
 
+    #[test]
+    fn test_health_category_for_event_type() {
+        assert_eq!(
+            HealthCategory::for_event_type("BatchDelivered"),
+            HealthCategory::Batch
+        );
+        assert_eq!(
+            HealthCategory::for_event_type("WithdrawalProven"),
+            HealthCategory::Activity
+        );
+        assert_eq!(
+            HealthCategory::for_event_type("AssertionConfirmed"),
+            HealthCategory::Finalization
+        );
+        assert_eq!(
+            HealthCategory::for_event_type("StateUpdate"),
+            HealthCategory::Settlement
+        );
+        assert_eq!(
+            HealthCategory::for_event_type("MessageLog"),
+            HealthCategory::Activity
+        );
+    }
+
+    #[test]
+    fn test_record_event_as_activity() {
+        let monitor = HealthMonitor::new();
+
+        let event = RollupEvent {
+            rollup: "starknet".to_string(),
+            event_type: "MessageLog".to_string(),
+            block_number: 12345,
+            tx_hash: "0xabc".to_string(),
+            batch_number: Some("1".to_string()),
+            timestamp: Some(Clock::system().now()),
+            observed_at: None,
+            status: EventStatus::Confirmed,
+        };
+
+        monitor.record_event_as(&event, HealthCategory::Activity);
+
+        let result = monitor.check_health("starknet");
+        assert!(result.last_event_age_secs.is_some());
+        assert!(result.last_batch_age_secs.is_none());
+        assert!(result.last_proof_age_secs.is_none());
+    }
+
+    #[test]
+    fn test_health_persisted_and_restored() {
+        let store = Store::open_in_memory().unwrap();
+        let monitor = HealthMonitor::new().with_store(store.clone());
+
+        let event = RollupEvent {
+            rollup: "base".to_string(),
+            event_type: "DisputeGameCreated".to_string(),
+            block_number: 12345,
+            tx_hash: "0xabc".to_string(),
+            batch_number: None,
+            timestamp: Some(Clock::system().now()),
+            observed_at: None,
+            status: EventStatus::Confirmed,
+        };
+        monitor.record_event(&event);
+        monitor.record_event(&event);
+
+        // Only the Disconnected -> Healthy change is a transition
+        let transitions = store.transitions("base", 10).unwrap();
+        assert_eq!(transitions.len(), 1);
+        assert_eq!(transitions[0].from, HealthStatus::Disconnected);
+        assert_eq!(transitions[0].to, HealthStatus::Healthy);
+
+        let restored = HealthMonitor::new().with_store(store);
+        restored.restore().unwrap();
+        assert_eq!(restored.get_status("base"), HealthStatus::Healthy);
+        assert!(restored.check_health("base").last_proof_age_secs.is_some());
+    }
+
+    #[test]
+    fn test_ages_measured_from_block_time() {
+        let monitor = HealthMonitor::new();
+        let now = Clock::system().now();
+
+        let mut event = RollupEvent {
+            rollup: "arbitrum".to_string(),
+            event_type: "BatchDelivered".to_string(),
+            block_number: 12345,
+            tx_hash: "0xabc".to_string(),
+            batch_number: Some("100".to_string()),
+            timestamp: Some(now - 700),
+            observed_at: Some(now),
+            status: EventStatus::Confirmed,
+        };
+        monitor.record_event(&event);
+
+        // Observed just now, but the batch landed on L1 past the delayed threshold
+        let result = monitor.check_health("arbitrum");
+        assert_eq!(result.status, HealthStatus::Delayed);
+        assert!(result.last_batch_age_secs.unwrap() >= 700);
+
+        // A newer event moves the time forward, an older backfilled one does not
+        event.timestamp = Some(now - 10);
+        monitor.record_event(&event);
+        event.timestamp = Some(now - 5000);
+        monitor.record_event(&event);
+        let result = monitor.check_health("arbitrum");
+        assert_eq!(result.status, HealthStatus::Healthy);
+        assert!(result.last_batch_age_secs.unwrap() < 1000);
+    }
+
+    #[test]
+    fn test_batch_gaps_reported_as_issues() {
+        let monitor = HealthMonitor::new();
+        let gap = |kind| BatchGap {
+            rollup: "zksync".to_string(),
+            stream: "block_commit".to_string(),
+            kind,
+            expected: 12,
+            found: 14,
+            from_block: 100,
+            to_block: 110,
+            detected_at: 0,
+        };
+        monitor.record_batch_gap(gap(GapKind::Skipped));
+        monitor.record_batch_gap(gap(GapKind::Repeated));
+
+        let result = monitor.check_health("zksync");
+        assert_eq!(result.batch_gaps.len(), 2);
+        assert!(result.issues.contains(&gap(GapKind::Skipped).describe()));
+
+        monitor.clear_batch_gaps("zksync", "block_commit", GapKind::Repeated);
+        let result = monitor.check_health("zksync");
+        assert_eq!(result.batch_gaps, vec![gap(GapKind::Skipped)]);
+    }
+
     #[test]
     fn test_check_health_no_events() {
         let monitor = HealthMonitor::new();
@@ -543,7 +1591,9 @@ mod tests {
             block_number: 12345,
             tx_hash: "0xabc".to_string(),
             batch_number: Some("100".to_string()),
-            timestamp: Some(1234567890),
+            timestamp: Some(Clock::system().now()),
+            observed_at: None,
+            status: EventStatus::Confirmed,
         };
 
         monitor.record_event(&event);
@@ -574,6 +1624,16 @@ mod tests {
         assert_eq!(zksync.status, HealthStatus::Disconnected);
     }
 
+    #[test]
+    fn test_evaluate_all_with_rollups() {
+        let monitor = HealthMonitor::with_rollups(vec!["scroll".to_string()]);
+
+        let results = monitor.evaluate_all();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].rollup, "scroll");
+        assert_eq!(results[0].status, HealthStatus::Disconnected);
+    }
+
     #[test]
     fn test_health_config_defaults() {
         let config = HealthConfig::default();
@@ -604,29 +1664,35 @@ mod tests {
         let state = RollupHealthState::default();
         let config = RollupHealthConfig::default();
 
-        let status = HealthMonitor::evaluate_health_static(&state, &config);
+        let status = HealthMonitor::evaluate_health_static(&state, &config, 1_700_000_000);
         assert_eq!(status, HealthStatus::Disconnected);
     }
 
     #[test]
     fn test_evaluate_health_static_healthy() {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        let now = Clock::system().now();
 
         let state = RollupHealthState {
             status: HealthStatus::Healthy,
             last_event_time: Some(now),
             last_batch_time: Some(now),
             last_proof_time: Some(now),
+            last_finalization_time: Some(now),
             missed_cadences: 0,
+            missed_cadences_since: None,
             last_sequencer_activity: None,
             sequencer_producing: false,
+            sequencer_down_since: None,
+            cadence: CadenceStats::default(),
+            incident: None,
+            stage_statuses: BTreeMap::new(),
+            batch_gaps: Vec::new(),
+            watcher_down: None,
+            maintenance: Vec::new(),
         };
         let config = RollupHealthConfig::default();
 
-        let status = HealthMonitor::evaluate_health_static(&state, &config);
+        let status = HealthMonitor::evaluate_health_static(&state, &config, now);
         assert_eq!(status, HealthStatus::Healthy);
     }
 
@@ -667,7 +1733,9 @@ mod tests {
             block_number: 12345,
             tx_hash: "0xabc".to_string(),
             batch_number: Some("100".to_string()),
-            timestamp: Some(1234567890),
+            timestamp: Some(Clock::system().now()),
+            observed_at: None,
+            status: EventStatus::Confirmed,
         };
         monitor.record_event(&event);
 
@@ -683,4 +1751,473 @@ mod tests {
             .iter()
             .any(|i| i.contains("Sequencer not producing")));
     }
+
+    fn event_at(rollup: &str, event_type: &str, timestamp: u64) -> RollupEvent {
+        RollupEvent {
+            rollup: rollup.to_string(),
+            event_type: event_type.to_string(),
+            block_number: 12345,
+            tx_hash: "0xabc".to_string(),
+            batch_number: None,
+            timestamp: Some(timestamp),
+            observed_at: None,
+            status: EventStatus::Confirmed,
+        }
+    }
+
+    fn stage(result: &HealthCheckResult, stage: HealthStage) -> &StageHealth {
+        result.stages.iter().find(|s| s.stage == stage).unwrap()
+    }
+
+    #[test]
+    fn test_stalled_prover_degrades_health_despite_batches() {
+        let clock = Clock::manual(1_700_000_000);
+        let monitor = HealthMonitor::new().with_clock(clock.clone());
+        let start = clock.now();
+
+        monitor.record_event(&event_at("arbitrum", "ProofSubmitted", start));
+        monitor.record_event(&event_at("arbitrum", "BatchDelivered", start));
+
+        // Batches keep arriving while proofs stop (proof cadence: 1 hour)
+        for minutes in (5..=320).step_by(5) {
+            clock.set(start + minutes * 60);
+            monitor.record_event(&event_at("arbitrum", "BatchDelivered", clock.now()));
+            if minutes == 125 {
+                let result = monitor.check_health("arbitrum");
+                assert_eq!(
+                    stage(&result, HealthStage::Batching).status,
+                    HealthStatus::Healthy
+                );
+                assert_eq!(
+                    stage(&result, HealthStage::Proving).status,
+                    HealthStatus::Delayed
+                );
+                assert_eq!(result.status, HealthStatus::Delayed);
+            }
+        }
+
+        let result = monitor.check_health("arbitrum");
+        assert_eq!(
+            stage(&result, HealthStage::Proving).status,
+            HealthStatus::Halted
+        );
+        assert_eq!(result.status, HealthStatus::Halted);
+        assert!(result
+            .issues
+            .iter()
+            .any(|i| i.starts_with("No proof for 19200 seconds, 5 missed cadences")));
+    }
+
+    #[test]
+    fn test_missed_cadences_counted_by_periodic_check() {
+        let clock = Clock::manual(1_700_000_000);
+        let monitor = HealthMonitor::new().with_clock(clock.clone());
+        let start = clock.now();
+        monitor.record_event(&event_at("starknet", "StateUpdate", start));
+
+        // State update cadence: 1 hour for batches, 2 hours for proofs
+        let mut seen = Vec::new();
+        for hour in 1..=6 {
+            clock.set(start + hour * 3600 + 60);
+            monitor.reevaluate();
+            let result = monitor.check_health("starknet");
+            seen.push((result.missed_cadences, result.status));
+        }
+        assert_eq!(
+            seen,
+            vec![
+                (1, HealthStatus::Healthy),
+                (2, HealthStatus::Delayed),
+                (3, HealthStatus::Delayed),
+                // No events past the 4 hour halted threshold
+                (4, HealthStatus::Halted),
+                (5, HealthStatus::Halted),
+                (6, HealthStatus::Halted),
+            ]
+        );
+
+        // The streak started when the first batch window closed
+        let result = monitor.check_health("starknet");
+        assert_eq!(result.missed_cadences_since, Some(start + 3600));
+        assert_eq!(stage(&result, HealthStage::Proving).missed_cadences, 3);
+
+        monitor.record_event(&event_at("starknet", "StateUpdate", clock.now()));
+        let result = monitor.check_health("starknet");
+        assert_eq!(result.missed_cadences, 0);
+        assert_eq!(result.missed_cadences_since, None);
+        assert_eq!(result.status, HealthStatus::Healthy);
+    }
+
+    #[test]
+    fn test_adaptive_thresholds_learned_from_events() {
+        let clock = Clock::manual(1_700_000_000);
+        let adaptive = HealthMonitor::new()
+            .with_clock(clock.clone())
+            .with_config(HealthConfig::default().with_adaptive(AdaptiveThresholds::default()));
+        let fixed = HealthMonitor::new().with_clock(clock.clone());
+
+        // Arbitrum batches every 2 minutes, well under the 5 minute cadence
+        let start = clock.now();
+        for i in 0..30 {
+            clock.set(start + i * 120);
+            for monitor in [&adaptive, &fixed] {
+                monitor.record_event(&event_at("arbitrum", "BatchDelivered", clock.now()));
+            }
+        }
+        clock.set(clock.now() + 310);
+        adaptive.reevaluate();
+        fixed.reevaluate();
+
+        let result = adaptive.check_health("arbitrum");
+        assert!(result.thresholds.adaptive);
+        assert_eq!(result.cadence.batch.samples, 29);
+        assert_eq!(result.cadence.batch.p95_secs, Some(120));
+        assert_eq!(result.thresholds.batch_cadence.learned, Some(120));
+        // Floored at half the configured cadence
+        assert_eq!(result.thresholds.batch_cadence.effective, 150);
+        assert_eq!(stage(&result, HealthStage::Batching).missed_cadences, 2);
+        assert_eq!(result.status, HealthStatus::Delayed);
+
+        let result = fixed.check_health("arbitrum");
+        assert!(!result.thresholds.adaptive);
+        assert_eq!(result.thresholds.batch_cadence.effective, 300);
+        assert_eq!(result.status, HealthStatus::Healthy);
+    }
+
+    #[test]
+    fn test_status_changes_published_to_stream() {
+        let (tx, mut rx) = broadcast::channel(16);
+        let clock = Clock::manual(1_700_000_000);
+        let monitor = HealthMonitor::new()
+            .with_clock(clock.clone())
+            .with_stream(tx);
+        let start = clock.now();
+
+        monitor.record_event(&event_at("arbitrum", "BatchDelivered", start));
+        monitor.record_event(&event_at("arbitrum", "BatchDelivered", start));
+        clock.set(start + 700);
+        monitor.reevaluate();
+        monitor.reevaluate();
+
+        let mut changes = Vec::new();
+        while let Ok(StreamMessage::Notice(StreamNotice::HealthChanged(change))) = rx.try_recv() {
+            changes.push(change);
+        }
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].previous, HealthStatus::Disconnected);
+        assert_eq!(changes[0].status, HealthStatus::Healthy);
+        assert!(changes[0].reasons.is_empty());
+        assert_eq!(changes[1].previous, HealthStatus::Healthy);
+        assert_eq!(changes[1].status, HealthStatus::Delayed);
+        assert_eq!(changes[1].at, start + 700);
+        assert!(changes[1].reasons[0].starts_with("No events for 700 seconds"));
+    }
+
+    #[test]
+    fn test_dead_watcher_reports_disconnected() {
+        use crate::liveness::ConnectionState;
+
+        let clock = Clock::manual(1_700_000_000);
+        let watchers = WatcherRegistry::new().with_clock(clock.clone());
+        watchers.register("arbitrum", "batch");
+        let monitor = HealthMonitor::new()
+            .with_clock(clock.clone())
+            .with_watchers(watchers.clone());
+        let start = clock.now();
+        monitor.record_event(&event_at("arbitrum", "BatchDelivered", start));
+
+        clock.set(start + 2000);
+        monitor.reevaluate();
+        assert_eq!(monitor.get_status("arbitrum"), HealthStatus::Halted);
+
+        watchers.set_state(ConnectionState::Dead, Some("gave up".to_string()));
+        clock.set(start + 2060);
+        monitor.reevaluate();
+        let result = monitor.check_health("arbitrum");
+        assert_eq!(result.status, HealthStatus::Disconnected);
+        assert_eq!(result.issues[0], "L1 watcher down for 60 seconds: gave up");
+        // Other rollups' watchers are not affected
+        assert!(monitor.check_health("base").issues[0].starts_with("No events"));
+
+        watchers.set_state(ConnectionState::Subscribed, None);
+        monitor.reevaluate();
+        assert_eq!(monitor.get_status("arbitrum"), HealthStatus::Halted);
+    }
+
+    #[test]
+    fn test_maintenance_suspends_escalation() {
+        let clock = Clock::manual(1_700_000_000);
+        let monitor = HealthMonitor::new().with_clock(clock.clone());
+        let start = clock.now();
+        monitor.record_event(&event_at("arbitrum", "BatchDelivered", start));
+
+        clock.set(start + 2000);
+        monitor.reevaluate();
+        assert_eq!(monitor.get_status("arbitrum"), HealthStatus::Halted);
+        assert!(monitor.check_health("arbitrum").incident.is_some());
+
+        let window = |stage| MaintenanceWindow {
+            id: None,
+            rollup: "arbitrum".to_string(),
+            stage,
+            starts_at: start + 1900,
+            ends_at: start + 5000,
+            reason: Some("ArbOS upgrade".to_string()),
+        };
+        // Proving has never been seen, so its window leaves the rollup halted
+        let proving = monitor
+            .declare_maintenance(window(Some(HealthStage::Proving)))
+            .unwrap();
+        assert_eq!(monitor.get_status("arbitrum"), HealthStatus::Halted);
+
+        let batching = monitor
+            .declare_maintenance(window(Some(HealthStage::Batching)))
+            .unwrap();
+        assert_ne!(batching.id, proving.id);
+        let result = monitor.check_health("arbitrum");
+        assert_eq!(result.status, HealthStatus::Maintenance);
+        assert_eq!(
+            stage(&result, HealthStage::Batching).status,
+            HealthStatus::Maintenance
+        );
+        assert!(result
+            .incident
+            .as_ref()
+            .is_none_or(|i| i.ended_at.is_some()));
+        assert_eq!(result.maintenance.len(), 2);
+        assert!(result.issues.iter().all(|i| i.ends_with(&format!(
+            "(maintenance until {}: ArbOS upgrade)",
+            start + 5000
+        ))));
+        assert_eq!(monitor.maintenance_windows().len(), 2);
+
+        assert!(monitor.cancel_maintenance(batching.id.unwrap()).unwrap());
+        assert!(!monitor.cancel_maintenance(batching.id.unwrap()).unwrap());
+        assert_eq!(monitor.get_status("arbitrum"), HealthStatus::Halted);
+
+        // Windows close on their own
+        monitor.declare_maintenance(window(None)).unwrap();
+        assert_eq!(monitor.get_status("arbitrum"), HealthStatus::Maintenance);
+        clock.set(start + 5000);
+        monitor.reevaluate();
+        assert_eq!(monitor.get_status("arbitrum"), HealthStatus::Halted);
+        assert!(monitor.maintenance_windows().is_empty());
+
+        assert!(monitor
+            .declare_maintenance(MaintenanceWindow {
+                ends_at: start,
+                ..window(None)
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn test_incident_recorded_across_transitions() {
+        let store = Store::open_in_memory().unwrap();
+        let clock = Clock::manual(1_700_000_000);
+        let monitor = HealthMonitor::new()
+            .with_clock(clock.clone())
+            .with_store(store.clone());
+        let start = clock.now();
+        monitor.record_event(&event_at("arbitrum", "BatchDelivered", start));
+
+        for (offset, status) in [
+            (700, HealthStatus::Delayed),
+            (1600, HealthStatus::Halted),
+            (1700, HealthStatus::Halted),
+        ] {
+            clock.set(start + offset);
+            monitor.reevaluate();
+            assert_eq!(monitor.get_status("arbitrum"), status);
+        }
+        let open = monitor.check_health("arbitrum").incident.unwrap();
+        assert_eq!(open.started_at, start + 700);
+        assert_eq!(open.peak, HealthStatus::Halted);
+        assert_eq!(open.stage, Some(HealthStage::Batching));
+        assert_eq!(store.incidents(None, None, 10).unwrap(), vec![open.clone()]);
+
+        clock.set(start + 1800);
+        monitor.record_event(&event_at("arbitrum", "BatchDelivered", clock.now()));
+        assert!(monitor.check_health("arbitrum").incident.is_none());
+
+        let incidents = store.incidents(Some("arbitrum"), None, 10).unwrap();
+        assert_eq!(incidents.len(), 1);
+        assert_eq!(incidents[0].id, open.id);
+        assert_eq!(incidents[0].ended_at, Some(start + 1800));
+        assert_eq!(incidents[0].resolution, Some(IncidentResolution::Recovered));
+        assert!(incidents[0].issues[0].starts_with("No events for 1600 seconds"));
+    }
+
+    #[test]
+    fn test_incident_kept_open_while_disconnected() {
+        use crate::liveness::ConnectionState;
+
+        let store = Store::open_in_memory().unwrap();
+        let clock = Clock::manual(1_700_000_000);
+        let watchers = WatcherRegistry::new().with_clock(clock.clone());
+        watchers.register("arbitrum", "batch");
+        let monitor = HealthMonitor::new()
+            .with_clock(clock.clone())
+            .with_store(store.clone())
+            .with_watchers(watchers.clone());
+        let start = clock.now();
+        monitor.record_event(&event_at("arbitrum", "BatchDelivered", start));
+
+        clock.set(start + 2000);
+        monitor.reevaluate();
+        let open = monitor.check_health("arbitrum").incident.unwrap();
+
+        watchers.set_state(ConnectionState::Dead, Some("gave up".to_string()));
+        clock.set(start + 2060);
+        monitor.reevaluate();
+        assert_eq!(monitor.get_status("arbitrum"), HealthStatus::Disconnected);
+        assert_eq!(
+            monitor.check_health("arbitrum").incident,
+            Some(open.clone())
+        );
+        assert!(store.incidents(None, None, 10).unwrap()[0]
+            .ended_at
+            .is_none());
+
+        watchers.set_state(ConnectionState::Subscribed, None);
+        clock.set(start + 2100);
+        monitor.record_event(&event_at("arbitrum", "BatchDelivered", clock.now()));
+        let incidents = store.incidents(None, None, 10).unwrap();
+        assert_eq!(incidents.len(), 1);
+        assert_eq!(incidents[0].id, open.id);
+        assert_eq!(incidents[0].ended_at, Some(start + 2100));
+        assert_eq!(incidents[0].resolution, Some(IncidentResolution::Recovered));
+    }
+
+    #[test]
+    fn test_incident_closed_by_maintenance() {
+        let store = Store::open_in_memory().unwrap();
+        let clock = Clock::manual(1_700_000_000);
+        let monitor = HealthMonitor::new()
+            .with_clock(clock.clone())
+            .with_store(store.clone());
+        let start = clock.now();
+        monitor.record_event(&event_at("arbitrum", "BatchDelivered", start));
+
+        clock.set(start + 2000);
+        monitor.reevaluate();
+        assert!(monitor.check_health("arbitrum").incident.is_some());
+
+        monitor
+            .declare_maintenance(MaintenanceWindow {
+                id: None,
+                rollup: "arbitrum".to_string(),
+                stage: None,
+                starts_at: start + 2000,
+                ends_at: start + 5000,
+                reason: None,
+            })
+            .unwrap();
+        assert_eq!(monitor.get_status("arbitrum"), HealthStatus::Maintenance);
+        assert!(monitor.check_health("arbitrum").incident.is_none());
+
+        let incidents = store.incidents(None, None, 10).unwrap();
+        assert_eq!(incidents[0].ended_at, Some(start + 2000));
+        assert_eq!(
+            incidents[0].resolution,
+            Some(IncidentResolution::Maintenance)
+        );
+    }
+
+    #[test]
+    fn test_escalation_policy() {
+        let policy = EscalationPolicy::default();
+        assert_eq!(policy.status(0), HealthStatus::Healthy);
+        assert_eq!(policy.status(1), HealthStatus::Healthy);
+        assert_eq!(policy.status(2), HealthStatus::Delayed);
+        assert_eq!(policy.status(4), HealthStatus::Delayed);
+        assert_eq!(policy.status(5), HealthStatus::Halted);
+
+        let mut config = HealthConfig::default();
+        config.rollups.get_mut("arbitrum").unwrap().escalation = EscalationPolicy {
+            delayed_after: 1,
+            halted_after: 2,
+        };
+        let clock = Clock::manual(1_700_000_000);
+        let monitor = HealthMonitor::new()
+            .with_clock(clock.clone())
+            .with_config(config);
+        monitor.record_event(&event_at("arbitrum", "BatchDelivered", clock.now() - 400));
+        assert_eq!(monitor.get_status("arbitrum"), HealthStatus::Delayed);
+    }
+
+    #[test]
+    fn test_stage_rules_cap_overall_status() {
+        let clock = Clock::manual(1_700_000_000);
+        let monitor = HealthMonitor::new().with_clock(clock.clone());
+        let start = clock.now();
+
+        // Finalization is capped at Delayed by default
+        monitor.record_event(&event_at("zksync", "BlockExecution", start - 20_000));
+        monitor.record_event(&event_at("zksync", "BlockCommit", start));
+        let result = monitor.check_health("zksync");
+        assert_eq!(
+            stage(&result, HealthStage::Finalization).status,
+            HealthStatus::Halted
+        );
+        assert_eq!(result.status, HealthStatus::Delayed);
+
+        // Stages never observed do not count
+        assert_eq!(
+            stage(&result, HealthStage::Proving).status,
+            HealthStatus::Disconnected
+        );
+        assert_eq!(
+            stage(&result, HealthStage::Sequencer).status,
+            HealthStatus::Disconnected
+        );
+
+        // Ignoring finalization entirely
+        let mut config = HealthConfig::default();
+        config.rollups.get_mut("zksync").unwrap().rules.finalization = HealthStatus::Healthy;
+        let monitor = HealthMonitor::new().with_clock(clock).with_config(config);
+        monitor.record_event(&event_at("zksync", "BlockExecution", start - 20_000));
+        monitor.record_event(&event_at("zksync", "BlockCommit", start));
+        assert_eq!(monitor.get_status("zksync"), HealthStatus::Healthy);
+    }
+
+    #[test]
+    fn test_settlement_refreshes_every_l1_stage() {
+        let monitor = HealthMonitor::new();
+        monitor.record_event(&event_at("starknet", "StateUpdate", monitor.now()));
+
+        let result = monitor.check_health("starknet");
+        for l1_stage in [
+            HealthStage::Batching,
+            HealthStage::Proving,
+            HealthStage::Finalization,
+        ] {
+            assert_eq!(stage(&result, l1_stage).status, HealthStatus::Healthy);
+        }
+        assert!(result.last_finalization_age_secs.is_some());
+    }
+
+    #[test]
+    fn test_sequencer_down_halts_rollup() {
+        let clock = Clock::manual(1_700_000_000);
+        let monitor = HealthMonitor::new().with_clock(clock.clone());
+        monitor.record_event(&event_at("base", "DisputeGameCreated", clock.now()));
+
+        // Down from the first poll, with no activity ever seen
+        monitor.record_sequencer_downtime("base", 120);
+        let result = monitor.check_health("base");
+        assert_eq!(result.status, HealthStatus::Halted);
+        assert_eq!(result.sequencer_down, Some(true));
+        assert_eq!(result.sequencer_down_secs, Some(120));
+
+        // Still counted from when it stopped, not from the latest poll
+        clock.set(clock.now() + 60);
+        monitor.record_sequencer_downtime("base", 150);
+        assert_eq!(monitor.check_health("base").sequencer_down_secs, Some(180));
+
+        monitor.record_sequencer_activity("base");
+        let result = monitor.check_health("base");
+        assert_eq!(result.status, HealthStatus::Healthy);
+        assert_eq!(result.sequencer_down, Some(false));
+    }
 }
@@ -0,0 +1,600 @@
+use crate::alert_rules::AlertRule;
+use crate::alerts::WebhookTarget;
+use crate::cadence::AdaptiveThresholds;
+use crate::health::{
+    EscalationPolicy, HealthConfig, HealthMonitor, HealthRules, RollupHealthConfig,
+};
+use crate::maintenance::MaintenanceWindow;
+use crate::types::HealthStatus;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// Top-level layout of the health thresholds file
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HealthFile {
+    /// Overrides of the thresholds used for rollups without built-in ones
+    #[serde(default)]
+    pub default: ThresholdOverrides,
+    /// Per-rollup overrides, keyed by rollup name
+    #[serde(default)]
+    pub rollups: HashMap<String, ThresholdOverrides>,
+    /// Planned maintenance windows
+    #[serde(default)]
+    pub maintenance: Vec<MaintenanceWindow>,
+    /// Endpoints alerted on health and sequencer transitions
+    #[serde(default)]
+    pub webhooks: Vec<WebhookTarget>,
+    /// Rules routing alerts to the webhooks
+    #[serde(default)]
+    pub alert_rules: Vec<AlertRule>,
+}
+
+/// Thresholds set in the file; anything left out keeps its built-in value
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ThresholdOverrides {
+    pub delayed_threshold_secs: Option<u64>,
+    pub halted_threshold_secs: Option<u64>,
+    pub batch_cadence_secs: Option<u64>,
+    pub proof_cadence_secs: Option<u64>,
+    pub finalization_cadence_secs: Option<u64>,
+    pub escalation: Option<EscalationPolicy>,
+    pub rules: Option<HealthRules>,
+}
+
+impl ThresholdOverrides {
+    fn apply(&self, config: &mut RollupHealthConfig) {
+        let secs = [
+            (
+                self.delayed_threshold_secs,
+                &mut config.delayed_threshold_secs,
+            ),
+            (
+                self.halted_threshold_secs,
+                &mut config.halted_threshold_secs,
+            ),
+            (self.batch_cadence_secs, &mut config.batch_cadence_secs),
+            (self.proof_cadence_secs, &mut config.proof_cadence_secs),
+            (
+                self.finalization_cadence_secs,
+                &mut config.finalization_cadence_secs,
+            ),
+        ];
+        for (value, field) in secs {
+            if let Some(value) = value {
+                *field = value;
+            }
+        }
+        if let Some(escalation) = &self.escalation {
+            config.escalation = escalation.clone();
+        }
+        if let Some(rules) = &self.rules {
+            config.rules = rules.clone();
+        }
+    }
+}
+
+/// Where health thresholds are loaded from
+#[derive(Debug, Clone)]
+pub struct HealthConfigSource {
+    /// TOML file overriding the built-in thresholds; may not exist
+    pub path: PathBuf,
+    /// Rollups the file may configure
+    pub rollups: Vec<String>,
+    /// Learn thresholds from observed cadence, bounded by the loaded ones
+    pub adaptive: bool,
+}
+
+impl HealthConfigSource {
+    /// Built-in thresholds with the file's overrides applied, if it exists
+    pub fn load(&self) -> eyre::Result<HealthConfig> {
+        self.parse(self.read()?.as_deref())
+    }
+
+    /// Contents of the file, `None` if there is none
+    fn read(&self) -> eyre::Result<Option<String>> {
+        match fs::read_to_string(&self.path) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(eyre::eyre!(
+                "Failed to read health config {}: {}",
+                self.path.display(),
+                e
+            )),
+        }
+    }
+
+    fn parse(&self, contents: Option<&str>) -> eyre::Result<HealthConfig> {
+        let mut config = match contents {
+            Some(contents) => parse_health_config(contents, &self.rollups)
+                .map_err(|e| eyre::eyre!("Invalid health config {}: {}", self.path.display(), e))?,
+            None => HealthConfig::default(),
+        };
+        if self.adaptive {
+            config = config.with_adaptive(AdaptiveThresholds::default());
+        }
+        Ok(config)
+    }
+}
+
+/// Apply a health thresholds file to the built-in thresholds, rejecting
+/// rollups outside `rollups` and inconsistent thresholds
+pub fn parse_health_config(contents: &str, rollups: &[String]) -> eyre::Result<HealthConfig> {
+    let file: HealthFile = toml::from_str(contents)?;
+    let mut config = HealthConfig::default();
+
+    file.default.apply(&mut config.default);
+    validate("default", &config.default)?;
+
+    for (name, overrides) in &file.rollups {
+        if !rollups.contains(name) {
+            eyre::bail!("Thresholds set for unknown rollup {}", name);
+        }
+        let mut rollup = config.rollups.get(name).unwrap_or(&config.default).clone();
+        overrides.apply(&mut rollup);
+        validate(name, &rollup)?;
+        config.rollups.insert(name.clone(), rollup);
+    }
+
+    for window in &file.maintenance {
+        if !rollups.contains(&window.rollup) {
+            eyre::bail!(
+                "Maintenance window set for unknown rollup {}",
+                window.rollup
+            );
+        }
+        if window.id.is_some() {
+            eyre::bail!(
+                "Maintenance window for {}: id is assigned to declared windows only",
+                window.rollup
+            );
+        }
+        window.validate()?;
+    }
+    config.maintenance = file.maintenance;
+
+    for (i, webhook) in file.webhooks.iter().enumerate() {
+        webhook.validate(rollups)?;
+        if file.webhooks[..i].iter().any(|w| w.name == webhook.name) {
+            eyre::bail!("Webhook {} defined twice", webhook.name);
+        }
+    }
+    for (i, rule) in file.alert_rules.iter().enumerate() {
+        rule.validate(rollups, &file.webhooks)?;
+        if file.alert_rules[..i].iter().any(|r| r.name == rule.name) {
+            eyre::bail!("Alert rule {} defined twice", rule.name);
+        }
+    }
+    config.webhooks = file.webhooks;
+    config.alert_rules = file.alert_rules;
+    Ok(config)
+}
+
+/// Check that a rollup's thresholds are consistent
+fn validate(name: &str, config: &RollupHealthConfig) -> eyre::Result<()> {
+    let cadences = [
+        ("delayed_threshold_secs", config.delayed_threshold_secs),
+        ("batch_cadence_secs", config.batch_cadence_secs),
+        ("proof_cadence_secs", config.proof_cadence_secs),
+        (
+            "finalization_cadence_secs",
+            config.finalization_cadence_secs,
+        ),
+    ];
+    for (field, value) in cadences {
+        if value == 0 {
+            eyre::bail!("{}: {} must be positive", name, field);
+        }
+    }
+    if config.halted_threshold_secs <= config.delayed_threshold_secs {
+        eyre::bail!(
+            "{}: halted_threshold_secs ({}) must exceed delayed_threshold_secs ({})",
+            name,
+            config.halted_threshold_secs,
+            config.delayed_threshold_secs
+        );
+    }
+
+    let escalation = &config.escalation;
+    if escalation.delayed_after == 0 || escalation.halted_after < escalation.delayed_after {
+        eyre::bail!(
+            "{}: escalation needs 0 < delayed_after ({}) <= halted_after ({})",
+            name,
+            escalation.delayed_after,
+            escalation.halted_after
+        );
+    }
+
+    let rules = &config.rules;
+    for (stage, cap) in [
+        ("batching", &rules.batching),
+        ("proving", &rules.proving),
+        ("finalization", &rules.finalization),
+        ("sequencer", &rules.sequencer),
+    ] {
+        if matches!(cap, HealthStatus::Disconnected | HealthStatus::Maintenance) {
+            eyre::bail!("{}: rules.{} cannot be {:?}", name, stage, cap);
+        }
+    }
+    Ok(())
+}
+
+/// Resolves on every SIGHUP; never where the signal does not exist
+struct Hangup {
+    #[cfg(unix)]
+    signal: Option<tokio::signal::unix::Signal>,
+}
+
+impl Hangup {
+    fn new() -> Self {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{signal, SignalKind};
+            let signal = signal(SignalKind::hangup())
+                .map_err(|e| tracing::warn!(error = ?e, "Failed to listen for SIGHUP"))
+                .ok();
+            Self { signal }
+        }
+        #[cfg(not(unix))]
+        Self {}
+    }
+
+    async fn recv(&mut self) {
+        #[cfg(unix)]
+        if let Some(signal) = &mut self.signal {
+            signal.recv().await;
+            return;
+        }
+        std::future::pending::<()>().await
+    }
+}
+
+/// Reload health thresholds when the file changes or on SIGHUP.
+///
+/// A file that fails to load is logged and the current thresholds are kept;
+/// health states and stream clients are untouched either way.
+pub async fn watch_health_config(
+    monitor: HealthMonitor,
+    source: HealthConfigSource,
+    poll_interval: Duration,
+    cancel_token: CancellationToken,
+) {
+    tracing::info!(
+        path = %source.path.display(),
+        poll_secs = poll_interval.as_secs(),
+        "Watching health config"
+    );
+
+    let mut hangup = Hangup::new();
+    let mut loaded = source.read().ok().flatten();
+    loop {
+        let forced = tokio::select! {
+            _ = tokio::time::sleep(poll_interval) => false,
+            _ = hangup.recv() => true,
+            _ = cancel_token.cancelled() => {
+                tracing::info!("Health config watcher shutting down");
+                return;
+            }
+        };
+
+        let contents = match source.read() {
+            Ok(contents) => contents,
+            Err(e) => {
+                tracing::error!(error = ?e, "Failed to read health config");
+                continue;
+            }
+        };
+        if !forced && contents == loaded {
+            continue;
+        }
+
+        match source.parse(contents.as_deref()) {
+            Ok(config) => {
+                monitor.set_config(config);
+                tracing::info!(path = %source.path.display(), "Health thresholds reloaded");
+            }
+            Err(e) => {
+                tracing::error!(error = ?e, "Keeping current health thresholds");
+            }
+        }
+        // Not retried until the file changes again
+        loaded = contents;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alerts::{AlertKind, AlertSeverity};
+    use crate::health::HealthStage;
+    use crate::notifiers::WebhookFormat;
+    use crate::types::{EventStatus, RollupEvent};
+
+    fn rollups() -> Vec<String> {
+        [
+            "arbitrum", "starknet", "base", "optimism", "zksync", "scroll",
+        ]
+        .iter()
+        .map(|r| r.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn test_overrides_applied_to_built_in_thresholds() {
+        let config = parse_health_config(
+            r#"
+            [default]
+            delayed_threshold_secs = 900
+            halted_threshold_secs = 2700
+
+            [rollups.arbitrum]
+            batch_cadence_secs = 600
+            escalation = { halted_after = 8 }
+            rules = { finalization = "Healthy" }
+
+            [rollups.scroll]
+            proof_cadence_secs = 7200
+            "#,
+            &rollups(),
+        )
+        .unwrap();
+
+        let arbitrum = &config.rollups["arbitrum"];
+        assert_eq!(arbitrum.batch_cadence_secs, 600);
+        assert_eq!(arbitrum.delayed_threshold_secs, 600);
+        assert_eq!(arbitrum.escalation.delayed_after, 2);
+        assert_eq!(arbitrum.escalation.halted_after, 8);
+        assert_eq!(arbitrum.rules.finalization, HealthStatus::Healthy);
+        assert_eq!(arbitrum.rules.batching, HealthStatus::Halted);
+
+        // Without built-in thresholds: the overridden default, then its own
+        let scroll = &config.rollups["scroll"];
+        assert_eq!(scroll.delayed_threshold_secs, 900);
+        assert_eq!(scroll.proof_cadence_secs, 7200);
+        assert_eq!(config.default.halted_threshold_secs, 2700);
+
+        assert_eq!(config.rollups["starknet"].delayed_threshold_secs, 7200);
+    }
+
+    #[test]
+    fn test_maintenance_windows_configured() {
+        let config = parse_health_config(
+            r#"
+            [[maintenance]]
+            rollup = "arbitrum"
+            stage = "batching"
+            starts_at = 1700000000
+            ends_at = 1700003600
+            reason = "ArbOS upgrade"
+
+            [[maintenance]]
+            rollup = "base"
+            starts_at = 1700000000
+            ends_at = 1700007200
+            "#,
+            &rollups(),
+        )
+        .unwrap();
+        assert_eq!(config.maintenance.len(), 2);
+        assert_eq!(config.maintenance[0].stage, Some(HealthStage::Batching));
+        assert_eq!(config.maintenance[1].stage, None);
+        assert_eq!(config.maintenance[1].id, None);
+    }
+
+    #[test]
+    fn test_webhooks_configured() {
+        let config = parse_health_config(
+            r#"
+            [[webhooks]]
+            name = "ops"
+            url = "https://hooks.example.com/rollups"
+            min_severity = "critical"
+            rollups = ["base", "optimism"]
+            headers = { Authorization = "Bearer secret" }
+
+            [[webhooks]]
+            name = "chat"
+            url = "http://127.0.0.1:9000/alerts"
+            format = "slack"
+
+            [[webhooks]]
+            name = "pager"
+            url = "https://events.pagerduty.com/v2/enqueue"
+            format = "pagerduty"
+            routing_key = "R0UT1NGKEY"
+            "#,
+            &rollups(),
+        )
+        .unwrap();
+        let ops = &config.webhooks[0];
+        assert_eq!(ops.min_severity, AlertSeverity::Critical);
+        assert!(ops.matches("base") && !ops.matches("arbitrum"));
+        assert_eq!(ops.headers["Authorization"], "Bearer secret");
+        assert_eq!(config.webhooks[1].min_severity, AlertSeverity::Warning);
+        assert!(config.webhooks[1].matches("arbitrum"));
+        assert_eq!(ops.format, WebhookFormat::Json);
+        assert_eq!(config.webhooks[1].format, WebhookFormat::Slack);
+        assert_eq!(config.webhooks[2].format, WebhookFormat::PagerDuty);
+    }
+
+    #[test]
+    fn test_alert_rules_configured() {
+        let config = parse_health_config(
+            r#"
+            [[webhooks]]
+            name = "chat"
+            url = "http://127.0.0.1:9000/alerts"
+
+            [[webhooks]]
+            name = "pager"
+            url = "http://127.0.0.1:9000/pages"
+
+            [[alert_rules]]
+            name = "base-sequencer"
+            rollups = ["base"]
+            kinds = ["sequencer"]
+            notify = ["pager"]
+
+            [[alert_rules]]
+            name = "starknet-proofs"
+            rollups = ["starknet"]
+            stages = ["proving"]
+            notify = ["chat"]
+            renotify_secs = 1800
+            escalate_after_secs = 3600
+            escalate_to = ["pager"]
+            flap_window_secs = 600
+            "#,
+            &rollups(),
+        )
+        .unwrap();
+        let rules = &config.alert_rules;
+        assert_eq!(rules[0].kinds, vec![AlertKind::Sequencer]);
+        assert_eq!(rules[0].min_severity, AlertSeverity::Warning);
+        assert_eq!(rules[1].stages, vec![HealthStage::Proving]);
+        assert_eq!(rules[1].renotify_secs, Some(1800));
+        assert_eq!(rules[1].escalate_to, vec!["pager".to_string()]);
+        assert_eq!(rules[1].flap_window_secs, 600);
+    }
+
+    #[test]
+    fn test_invalid_config_rejected() {
+        let cases = [
+            (
+                "[rollups.polygon]\ndelayed_threshold_secs = 60",
+                "unknown rollup polygon",
+            ),
+            (
+                "[rollups.base]\nhalted_threshold_secs = 3600",
+                "must exceed",
+            ),
+            (
+                "[default]\nbatch_cadence_secs = 0",
+                "batch_cadence_secs must be positive",
+            ),
+            (
+                "[rollups.zksync.escalation]\ndelayed_after = 6",
+                "delayed_after (6)",
+            ),
+            (
+                "[rollups.base.rules]\nproving = \"Disconnected\"",
+                "rules.proving",
+            ),
+            ("[rollups.base]\ndelay = 60", "unknown field"),
+            (
+                "[[maintenance]]\nrollup = \"polygon\"\nstarts_at = 1\nends_at = 2",
+                "unknown rollup polygon",
+            ),
+            (
+                "[[maintenance]]\nrollup = \"base\"\nstarts_at = 2\nends_at = 1",
+                "before it starts",
+            ),
+            (
+                "[rollups.base.rules]\nbatching = \"Maintenance\"",
+                "rules.batching",
+            ),
+            (
+                "[[webhooks]]\nname = \"a\"\nurl = \"http://x\"\n[[webhooks]]\nname = \"a\"\nurl = \"http://y\"",
+                "defined twice",
+            ),
+            (
+                "[[webhooks]]\nname = \"a\"\nurl = \"http://x\"\nrollups = [\"polygon\"]",
+                "unknown rollup polygon",
+            ),
+            (
+                "[[webhooks]]\nname = \"a\"\nurl = \"http://x\"\nformat = \"pagerduty\"",
+                "needs a routing_key",
+            ),
+            (
+                "[[webhooks]]\nname = \"a\"\nurl = \"http://x\"\n[[alert_rules]]\nname = \"r\"\nnotify = [\"b\"]",
+                "unknown webhook b",
+            ),
+        ];
+        for (contents, expected) in cases {
+            let err = parse_health_config(contents, &rollups()).unwrap_err();
+            assert!(err.to_string().contains(expected), "{}: {}", contents, err);
+        }
+    }
+
+    #[test]
+    fn test_bundled_health_config() {
+        let source = HealthConfigSource {
+            path: PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("health.toml"),
+            rollups: rollups(),
+            adaptive: true,
+        };
+        let config = source.load().unwrap();
+        assert!(config.rollups["base"].adaptive.is_some());
+
+        let missing = HealthConfigSource {
+            path: PathBuf::from("no-such-health.toml"),
+            ..source
+        };
+        assert_eq!(
+            missing.load().unwrap().rollups["base"].delayed_threshold_secs,
+            3600
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reload_on_change_keeps_state() {
+        let path = std::env::temp_dir().join(format!("health-{}.toml", std::process::id()));
+        fs::write(&path, "").unwrap();
+        let source = HealthConfigSource {
+            path: path.clone(),
+            rollups: rollups(),
+            adaptive: false,
+        };
+        let monitor = HealthMonitor::new().with_config(source.load().unwrap());
+        let now = monitor.now();
+        monitor.record_event(&RollupEvent {
+            rollup: "base".to_string(),
+            event_type: "DisputeGameCreated".to_string(),
+            block_number: 1,
+            tx_hash: "0xabc".to_string(),
+            batch_number: None,
+            timestamp: Some(now - 1200),
+            observed_at: None,
+            status: EventStatus::Confirmed,
+        });
+        assert_eq!(monitor.get_status("base"), HealthStatus::Healthy);
+
+        let cancel = CancellationToken::new();
+        let watcher = tokio::spawn(watch_health_config(
+            monitor.clone(),
+            source,
+            Duration::from_millis(10),
+            cancel.clone(),
+        ));
+
+        // Invalid: ignored
+        fs::write(&path, "[rollups.base]\nhalted_threshold_secs = 1").unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(monitor.get_config("base").halted_threshold_secs, 7200);
+
+        fs::write(
+            &path,
+            "[rollups.base]\ndelayed_threshold_secs = 300\nhalted_threshold_secs = 900",
+        )
+        .unwrap();
+        for _ in 0..100 {
+            if monitor.get_config("base").halted_threshold_secs == 900 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(monitor.get_config("base").delayed_threshold_secs, 300);
+        assert_eq!(monitor.get_status("base"), HealthStatus::Halted);
+        assert!(monitor.check_health("base").last_event_age_secs.is_some());
+
+        cancel.cancel();
+        watcher.await.unwrap();
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,149 @@
+use crate::health::{HealthCheckResult, HealthRules, HealthStage};
+use crate::types::HealthStatus;
+use serde::{Deserialize, Serialize};
+
+/// A period during which a rollup was not healthy.
+///
+/// Opens when the rollup becomes `Delayed` or `Halted`, tracks the worst
+/// status reached, and stays open while its watcher is `Disconnected`. It
+/// closes when the rollup is `Healthy` again or a maintenance window begins,
+/// as recorded in `resolution`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Incident {
+    /// Row id, once persisted
+    pub id: Option<u64>,
+    pub rollup: String,
+    /// Unix timestamp the rollup left `Healthy`
+    pub started_at: u64,
+    /// Unix timestamp it recovered, while resolved
+    pub ended_at: Option<u64>,
+    /// Stage that degraded the rollup most at the peak, `None` if only the
+    /// time since any event did
+    pub stage: Option<HealthStage>,
+    /// Worst status reached
+    pub peak: HealthStatus,
+    /// Health issues at the peak
+    pub issues: Vec<String>,
+    /// How it closed, while resolved
+    pub resolution: Option<IncidentResolution>,
+}
+
+/// How an incident closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IncidentResolution {
+    /// The rollup became `Healthy` again
+    Recovered,
+    /// A maintenance window began before it recovered
+    Maintenance,
+}
+
+impl Incident {
+    /// Whether a status counts as an ongoing incident
+    pub fn is_incident(status: &HealthStatus) -> bool {
+        matches!(status, HealthStatus::Delayed | HealthStatus::Halted)
+    }
+
+    /// Open an incident from the health check that left `Healthy`
+    pub fn open(result: &HealthCheckResult, rules: &HealthRules, now: u64) -> Self {
+        Self {
+            id: None,
+            rollup: result.rollup.clone(),
+            started_at: now,
+            ended_at: None,
+            stage: worst_stage(result, rules),
+            peak: result.status.clone(),
+            issues: result.issues.clone(),
+            resolution: None,
+        }
+    }
+
+    /// Close the incident at `now`
+    pub fn resolve(&mut self, resolution: IncidentResolution, now: u64) {
+        self.ended_at = Some(now);
+        self.resolution = Some(resolution);
+    }
+
+    /// Raise the peak if the rollup got worse; returns whether it did
+    pub fn escalate(&mut self, result: &HealthCheckResult, rules: &HealthRules) -> bool {
+        if self.peak != HealthStatus::Delayed || result.status != HealthStatus::Halted {
+            return false;
+        }
+        self.peak = result.status.clone();
+        self.stage = worst_stage(result, rules);
+        self.issues = result.issues.clone();
+        true
+    }
+
+    /// Seconds the incident lasted, or has lasted so far
+    pub fn duration_secs(&self, now: u64) -> u64 {
+        self.ended_at.unwrap_or(now).saturating_sub(self.started_at)
+    }
+}
+
+/// Stage contributing the worst status, earliest in the pipeline on ties
+fn worst_stage(result: &HealthCheckResult, rules: &HealthRules) -> Option<HealthStage> {
+    let mut worst: Option<(HealthStage, u8)> = None;
+    for stage in &result.stages {
+        let rank = match rules.impact(stage) {
+            HealthStatus::Halted => 2,
+            HealthStatus::Delayed => 1,
+            _ => continue,
+        };
+        if worst.is_none_or(|(_, worst_rank)| rank > worst_rank) {
+            worst = Some((stage.stage, rank));
+        }
+    }
+    worst.map(|(stage, _)| stage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::Clock;
+    use crate::health::HealthMonitor;
+    use crate::types::{EventStatus, RollupEvent};
+
+    fn event(event_type: &str, timestamp: u64) -> RollupEvent {
+        RollupEvent {
+            rollup: "arbitrum".to_string(),
+            event_type: event_type.to_string(),
+            block_number: 1,
+            tx_hash: "0xabc".to_string(),
+            batch_number: None,
+            timestamp: Some(timestamp),
+            observed_at: None,
+            status: EventStatus::Confirmed,
+        }
+    }
+
+    #[test]
+    fn test_open_and_escalate() {
+        let clock = Clock::manual(1_700_000_000);
+        let monitor = HealthMonitor::new().with_clock(clock.clone());
+        let start = clock.now();
+        let rules = HealthRules::default();
+
+        monitor.record_event(&event("ProofSubmitted", start - 3 * 3600));
+        monitor.record_event(&event("BatchDelivered", start));
+        let result = monitor.check_health("arbitrum");
+        assert_eq!(result.status, HealthStatus::Delayed);
+
+        let mut incident = Incident::open(&result, &rules, start);
+        assert_eq!(incident.stage, Some(HealthStage::Proving));
+        assert_eq!(incident.peak, HealthStatus::Delayed);
+        assert!(incident.issues[0].starts_with("No proof for 10800 seconds"));
+
+        // Not worse: unchanged
+        assert!(!incident.escalate(&result, &rules));
+
+        clock.set(start + 1500);
+        monitor.reevaluate();
+        let result = monitor.check_health("arbitrum");
+        assert_eq!(result.status, HealthStatus::Halted);
+        assert!(incident.escalate(&result, &rules));
+        assert_eq!(incident.peak, HealthStatus::Halted);
+        assert_eq!(incident.stage, Some(HealthStage::Batching));
+        assert_eq!(incident.duration_secs(start + 1800), 1800);
+    }
+}
@@ -0,0 +1,810 @@
+use crate::backfill::{backfill, fill_gaps, process_log, recheck_pending, stream_key, LogTracker};
+use crate::capture::CaptureRecord;
+use crate::config::{L1Config, L1Mode, ReconnectConfig};
+use crate::endpoints::{redact_error, redact_url, EndpointKind, EndpointPool};
+use crate::health::HealthMonitor;
+use crate::liveness::{ConnectionState, WatcherRegistry};
+use crate::reconnect::{connect_with_retry, ReconnectResult};
+use crate::registry::RollupRegistry;
+use crate::types::{AppState, EventStatus, RollupEvent};
+use crate::watcher::{handle_log, EventStream, RollupWatcher};
+use ethers::prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio_stream::StreamExt;
+use tokio_util::sync::CancellationToken;
+
+/// Routes logs from the shared L1 subscription to the rollup stream they belong to
+pub struct LogRouter {
+    /// Rollup and stream keyed by (contract address, event topic0)
+    routes: HashMap<(Address, H256), (Arc<dyn RollupWatcher>, EventStream)>,
+}
+
+impl LogRouter {
+    /// Build routes for every rollup in the registry.
+    ///
+    /// Rollups whose contracts cannot be resolved are skipped (and logged) so a
+    /// single misconfigured rollup does not stop the others from being watched.
+    /// Likewise a stream on a contract and topic already routed is skipped, as
+    /// each log can only be routed to one stream.
+    pub fn from_registry(registry: &RollupRegistry) -> Self {
+        let mut routes: HashMap<_, (Arc<dyn RollupWatcher>, EventStream)> = HashMap::new();
+
+        for rollup in registry.iter() {
+            let contracts = match rollup.contracts() {
+                Ok(contracts) => contracts,
+                Err(e) => {
+                    tracing::error!(rollup = rollup.name(), error = ?e, "Watcher failed to start");
+                    continue;
+                }
+            };
+
+            tracing::info!(
+                rollup = rollup.name(),
+                contracts = ?contracts,
+                "Contract addresses loaded"
+            );
+
+            for stream in rollup.streams() {
+                let Some(contract) = contracts.iter().find(|c| c.name == stream.contract) else {
+                    tracing::error!(
+                        rollup = rollup.name(),
+                        stream = %stream.name,
+                        contract = %stream.contract,
+                        "Stream references unknown contract, skipping"
+                    );
+                    continue;
+                };
+                if let Some((other, routed)) = routes.get(&(contract.address, stream.topic)) {
+                    tracing::error!(
+                        rollup = rollup.name(),
+                        stream = %stream.name,
+                        routed = %format!("{}/{}", other.name(), routed.name),
+                        "Stream shares its contract and topic with a routed stream, skipping"
+                    );
+                    continue;
+                }
+                routes.insert((contract.address, stream.topic), (rollup.clone(), stream));
+            }
+        }
+
+        Self { routes }
+    }
+
+    /// Number of routed (rollup, stream) pairs
+    pub fn len(&self) -> usize {
+        self.routes.len()
+    }
+
+    /// Whether no streams are routed
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    /// Cursor keys of every routed stream
+    pub fn stream_keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self
+            .routes
+            .values()
+            .map(|(rollup, stream)| stream_key(rollup.name(), &stream.name))
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    /// Rollup and stream name of every routed stream
+    pub fn streams(&self) -> Vec<(String, String)> {
+        let mut streams: Vec<(String, String)> = self
+            .routes
+            .values()
+            .map(|(rollup, stream)| (rollup.name().to_string(), stream.name.clone()))
+            .collect();
+        streams.sort();
+        streams
+    }
+
+    /// Cursor key and confirmation depth of every routed stream
+    pub fn stream_confirmations(&self) -> Vec<(String, u64)> {
+        let mut streams: Vec<(String, u64)> = self
+            .routes
+            .values()
+            .map(|(rollup, stream)| {
+                (
+                    stream_key(rollup.name(), &stream.name),
+                    rollup.confirmations(),
+                )
+            })
+            .collect();
+        streams.sort();
+        streams
+    }
+
+    /// Combined address/topic filter covering every routed stream
+    pub fn filter(&self) -> Filter {
+        let mut addresses: Vec<Address> = self.routes.keys().map(|(a, _)| *a).collect();
+        let mut topics: Vec<H256> = self.routes.keys().map(|(_, t)| *t).collect();
+        addresses.sort();
+        addresses.dedup();
+        topics.sort();
+        topics.dedup();
+
+        Filter::new().address(addresses).topic0(topics)
+    }
+
+    /// Filter matching only the stream with the given cursor key
+    pub fn stream_filter(&self, key: &str) -> Option<Filter> {
+        self.routes
+            .iter()
+            .find(|(_, (rollup, stream))| stream_key(rollup.name(), &stream.name) == key)
+            .map(|((address, topic), _)| Filter::new().address(*address).topic0(*topic))
+    }
+
+    /// Find the rollup stream a log belongs to
+    pub fn route(&self, log: &Log) -> Option<(&Arc<dyn RollupWatcher>, &EventStream)> {
+        let topic = log.topics.first()?;
+        self.routes
+            .get(&(log.address, *topic))
+            .map(|(rollup, stream)| (rollup, stream))
+    }
+
+    /// Apply a log to the rollup stream it belongs to, ignoring unrouted logs.
+    /// `block_timestamp` is the L1 time of the log's block, if known.
+    ///
+    /// Returns the applied event, or `None` if the log was unrouted or failed
+    /// to decode.
+    pub fn dispatch(
+        &self,
+        log: &Log,
+        block_timestamp: Option<u64>,
+        status: EventStatus,
+        state: &AppState,
+        health: &HealthMonitor,
+    ) -> Option<RollupEvent> {
+        // The combined filter also matches address/topic pairs no stream asked for
+        let (rollup, stream) = self.route(log)?;
+
+        match handle_log(
+            rollup.as_ref(),
+            stream,
+            log,
+            block_timestamp,
+            status,
+            state,
+            health,
+        ) {
+            Ok(event) => Some(event),
+            Err(e) => {
+                tracing::warn!(
+                    rollup = rollup.name(),
+                    stream = %stream.name,
+                    error = ?e,
+                    "Failed to decode log"
+                );
+                None
+            }
+        }
+    }
+}
+
+/// Result of ingesting over WebSocket
+enum WsOutcome {
+    /// The watcher was cancelled
+    Cancelled,
+    /// Reconnecting failed past `ReconnectConfig.max_retries`
+    Exhausted,
+}
+
+/// The shared L1 watcher and everything its ingestion modes need
+struct L1Watcher {
+    pool: EndpointPool,
+    router: Arc<LogRouter>,
+    state: AppState,
+    health: HealthMonitor,
+    watchers: WatcherRegistry,
+    l1_config: L1Config,
+    reconnect_config: ReconnectConfig,
+    cancel_token: CancellationToken,
+}
+
+/// Watch all rollup event streams over one shared L1 connection.
+///
+/// In WebSocket mode a single `eth_subscribe` log subscription with the
+/// router's combined filter feeds every rollup. The connection is served by the
+/// best-scoring endpoint in the pool; when it drops, goes stale or a healthier
+/// endpoint is available it is re-established once for everyone. Every
+/// (re)connect first backfills each stream from its cursor (or the configured
+/// lookback on a cold start) with chunked `eth_getLogs`, then hands over to the
+/// live subscription, so no events are dropped or duplicated.
+///
+/// In HTTP mode, or once WebSocket reconnects fail past `max_retries` (or no
+/// WebSocket endpoint is configured), the same cursors are advanced by polling
+/// `eth_getLogs` over HTTP every `poll_interval`.
+///
+/// Every stream's connection state and last log are reported to the state's
+/// watcher registry; if the watcher gives up, its rollups are reported
+/// `Disconnected`.
+pub async fn start_l1_watcher(
+    pool: EndpointPool,
+    router: Arc<LogRouter>,
+    state: AppState,
+    health: HealthMonitor,
+    l1_config: L1Config,
+    reconnect_config: ReconnectConfig,
+    cancel_token: CancellationToken,
+) {
+    if router.is_empty() {
+        tracing::warn!("No L1 event streams configured, L1 watcher not started");
+        return;
+    }
+
+    let watchers = state.watchers.clone();
+    for (rollup, stream) in router.streams() {
+        watchers.register(&rollup, &stream);
+    }
+    let mut tracker = LogTracker::restore(&state);
+    tracker.watchers = Some(watchers.clone());
+    let watcher = L1Watcher {
+        pool,
+        router,
+        state,
+        health,
+        watchers,
+        l1_config,
+        reconnect_config,
+        cancel_token,
+    };
+    let has_http = !watcher.l1_config.http_urls.is_empty();
+
+    if watcher.l1_config.mode == L1Mode::Ws && !watcher.l1_config.ws_urls.is_empty() {
+        match watcher.watch_ws(&mut tracker).await {
+            WsOutcome::Cancelled => return,
+            WsOutcome::Exhausted if !has_http => {
+                tracing::error!(stream = "l1", "Max retries exceeded, stopping watcher");
+                watcher.give_up("WebSocket reconnects exhausted and RPC_HTTP not set");
+                return;
+            }
+            WsOutcome::Exhausted => {
+                tracing::warn!(
+                    stream = "l1",
+                    "Max retries exceeded, falling back to HTTP polling"
+                );
+            }
+        }
+    }
+
+    if !has_http {
+        tracing::error!("RPC_HTTP not set, L1 HTTP polling not started");
+        watcher.give_up("RPC_HTTP not set");
+        return;
+    }
+    watcher.poll_http(&mut tracker).await;
+}
+
+impl L1Watcher {
+    /// Stop listening for good and report the rollups as disconnected
+    fn give_up(&self, error: &str) {
+        self.watchers
+            .set_state(ConnectionState::Dead, Some(error.to_string()));
+        self.health.reevaluate();
+    }
+
+    /// Ingest over WebSocket until cancelled or reconnects are exhausted
+    async fn watch_ws(&self, tracker: &mut LogTracker) -> WsOutcome {
+        let Self {
+            pool,
+            router,
+            state,
+            health,
+            watchers,
+            l1_config,
+            reconnect_config,
+            cancel_token,
+        } = self;
+        let live_filter = router.filter().from_block(BlockNumber::Latest);
+
+        loop {
+            if cancel_token.is_cancelled() {
+                tracing::info!(stream = "l1", "Watcher cancelled");
+                return WsOutcome::Cancelled;
+            }
+
+            // Each attempt picks the best-scoring endpoint, so failures fail over
+            let connect_result =
+                connect_with_retry("l1", "logs", reconnect_config, cancel_token, || async {
+                    watchers.set_state(ConnectionState::Connecting, None);
+                    let url = pool
+                        .best(EndpointKind::Ws)
+                        .ok_or_else(|| eyre::eyre!("No L1 WebSocket endpoints configured"))?;
+                    let started = Instant::now();
+                    let connected = Provider::<Ws>::connect(&url).await;
+                    state.metrics.record_connect("l1/logs", connected.is_ok());
+                    match connected {
+                        Ok(provider) => {
+                            pool.record_success(&url, started.elapsed());
+                            Ok((url, provider))
+                        }
+                        Err(e) => {
+                            let error = redact_error(&e.to_string());
+                            pool.record_failure(&url, &error);
+                            let e = eyre::eyre!("{}: {}", redact_url(&url), error);
+                            watchers.set_state(ConnectionState::BackingOff, Some(e.to_string()));
+                            Err(e)
+                        }
+                    }
+                })
+                .await;
+
+            let (url, provider) = match connect_result {
+                ReconnectResult::Connected(connection) => connection,
+                ReconnectResult::MaxRetriesExceeded => return WsOutcome::Exhausted,
+                ReconnectResult::Cancelled => {
+                    tracing::info!(stream = "l1", "Watcher cancelled");
+                    return WsOutcome::Cancelled;
+                }
+            };
+
+            pool.set_active(&url);
+            tracing::info!(
+                stream = "l1",
+                endpoint = %redact_url(&url),
+                "Connected to Ethereum node"
+            );
+
+            // New heads drive confirmations and carry block timestamps; both
+            // subscriptions share the connection
+            let subscriptions = tokio::try_join!(
+                provider.subscribe_logs(&live_filter),
+                provider.subscribe_blocks()
+            );
+            let (mut logs, mut heads) = match subscriptions {
+                Ok(subscriptions) => subscriptions,
+                Err(e) => {
+                    let error = redact_error(&e.to_string());
+                    pool.record_failure(&url, &error);
+                    watchers.set_state(ConnectionState::BackingOff, Some(error.clone()));
+                    tracing::warn!(stream = "l1", error = %error, "Log subscription failed, reconnecting");
+                    tokio::select! {
+                        _ = tokio::time::sleep(reconnect_config.base_backoff) => continue,
+                        _ = cancel_token.cancelled() => {
+                            tracing::info!(stream = "l1", "Watcher cancelled");
+                            return WsOutcome::Cancelled;
+                        }
+                    }
+                }
+            };
+
+            tracing::info!(stream = "l1", streams = router.len(), "Stream connected");
+
+            // Subscribe first, then backfill: logs arriving meanwhile are buffered by
+            // the subscription and de-duplicated by the cursor
+            let backfill_result = tokio::select! {
+                result = backfill(
+                    &provider,
+                    router,
+                    tracker,
+                    &l1_config.backfill,
+                    state,
+                    health,
+                ) => result,
+                _ = cancel_token.cancelled() => {
+                    tracing::info!(stream = "l1", "Watcher cancelled");
+                    return WsOutcome::Cancelled;
+                }
+            };
+            match backfill_result {
+                Ok(head) => {
+                    tracker.pending.confirm(head, state);
+                    watchers.set_state(ConnectionState::Subscribed, None);
+                    tracing::info!(
+                        stream = "l1",
+                        head = head,
+                        pending = tracker.pending.len(),
+                        "Handing over to live subscription"
+                    );
+                }
+                Err(e) => {
+                    let error = redact_error(&e.to_string());
+                    pool.record_failure(&url, &error);
+                    watchers.set_state(ConnectionState::BackingOff, Some(error.clone()));
+                    tracing::warn!(stream = "l1", error = %error, "Backfill failed, reconnecting");
+                    continue;
+                }
+            }
+
+            let mut failover_check = tokio::time::interval(l1_config.probe_interval);
+            failover_check.tick().await;
+
+            // Only logs count as activity; new heads arrive every block regardless
+            let stale = tokio::time::sleep(reconnect_config.stale_timeout);
+            tokio::pin!(stale);
+
+            loop {
+                tokio::select! {
+                    result = logs.next() => {
+                        match result {
+                            Some(log) => {
+                                stale
+                                    .as_mut()
+                                    .reset(tokio::time::Instant::now() + reconnect_config.stale_timeout);
+                                tracker.fetch_block_time(&provider, &log).await;
+                                process_log(&log, router, tracker, state, health);
+                                if !tracker.gap_backfills.is_empty() {
+                                    if let Err(e) = fill_gaps(
+                                        &provider,
+                                        router,
+                                        tracker,
+                                        &l1_config.backfill,
+                                        state,
+                                        health,
+                                    )
+                                    .await
+                                    {
+                                        tracing::warn!(stream = "l1", error = ?e, "Gap backfill failed, will retry");
+                                    }
+                                }
+                            }
+                            None => {
+                                pool.record_failure(&url, "subscription ended");
+                                tracing::warn!(stream = "l1", "Stream ended, reconnecting");
+                                break;
+                            }
+                        }
+                    }
+                    result = heads.next() => {
+                        match result.and_then(|block| block.number.map(|n| (n.as_u64(), block.timestamp))) {
+                            Some((number, timestamp)) => {
+                                state.record(|| CaptureRecord::L1Head {
+                                    block: number,
+                                    timestamp: Some(timestamp.as_u64()),
+                                });
+                                tracker.block_times.insert(number, timestamp.as_u64());
+                                // Live logs move the cursors, and quiet streams follow the
+                                // confirmed head; persist them once per block
+                                tracker.cursor.advance_confirmed(router, number);
+                                tracker.cursor.save(state, &tracker.pending);
+                                // Keeps the active endpoint's head current without probing it
+                                pool.record_head(&url, number);
+                                if tracker.pending.is_empty() {
+                                    tracker.pending.observe_head(number);
+                                } else {
+                                    tracker.pending.confirm(number, state);
+                                }
+                            }
+                            None => {
+                                pool.record_failure(&url, "head subscription ended");
+                                tracing::warn!(stream = "l1", "Head stream ended, reconnecting");
+                                break;
+                            }
+                        }
+                    }
+                    _ = failover_check.tick() => {
+                        if pool.should_failover() {
+                            tracing::warn!(
+                                stream = "l1",
+                                endpoint = %redact_url(&url),
+                                "Healthier L1 endpoint available, failing over"
+                            );
+                            break;
+                        }
+                    }
+                    _ = &mut stale => {
+                        tracing::warn!(
+                            stream = "l1",
+                            timeout_secs = reconnect_config.stale_timeout.as_secs(),
+                            "Stale filter detected, forcing reconnect"
+                        );
+                        break;
+                    }
+                    _ = cancel_token.cancelled() => {
+                        tracker.cursor.save(state, &tracker.pending);
+                        tracing::info!(stream = "l1", "Watcher cancelled");
+                        return WsOutcome::Cancelled;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Poll `eth_getLogs` over HTTP from the stream cursors until cancelled.
+    ///
+    /// Each poll uses the best-scoring HTTP endpoint, so failures fail over.
+    /// Logs removed by reorgs are not reported over HTTP, so pending events
+    /// are checked against their transaction receipts before they are
+    /// confirmed.
+    async fn poll_http(&self, tracker: &mut LogTracker) {
+        let mut interval = tokio::time::interval(self.l1_config.poll_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut current: Option<(String, Provider<Http>)> = None;
+
+        tracing::info!(
+            stream = "l1",
+            interval_secs = self.l1_config.poll_interval.as_secs(),
+            streams = self.router.len(),
+            "Polling L1 logs over HTTP"
+        );
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = self.cancel_token.cancelled() => {
+                    tracing::info!(stream = "l1", "Watcher cancelled");
+                    return;
+                }
+            }
+
+            let Some(url) = self.pool.best(EndpointKind::Http) else {
+                tracing::error!(stream = "l1", "No L1 HTTP endpoints configured");
+                self.give_up("No L1 HTTP endpoints configured");
+                return;
+            };
+            if current.as_ref().map(|(active, _)| active) != Some(&url) {
+                match Provider::<Http>::try_from(url.as_str()) {
+                    Ok(provider) => {
+                        self.pool.set_active(&url);
+                        tracing::info!(
+                            stream = "l1",
+                            endpoint = %redact_url(&url),
+                            "Polling Ethereum node"
+                        );
+                        current = Some((url, provider));
+                    }
+                    Err(e) => {
+                        let error = redact_error(&e.to_string());
+                        self.pool.record_failure(&url, &error);
+                        self.watchers
+                            .set_state(ConnectionState::BackingOff, Some(error.clone()));
+                        tracing::warn!(stream = "l1", error = %error, "Invalid L1 HTTP endpoint");
+                        continue;
+                    }
+                }
+            }
+            let Some((url, provider)) = &current else {
+                continue;
+            };
+
+            let started = Instant::now();
+            let poll = async {
+                let head = backfill(
+                    provider,
+                    &self.router,
+                    tracker,
+                    &self.l1_config.backfill,
+                    &self.state,
+                    &self.health,
+                )
+                .await?;
+                recheck_pending(
+                    provider,
+                    &self.router,
+                    tracker,
+                    head,
+                    &self.state,
+                    &self.health,
+                )
+                .await?;
+                Ok::<_, eyre::Report>(head)
+            };
+            let result = tokio::select! {
+                result = poll => result,
+                _ = self.cancel_token.cancelled() => {
+                    tracing::info!(stream = "l1", "Watcher cancelled");
+                    return;
+                }
+            };
+            match result {
+                Ok(head) => {
+                    self.pool.record_success(url, started.elapsed());
+                    self.pool.record_head(url, head);
+                    tracker.pending.confirm(head, &self.state);
+                    self.watchers.set_state(ConnectionState::Polling, None);
+                }
+                Err(e) => {
+                    let error = redact_error(&e.to_string());
+                    self.pool.record_failure(url, &error);
+                    self.watchers
+                        .set_state(ConnectionState::BackingOff, Some(error.clone()));
+                    tracing::warn!(stream = "l1", error = %error, "L1 poll failed, retrying");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::health::HealthCategory;
+    use crate::watcher::{Contract, StatusSlot};
+
+    struct TestRollup {
+        name: &'static str,
+        address: Address,
+    }
+
+    impl RollupWatcher for TestRollup {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn contracts(&self) -> eyre::Result<Vec<Contract>> {
+            Ok(vec![Contract {
+                name: "portal".to_string(),
+                address: self.address,
+            }])
+        }
+
+        fn streams(&self) -> Vec<EventStream> {
+            vec![EventStream {
+                name: "dispute_game".to_string(),
+                event_type: "DisputeGameCreated".to_string(),
+                contract: "portal".to_string(),
+                topic: H256::repeat_byte(0x01),
+                slots: vec![StatusSlot::Proof],
+                category: HealthCategory::Proof,
+                sequential: false,
+            }]
+        }
+
+        fn decode(&self, _stream: &EventStream, _log: &Log) -> eyre::Result<String> {
+            Ok("0xclaim".to_string())
+        }
+    }
+
+    struct Misconfigured;
+
+    impl RollupWatcher for Misconfigured {
+        fn name(&self) -> &str {
+            "broken"
+        }
+
+        fn contracts(&self) -> eyre::Result<Vec<Contract>> {
+            Err(eyre::eyre!("missing address"))
+        }
+
+        fn streams(&self) -> Vec<EventStream> {
+            Vec::new()
+        }
+
+        fn decode(&self, _stream: &EventStream, _log: &Log) -> eyre::Result<String> {
+            unreachable!()
+        }
+    }
+
+    fn registry() -> RollupRegistry {
+        RollupRegistry::new()
+            .with(TestRollup {
+                name: "base",
+                address: Address::repeat_byte(0xba),
+            })
+            .with(TestRollup {
+                name: "optimism",
+                address: Address::repeat_byte(0x0e),
+            })
+            .with(Misconfigured)
+    }
+
+    fn log_from(address: Address, topic: H256) -> Log {
+        Log {
+            address,
+            topics: vec![topic],
+            block_number: Some(U64::from(100)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_router_skips_misconfigured_rollups() {
+        let router = LogRouter::from_registry(&registry());
+        assert_eq!(router.len(), 2);
+    }
+
+    #[test]
+    fn test_router_skips_streams_already_routed() {
+        let registry = RollupRegistry::new()
+            .with(TestRollup {
+                name: "base",
+                address: Address::repeat_byte(0xba),
+            })
+            .with(TestRollup {
+                name: "optimism",
+                address: Address::repeat_byte(0xba),
+            });
+        let router = LogRouter::from_registry(&registry);
+        assert_eq!(router.stream_keys(), vec!["base/dispute_game"]);
+    }
+
+    #[test]
+    fn test_router_stream_keys() {
+        let router = LogRouter::from_registry(&registry());
+        assert_eq!(
+            router.stream_keys(),
+            vec!["base/dispute_game", "optimism/dispute_game"]
+        );
+    }
+
+    #[test]
+    fn test_router_stream_filter() {
+        let router = LogRouter::from_registry(&registry());
+        let filter = router.stream_filter("base/dispute_game").unwrap();
+        assert_eq!(
+            filter.address,
+            Some(ValueOrArray::Value(Address::repeat_byte(0xba)))
+        );
+        assert!(router.stream_filter("base/unknown").is_none());
+    }
+
+    #[test]
+    fn test_router_combined_filter() {
+        let router = LogRouter::from_registry(&registry());
+        let filter = router.filter();
+
+        match filter.address {
+            Some(ValueOrArray::Array(addresses)) => {
+                assert_eq!(addresses.len(), 2);
+                assert!(addresses.contains(&Address::repeat_byte(0xba)));
+                assert!(addresses.contains(&Address::repeat_byte(0x0e)));
+            }
+            other => panic!("Expected address list, got {:?}", other),
+        }
+
+        match &filter.topics[0] {
+            Some(ValueOrArray::Array(topics)) => {
+                assert_eq!(topics, &vec![Some(H256::repeat_byte(0x01))]);
+            }
+            other => panic!("Expected topic list, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_router_routes_by_address() {
+        let router = LogRouter::from_registry(&registry());
+
+        let (rollup, stream) = router
+            .route(&log_from(
+                Address::repeat_byte(0x0e),
+                H256::repeat_byte(0x01),
+            ))
+            .unwrap();
+        assert_eq!(rollup.name(), "optimism");
+        assert_eq!(stream.name, "dispute_game");
+
+        assert!(router
+            .route(&log_from(
+                Address::repeat_byte(0xff),
+                H256::repeat_byte(0x01)
+            ))
+            .is_none());
+        assert!(router
+            .route(&log_from(
+                Address::repeat_byte(0xba),
+                H256::repeat_byte(0x02)
+            ))
+            .is_none());
+        assert!(router.route(&Log::default()).is_none());
+    }
+
+    #[test]
+    fn test_router_dispatch_updates_only_matching_rollup() {
+        let router = LogRouter::from_registry(&registry());
+        let state = AppState::new();
+        let health = HealthMonitor::new();
+
+        let event = router
+            .dispatch(
+                &log_from(Address::repeat_byte(0xba), H256::repeat_byte(0x01)),
+                None,
+                EventStatus::Pending,
+                &state,
+                &health,
+            )
+            .unwrap();
+        assert_eq!(event.status, EventStatus::Pending);
+
+        assert_eq!(
+            state.get_status("base").latest_proof,
+            Some("0xclaim".to_string())
+        );
+        assert!(state.get_status("optimism").latest_proof.is_none());
+    }
+}
@@ -3,17 +3,75 @@
 //! This library provides types and utilities for monitoring rollup proof
 //! submissions and state updates on Ethereum.
 
+pub mod alert_rules;
+pub mod alerts;
+pub mod api;
+pub mod backfill;
+pub mod block_times;
+pub mod cadence;
+pub mod capture;
+pub mod clock;
 pub mod config;
+pub mod endpoints;
+pub mod gaps;
 pub mod health;
+pub mod health_config;
+pub mod incidents;
+pub mod l1;
+pub mod liveness;
+pub mod maintenance;
+pub mod metrics;
+pub mod notifiers;
 pub mod reconnect;
+pub mod registry;
+pub mod reorg;
+pub mod replay;
+pub mod rollup_config;
 pub mod sequencer;
+pub mod store;
 pub mod types;
+pub mod uptime;
+pub mod watcher;
 
 // Re-export commonly used types
+pub use alert_rules::AlertRule;
+pub use alerts::{
+    Alert, AlertCause, AlertDelivery, AlertDispatcher, AlertKind, AlertSeverity, AlertState,
+    OpenAlert, WebhookTarget,
+};
+pub use api::ApiState;
+pub use backfill::{LogCursor, LogTracker};
+pub use block_times::BlockTimes;
+pub use cadence::{AdaptiveThresholds, CadenceStats, HealthThresholds, IntervalStats};
+pub use capture::{CaptureEntry, CaptureRecord, Recorder};
+pub use clock::Clock;
 pub use config::{
-    BroadcastConfig, Config, HealthCheckConfig, ReconnectConfig, SequencerConfig, ServerConfig,
+    AlertConfig, BackfillConfig, BroadcastConfig, CaptureConfig, Config, HealthCheckConfig,
+    L1Config, L1Mode, ReconnectConfig, ReplayConfig, RollupsConfig, SequencerConfig, ServerConfig,
+};
+pub use endpoints::{EndpointKind, EndpointPool, EndpointStatus};
+pub use gaps::{BatchGap, BatchSequences, GapKind};
+pub use health::{
+    EscalationPolicy, HealthCategory, HealthCheckResult, HealthConfig, HealthMonitor, HealthRules,
+    HealthStage, RollupHealthConfig, StageHealth,
 };
-pub use health::{HealthCheckResult, HealthConfig, HealthMonitor, RollupHealthConfig};
+pub use health_config::{HealthConfigSource, HealthFile, ThresholdOverrides};
+pub use incidents::Incident;
+pub use l1::LogRouter;
+pub use liveness::{ConnectionState, StreamLiveness, WatcherDown, WatcherRegistry};
+pub use maintenance::MaintenanceWindow;
+pub use metrics::Metrics;
+pub use notifiers::WebhookFormat;
 pub use reconnect::{connect_with_retry, ReconnectResult};
-pub use sequencer::L2ChainConfig;
-pub use types::{AppState, HealthStatus, RollupEvent, RollupStatus, SequencerStatus};
+pub use registry::RollupRegistry;
+pub use reorg::PendingEvents;
+pub use replay::Replay;
+pub use rollup_config::{load_registry, ConfiguredRollup, RollupDefinition};
+pub use sequencer::{L2Block, L2ChainConfig, SequencerKind, SequencerTracker};
+pub use store::{HealthTransition, StageTransition, Store};
+pub use types::{
+    AppState, EventStatus, HealthStatus, RollupEvent, RollupStatus, SequencerStatus, StreamMessage,
+    StreamNotice,
+};
+pub use uptime::{StatusTimeline, UptimeStats, UptimeWindow};
+pub use watcher::{Contract, EventStream, RollupWatcher, StatusSlot};
@@ -0,0 +1,197 @@
+use crate::backfill::stream_key;
+use crate::clock::Clock;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+/// Connection state of an event stream's watcher
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionState {
+    /// Connecting, subscribing or backfilling
+    Connecting,
+    /// Receiving logs from a live subscription
+    Subscribed,
+    /// Polling `eth_getLogs` over HTTP
+    Polling,
+    /// Waiting to retry after a failed connection or poll
+    BackingOff,
+    /// Given up; no longer listening until restarted
+    Dead,
+}
+
+impl ConnectionState {
+    /// Whether new logs are being received
+    pub fn is_listening(&self) -> bool {
+        matches!(self, ConnectionState::Subscribed | ConnectionState::Polling)
+    }
+}
+
+/// Liveness of one rollup event stream
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StreamLiveness {
+    pub rollup: String,
+    pub stream: String,
+    pub state: ConnectionState,
+    /// Unix timestamp the stream entered its state
+    pub state_since: u64,
+    /// Unix timestamp a log for the stream was last received
+    pub last_message_at: Option<u64>,
+    /// Most recent connection error, cleared once listening again
+    pub last_error: Option<String>,
+}
+
+/// A rollup whose events are not being listened for
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatcherDown {
+    /// Unix timestamp the watcher gave up
+    pub since: u64,
+    pub error: Option<String>,
+}
+
+impl WatcherDown {
+    /// Health issue describing the outage
+    pub fn describe(&self, now: u64) -> String {
+        let age = now.saturating_sub(self.since);
+        match &self.error {
+            Some(error) => format!("L1 watcher down for {} seconds: {}", age, error),
+            None => format!("L1 watcher down for {} seconds", age),
+        }
+    }
+}
+
+/// Connection state and last-message time of every event stream, shared
+/// between the watchers reporting it and the health monitor and API
+#[derive(Debug, Clone, Default)]
+pub struct WatcherRegistry {
+    streams: Arc<RwLock<BTreeMap<String, StreamLiveness>>>,
+    clock: Clock,
+}
+
+impl WatcherRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Timestamp states and messages with the given clock
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Start tracking a stream, as `Connecting`
+    pub fn register(&self, rollup: &str, stream: &str) {
+        let now = self.clock.now();
+        self.write().insert(
+            stream_key(rollup, stream),
+            StreamLiveness {
+                rollup: rollup.to_string(),
+                stream: stream.to_string(),
+                state: ConnectionState::Connecting,
+                state_since: now,
+                last_message_at: None,
+                last_error: None,
+            },
+        );
+    }
+
+    /// Set the state of every stream, which share one L1 connection.
+    /// `error` replaces the last error; it is cleared once listening.
+    pub fn set_state(&self, state: ConnectionState, error: Option<String>) {
+        let now = self.clock.now();
+        for stream in self.write().values_mut() {
+            if stream.state != state {
+                stream.state = state;
+                stream.state_since = now;
+            }
+            if error.is_some() || state.is_listening() {
+                stream.last_error = error.clone();
+            }
+        }
+    }
+
+    /// Record a log received for a stream
+    pub fn record_message(&self, key: &str) {
+        let now = self.clock.now();
+        if let Some(stream) = self.write().get_mut(key) {
+            stream.last_message_at = Some(now);
+        }
+    }
+
+    /// Every tracked stream, ordered by rollup and stream
+    pub fn streams(&self) -> Vec<StreamLiveness> {
+        self.read().values().cloned().collect()
+    }
+
+    /// Whether any of a rollup's streams is dead
+    pub fn down(&self, rollup: &str) -> Option<WatcherDown> {
+        self.read()
+            .values()
+            .find(|s| s.rollup == rollup && s.state == ConnectionState::Dead)
+            .map(|s| WatcherDown {
+                since: s.state_since,
+                error: s.last_error.clone(),
+            })
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, BTreeMap<String, StreamLiveness>> {
+        match self.streams.read() {
+            Ok(streams) => streams,
+            Err(poisoned) => {
+                tracing::error!("RwLock poisoned in watcher registry, recovering");
+                poisoned.into_inner()
+            }
+        }
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, BTreeMap<String, StreamLiveness>> {
+        match self.streams.write() {
+            Ok(streams) => streams,
+            Err(poisoned) => {
+                tracing::error!("RwLock poisoned in watcher registry, recovering");
+                poisoned.into_inner()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_states_and_messages() {
+        let clock = Clock::manual(1_000);
+        let registry = WatcherRegistry::new().with_clock(clock.clone());
+        registry.register("arbitrum", "batches");
+        registry.register("base", "games");
+        assert!(registry.down("arbitrum").is_none());
+
+        clock.set(1_010);
+        registry.set_state(ConnectionState::BackingOff, Some("refused".to_string()));
+        registry.set_state(ConnectionState::Connecting, None);
+        let streams = registry.streams();
+        assert_eq!(streams[0].state, ConnectionState::Connecting);
+        assert_eq!(streams[0].last_error.as_deref(), Some("refused"));
+
+        clock.set(1_020);
+        registry.set_state(ConnectionState::Subscribed, None);
+        registry.record_message("arbitrum/batches");
+        let streams = registry.streams();
+        assert_eq!(streams[0].rollup, "arbitrum");
+        assert_eq!(streams[0].state_since, 1_020);
+        assert_eq!(streams[0].last_message_at, Some(1_020));
+        assert_eq!(streams[0].last_error, None);
+        assert_eq!(streams[1].last_message_at, None);
+
+        clock.set(1_030);
+        registry.set_state(ConnectionState::Dead, Some("max retries".to_string()));
+        let down = registry.down("base").unwrap();
+        assert_eq!(down.since, 1_030);
+        assert_eq!(
+            down.describe(1_090),
+            "L1 watcher down for 60 seconds: max retries"
+        );
+        assert!(registry.down("zksync").is_none());
+    }
+}
@@ -1,38 +1,45 @@
-use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
-use axum::http::{header, Method};
 use axum::serve;
-use axum::{
-    extract::State,
-    response::IntoResponse,
-    routing::{get, post},
-    Json, Router,
-};
 use dotenv::dotenv;
+use std::sync::Arc;
 use tokio::net::TcpListener;
 use tokio_util::sync::CancellationToken;
-use tower_http::cors::{Any, CorsLayer};
 
-mod arbitrum;
-mod base;
+mod alert_rules;
+mod alerts;
+mod api;
+mod backfill;
+mod block_times;
+mod cadence;
+mod capture;
+mod clock;
 mod config;
+mod endpoints;
+mod gaps;
 mod health;
-mod optimism;
+mod health_config;
+mod incidents;
+mod l1;
+mod liveness;
+mod maintenance;
+mod metrics;
+mod notifiers;
 mod reconnect;
+mod registry;
+mod reorg;
+mod replay;
+mod rollup_config;
 mod sequencer;
-mod starknet;
+mod store;
 mod types;
-mod zksync;
+mod uptime;
+mod watcher;
 
+pub use api::ApiState;
 pub use config::Config;
+pub use endpoints::EndpointPool;
 pub use health::HealthMonitor;
-pub use types::{AppState, RollupEvent, RollupStatus};
-
-/// Combined state for API handlers
-#[derive(Clone)]
-pub struct ApiState {
-    pub app: AppState,
-    pub health: HealthMonitor,
-}
+pub use registry::RollupRegistry;
+pub use types::{AppState, EventStatus, RollupEvent, RollupStatus};
 
 #[tokio::main]
 async fn main() -> eyre::Result<()> {
@@ -47,228 +54,193 @@ async fn main() -> eyre::Result<()> {
     // Create cancellation token for graceful shutdown
     let cancel_token = CancellationToken::new();
 
-    // Create shared global state
-    let app_state = AppState::with_config(config.broadcast.clone());
-
-    // Create health monitor
-    let health_monitor = HealthMonitor::new();
-
-    // Spawn the Arbitrum watcher
-    let arbitrum_state = app_state.clone();
-    let arbitrum_health = health_monitor.clone();
-    let arbitrum_reconnect = config.reconnect.clone();
-    let arbitrum_cancel = cancel_token.child_token();
-    tokio::spawn(async move {
-        if let Err(e) = arbitrum::start_arbitrum_watcher(
-            arbitrum_state,
-            arbitrum_health,
-            arbitrum_reconnect,
-            arbitrum_cancel,
-        )
-        .await
-        {
-            tracing::error!(rollup = "arbitrum", error = ?e, "Watcher failed to start");
+    // A replay runs on the recording's virtual time instead of watching live chains
+    let replay_capture = match &config.replay.path {
+        Some(path) => {
+            let entries = capture::read_capture(path)?;
+            tracing::info!(path = %path.display(), entries = entries.len(), "Replaying capture");
+            Some(entries)
         }
-    });
+        None => None,
+    };
+    let clock = match replay_capture.as_ref().and_then(|entries| entries.first()) {
+        Some(first) => clock::Clock::manual(first.at),
+        None => clock::Clock::system(),
+    };
 
-    // Spawn the Starknet watcher
-    let starknet_state = app_state.clone();
-    let starknet_health = health_monitor.clone();
-    let starknet_reconnect = config.reconnect.clone();
-    let starknet_cancel = cancel_token.child_token();
-    tokio::spawn(async move {
-        if let Err(e) = starknet::start_starknet_watcher(
-            starknet_state,
-            starknet_health,
-            starknet_reconnect,
-            starknet_cancel,
-        )
-        .await
-        {
-            tracing::error!(rollup = "starknet", error = ?e, "Watcher failed to start");
+    // Open the persistent store, if enabled; replays never touch the live database
+    let store = match (&config.store.path, &replay_capture) {
+        (_, Some(_)) => Some(store::Store::open_in_memory()?),
+        (Some(path), None) => {
+            let store = store::Store::open(path)
+                .map_err(|e| eyre::eyre!("Failed to open database {}: {}", path.display(), e))?;
+            tracing::info!(path = %path.display(), "Database opened");
+            Some(store)
         }
-    });
-
-    // Spawn the Base watcher
-    let base_state = app_state.clone();
-    let base_health = health_monitor.clone();
-    let base_reconnect = config.reconnect.clone();
-    let base_cancel = cancel_token.child_token();
-    tokio::spawn(async move {
-        if let Err(e) =
-            base::start_base_watcher(base_state, base_health, base_reconnect, base_cancel).await
-        {
-            tracing::error!(rollup = "base", error = ?e, "Watcher failed to start");
+        (None, None) => {
+            tracing::warn!("DATABASE_PATH empty, state will not survive restarts");
+            None
         }
-    });
+    };
 
-    // Spawn the Optimism watcher
-    let optimism_state = app_state.clone();
-    let optimism_health = health_monitor.clone();
-    let optimism_reconnect = config.reconnect.clone();
-    let optimism_cancel = cancel_token.child_token();
-    tokio::spawn(async move {
-        if let Err(e) = optimism::start_optimism_watcher(
-            optimism_state,
-            optimism_health,
-            optimism_reconnect,
-            optimism_cancel,
-        )
-        .await
-        {
-            tracing::error!(rollup = "optimism", error = ?e, "Watcher failed to start");
-        }
-    });
+    // Create shared global state
+    let mut app_state = AppState::with_config(config.broadcast.clone());
+    if let Some(store) = &store {
+        app_state = app_state.with_store(store.clone());
+        app_state.restore()?;
+    }
+    if let (Some(path), None) = (&config.capture.path, &replay_capture) {
+        let recorder = capture::Recorder::create(path)?;
+        app_state = app_state.with_recorder(recorder);
+        tracing::info!(path = %path.display(), "Recording watcher inputs");
+    }
 
-    // Spawn the zkSync watcher
-    let zksync_state = app_state.clone();
-    let zksync_health = health_monitor.clone();
-    let zksync_reconnect = config.reconnect.clone();
-    let zksync_cancel = cancel_token.child_token();
-    tokio::spawn(async move {
-        if let Err(e) = zksync::start_zksync_watcher(
-            zksync_state,
-            zksync_health,
-            zksync_reconnect,
-            zksync_cancel,
-        )
-        .await
-        {
-            tracing::error!(rollup = "zksync", error = ?e, "Watcher failed to start");
-        }
-    });
+    // Load rollup definitions
+    let registry = rollup_config::load_registry(&config.rollups.path)?;
+    tracing::info!(
+        path = %config.rollups.path.display(),
+        rollups = ?registry.names(),
+        "Rollup definitions loaded"
+    );
 
-    // Spawn the health monitor background task
-    let monitor_clone = health_monitor.clone();
-    let health_config = config.health.clone();
-    let health_cancel = cancel_token.child_token();
+    // Create health monitor
+    let health_source = health_config::HealthConfigSource {
+        path: config.health.config_path.clone(),
+        rollups: registry.names(),
+        adaptive: config.health.adaptive_thresholds,
+    };
+    let health_config = health_source.load()?;
+    let mut health_monitor = HealthMonitor::with_rollups(registry.names())
+        .with_clock(clock.clone())
+        .with_config(health_config)
+        .with_stream(app_state.tx.clone())
+        .with_watchers(app_state.watchers.clone());
+    if let Some(store) = &store {
+        health_monitor = health_monitor.with_store(store.clone());
+        health_monitor.restore()?;
+        tracing::info!("State restored from database");
+    }
+    let watched_monitor = health_monitor.clone();
+    let poll_interval = config.health.config_poll_interval;
+    let watch_cancel = cancel_token.child_token();
     tokio::spawn(async move {
-        health::start_health_monitor(monitor_clone, health_config, health_cancel).await;
+        health_config::watch_health_config(
+            watched_monitor,
+            health_source,
+            poll_interval,
+            watch_cancel,
+        )
+        .await;
     });
 
-    // Conditionally spawn L2 sequencer pollers
-    if let Some(rpc_url) = config.sequencer.arbitrum_l2_rpc.clone() {
-        let chain_config = sequencer::L2ChainConfig {
-            name: "arbitrum".to_string(),
-            rpc_url,
-            poll_interval: config.sequencer.arbitrum_poll_interval,
+    // L1 RPC endpoints, health-scored for failover
+    let endpoint_pool = EndpointPool::new(&config.l1.ws_urls, &config.l1.http_urls);
+    let router = Arc::new(l1::LogRouter::from_registry(&registry));
+
+    if let Some(entries) = replay_capture {
+        // Feed the capture through the watcher, state and health code instead
+        // of the live watchers, pollers and health monitor
+        let replay = replay::Replay {
+            router,
+            state: app_state.clone(),
+            health: health_monitor.clone(),
+            clock,
+            check_interval: config.health.check_interval,
             downtime_threshold: config.sequencer.downtime_threshold,
+            speed: config.replay.speed,
         };
-        let seq_state = app_state.clone();
-        let seq_health = health_monitor.clone();
-        let seq_cancel = cancel_token.child_token();
+        let replay_cancel = cancel_token.child_token();
         tokio::spawn(async move {
-            sequencer::start_sequencer_poller(chain_config, seq_state, seq_health, seq_cancel)
-                .await;
+            replay::start_replay(entries, replay, replay_cancel).await;
         });
-    }
-
-    if let Some(rpc_url) = config.sequencer.base_l2_rpc.clone() {
-        let chain_config = sequencer::L2ChainConfig {
-            name: "base".to_string(),
-            rpc_url,
-            poll_interval: config.sequencer.base_poll_interval,
-            downtime_threshold: config.sequencer.downtime_threshold,
-        };
-        let seq_state = app_state.clone();
-        let seq_health = health_monitor.clone();
-        let seq_cancel = cancel_token.child_token();
+    } else {
+        let prober_pool = endpoint_pool.clone();
+        let probe_interval = config.l1.probe_interval;
+        let prober_cancel = cancel_token.child_token();
         tokio::spawn(async move {
-            sequencer::start_sequencer_poller(chain_config, seq_state, seq_health, seq_cancel)
-                .await;
+            endpoints::start_endpoint_prober(prober_pool, probe_interval, prober_cancel).await;
         });
-    }
 
-    if let Some(rpc_url) = config.sequencer.starknet_l2_rpc.clone() {
-        let starknet_config = sequencer::StarknetChainConfig {
-            rpc_url,
-            poll_interval: config.sequencer.starknet_poll_interval,
-            downtime_threshold: config.sequencer.downtime_threshold,
-        };
-        let seq_state = app_state.clone();
-        let seq_health = health_monitor.clone();
-        let seq_cancel = cancel_token.child_token();
-        tokio::spawn(async move {
-            sequencer::start_starknet_sequencer_poller(
-                starknet_config,
-                seq_state,
-                seq_health,
-                seq_cancel,
-            )
-            .await;
-        });
-    }
+        // Spawn the shared L1 watcher feeding every rollup
+        if config.l1.ws_urls.is_empty() && config.l1.http_urls.is_empty() {
+            tracing::error!("Neither RPC_WS nor RPC_HTTP set, L1 watcher not started");
+        } else {
+            let l1_pool = endpoint_pool.clone();
+            let l1_state = app_state.clone();
+            let l1_health = health_monitor.clone();
+            let l1_config = config.l1.clone();
+            let l1_reconnect = config.reconnect.clone();
+            let l1_cancel = cancel_token.child_token();
+            tokio::spawn(async move {
+                l1::start_l1_watcher(
+                    l1_pool,
+                    router,
+                    l1_state,
+                    l1_health,
+                    l1_config,
+                    l1_reconnect,
+                    l1_cancel,
+                )
+                .await;
+            });
+        }
 
-    if let Some(rpc_url) = config.sequencer.optimism_l2_rpc.clone() {
-        let chain_config = sequencer::L2ChainConfig {
-            name: "optimism".to_string(),
-            rpc_url,
-            poll_interval: config.sequencer.optimism_poll_interval,
-            downtime_threshold: config.sequencer.downtime_threshold,
-        };
-        let seq_state = app_state.clone();
-        let seq_health = health_monitor.clone();
-        let seq_cancel = cancel_token.child_token();
+        // Spawn the health monitor background task
+        let monitor_clone = health_monitor.clone();
+        let health_config = config.health.clone();
+        let health_cancel = cancel_token.child_token();
         tokio::spawn(async move {
-            sequencer::start_sequencer_poller(chain_config, seq_state, seq_health, seq_cancel)
-                .await;
+            health::start_health_monitor(monitor_clone, health_config, health_cancel).await;
         });
-    }
 
-    if let Some(rpc_url) = config.sequencer.zksync_l2_rpc.clone() {
-        let chain_config = sequencer::L2ChainConfig {
-            name: "zksync".to_string(),
-            rpc_url,
-            poll_interval: config.sequencer.zksync_poll_interval,
-            downtime_threshold: config.sequencer.downtime_threshold,
-        };
-        let seq_state = app_state.clone();
-        let seq_health = health_monitor.clone();
-        let seq_cancel = cancel_token.child_token();
+        // Alert webhook targets on health and sequencer transitions
+        let mut dispatcher =
+            alerts::AlertDispatcher::new(health_monitor.clone(), config.alerts.clone());
+        if let Some(store) = &store {
+            dispatcher = dispatcher.with_store(store.clone());
+            dispatcher.restore()?;
+        }
+        let alerts_rx = app_state.tx.subscribe();
+        let alerts_cancel = cancel_token.child_token();
         tokio::spawn(async move {
-            sequencer::start_sequencer_poller(chain_config, seq_state, seq_health, seq_cancel)
-                .await;
+            alerts::start_alerting(dispatcher, alerts_rx, alerts_cancel).await;
         });
+
+        // Conditionally spawn L2 sequencer pollers (only for rollups with an L2 RPC set)
+        for rollup in registry.iter() {
+            let Some(rpc_url) = config.sequencer.rpc_url(rollup.name()) else {
+                continue;
+            };
+            let chain_config = sequencer::L2ChainConfig {
+                name: rollup.name().to_string(),
+                rpc_url: rpc_url.to_string(),
+                poll_interval: config
+                    .sequencer
+                    .poll_interval(rollup.name(), rollup.sequencer_poll_interval()),
+                downtime_threshold: config.sequencer.downtime_threshold,
+            };
+            let kind = rollup.sequencer_kind();
+            let seq_state = app_state.clone();
+            let seq_health = health_monitor.clone();
+            let seq_cancel = cancel_token.child_token();
+            tokio::spawn(async move {
+                sequencer::start_poller(kind, chain_config, seq_state, seq_health, seq_cancel)
+                    .await;
+            });
+        }
     }
 
     // Combined API state
     let api_state = ApiState {
         app: app_state,
         health: health_monitor,
+        registry,
+        endpoints: endpoint_pool,
+        admin_token: config.server.admin_token.clone(),
     };
 
-    // CORS configuration for cross-origin requests from frontend
-    let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
-        .allow_headers([header::CONTENT_TYPE]);
-
     // Build Axum routes
-    let app = Router::new()
-        .route("/", get(root))
-        .route("/health", get(service_health))
-        .route("/rollups", get(list_rollups))
-        .route("/rollups/arbitrum/status", get(get_arbitrum_status))
-        .route("/rollups/starknet/status", get(get_starknet_status))
-        .route("/rollups/base/status", get(get_base_status))
-        .route("/rollups/optimism/status", get(get_optimism_status))
-        .route("/rollups/zksync/status", get(get_zksync_status))
-        .route("/rollups/arbitrum/health", get(get_arbitrum_health))
-        .route("/rollups/starknet/health", get(get_starknet_health))
-        .route("/rollups/base/health", get(get_base_health))
-        .route("/rollups/optimism/health", get(get_optimism_health))
-        .route("/rollups/zksync/health", get(get_zksync_health))
-        .route("/rollups/health", get(get_all_health))
-        .route("/rollups/arbitrum/sequencer", get(get_arbitrum_sequencer))
-        .route("/rollups/starknet/sequencer", get(get_starknet_sequencer))
-        .route("/rollups/base/sequencer", get(get_base_sequencer))
-        .route("/rollups/optimism/sequencer", get(get_optimism_sequencer))
-        .route("/rollups/zksync/sequencer", get(get_zksync_sequencer))
-        .route("/rollups/sequencer", get(get_all_sequencer))
-        .route("/rollups/stream", get(ws_handler))
-        .route("/test/event", post(post_test_event))
-        .layer(cors)
-        .with_state(api_state);
+    let app = api::router(api_state);
 
     // Parse socket address
     let addr: std::net::SocketAddr = config
@@ -282,27 +254,33 @@ async fn main() -> eyre::Result<()> {
         port = config.server.port,
         "API server starting"
     );
+    if config.server.admin_token.is_none() {
+        tracing::info!(
+            "ADMIN_TOKEN unset, maintenance windows can only be declared in health.toml"
+        );
+    }
     tracing::info!("Endpoints:");
     tracing::info!("  GET  /                          - Root");
     tracing::info!("  GET  /health                    - Service health check");
     tracing::info!("  GET  /rollups                   - List supported rollups");
-    tracing::info!("  GET  /rollups/arbitrum/status   - Arbitrum status");
-    tracing::info!("  GET  /rollups/starknet/status   - Starknet status");
-    tracing::info!("  GET  /rollups/base/status       - Base status");
-    tracing::info!("  GET  /rollups/optimism/status   - Optimism status");
-    tracing::info!("  GET  /rollups/zksync/status     - zkSync status");
-    tracing::info!("  GET  /rollups/arbitrum/health   - Arbitrum health");
-    tracing::info!("  GET  /rollups/starknet/health   - Starknet health");
-    tracing::info!("  GET  /rollups/base/health       - Base health");
-    tracing::info!("  GET  /rollups/optimism/health   - Optimism health");
-    tracing::info!("  GET  /rollups/zksync/health     - zkSync health");
+    tracing::info!("  GET  /rollups/{{name}}/status     - Rollup status");
+    tracing::info!("  GET  /rollups/{{name}}/health     - Rollup health");
+    tracing::info!("  GET  /rollups/{{name}}/sequencer  - Rollup L2 sequencer");
+    tracing::info!("  GET  /rollups/{{name}}/events     - Stored rollup events");
+    tracing::info!("  GET  /rollups/{{name}}/health/history - Health transitions");
+    tracing::info!("  GET  /rollups/{{name}}/incidents  - Rollup incidents");
+    tracing::info!("  GET  /rollups/{{name}}/uptime     - Uptime, MTTR and incidents (?from=&to=)");
+    tracing::info!("  POST /rollups/{{name}}/maintenance - Declare a maintenance window (admin)");
+    tracing::info!("  GET  /incidents                 - All incidents (?since=)");
+    tracing::info!("  GET  /maintenance               - Open and upcoming maintenance windows");
+    tracing::info!("  GET  /alerts/deliveries         - Alert webhook delivery log (?rollup=)");
+    tracing::info!("  GET  /alerts/open               - Unresolved alerts per rule");
+    tracing::info!("  DELETE /maintenance/{{id}}        - Cancel a maintenance window (admin)");
     tracing::info!("  GET  /rollups/health            - All rollups health");
-    tracing::info!("  GET  /rollups/arbitrum/sequencer - Arbitrum L2 sequencer");
-    tracing::info!("  GET  /rollups/starknet/sequencer - Starknet L2 sequencer");
-    tracing::info!("  GET  /rollups/base/sequencer    - Base L2 sequencer");
-    tracing::info!("  GET  /rollups/optimism/sequencer - Optimism L2 sequencer");
-    tracing::info!("  GET  /rollups/zksync/sequencer  - zkSync L2 sequencer");
     tracing::info!("  GET  /rollups/sequencer         - All L2 sequencer statuses");
+    tracing::info!("  GET  /l1/endpoints              - L1 RPC endpoint health");
+    tracing::info!("  GET  /metrics                   - Prometheus metrics");
+    tracing::info!("  GET  /watchers                  - Event stream watcher liveness");
     tracing::info!("  WS   /rollups/stream            - Real-time event stream");
 
     let listener = TcpListener::bind(addr).await?;
@@ -332,222 +310,3 @@ async fn main() -> eyre::Result<()> {
     tracing::info!("Server shutdown complete");
     Ok(())
 }
-
-// ------------------------------------------
-// REST Endpoints
-// ------------------------------------------
-
-async fn root() -> &'static str {
-    "Rollup Proof Status API - Track L2 rollup commitments on Ethereum L1"
-}
-
-async fn service_health() -> impl IntoResponse {
-    Json(serde_json::json!({
-        "status": "ok",
-        "service": "rollup-proof-status"
-    }))
-}
-
-async fn list_rollups() -> impl IntoResponse {
-    Json(serde_json::json!({
-        "rollups": [
-            {
-                "name": "arbitrum",
-                "status_endpoint": "/rollups/arbitrum/status",
-                "health_endpoint": "/rollups/arbitrum/health",
-                "sequencer_endpoint": "/rollups/arbitrum/sequencer",
-                "events": ["BatchDelivered", "ProofSubmitted", "ProofVerified"]
-            },
-            {
-                "name": "starknet",
-                "status_endpoint": "/rollups/starknet/status",
-                "health_endpoint": "/rollups/starknet/health",
-                "sequencer_endpoint": "/rollups/starknet/sequencer",
-                "events": ["StateUpdate", "MessageLog"]
-            },
-            {
-                "name": "base",
-                "status_endpoint": "/rollups/base/status",
-                "health_endpoint": "/rollups/base/health",
-                "sequencer_endpoint": "/rollups/base/sequencer",
-                "events": ["DisputeGameCreated", "WithdrawalProven"]
-            },
-            {
-                "name": "optimism",
-                "status_endpoint": "/rollups/optimism/status",
-                "health_endpoint": "/rollups/optimism/health",
-                "sequencer_endpoint": "/rollups/optimism/sequencer",
-                "events": ["DisputeGameCreated", "WithdrawalProven"]
-            },
-            {
-                "name": "zksync",
-                "status_endpoint": "/rollups/zksync/status",
-                "health_endpoint": "/rollups/zksync/health",
-                "sequencer_endpoint": "/rollups/zksync/sequencer",
-                "events": ["BlockCommit", "BlocksVerification", "BlockExecution"]
-            }
-        ]
-    }))
-}
-
-async fn get_arbitrum_status(State(state): State<ApiState>) -> impl IntoResponse {
-    Json(state.app.get_status("arbitrum"))
-}
-
-async fn get_starknet_status(State(state): State<ApiState>) -> impl IntoResponse {
-    Json(state.app.get_status("starknet"))
-}
-
-async fn get_arbitrum_health(State(state): State<ApiState>) -> impl IntoResponse {
-    Json(state.health.check_health("arbitrum"))
-}
-
-async fn get_starknet_health(State(state): State<ApiState>) -> impl IntoResponse {
-    Json(state.health.check_health("starknet"))
-}
-
-async fn get_base_status(State(state): State<ApiState>) -> impl IntoResponse {
-    Json(state.app.get_status("base"))
-}
-
-async fn get_base_health(State(state): State<ApiState>) -> impl IntoResponse {
-    Json(state.health.check_health("base"))
-}
-
-async fn get_optimism_status(State(state): State<ApiState>) -> impl IntoResponse {
-    Json(state.app.get_status("optimism"))
-}
-
-async fn get_optimism_health(State(state): State<ApiState>) -> impl IntoResponse {
-    Json(state.health.check_health("optimism"))
-}
-
-async fn get_zksync_status(State(state): State<ApiState>) -> impl IntoResponse {
-    Json(state.app.get_status("zksync"))
-}
-
-async fn get_zksync_health(State(state): State<ApiState>) -> impl IntoResponse {
-    Json(state.health.check_health("zksync"))
-}
-
-async fn get_all_health(State(state): State<ApiState>) -> impl IntoResponse {
-    Json(serde_json::json!({
-        "rollups": state.health.evaluate_all()
-    }))
-}
-
-async fn get_arbitrum_sequencer(State(state): State<ApiState>) -> impl IntoResponse {
-    Json(state.app.get_sequencer_status("arbitrum"))
-}
-
-async fn get_base_sequencer(State(state): State<ApiState>) -> impl IntoResponse {
-    Json(state.app.get_sequencer_status("base"))
-}
-
-async fn get_starknet_sequencer(State(state): State<ApiState>) -> impl IntoResponse {
-    Json(state.app.get_sequencer_status("starknet"))
-}
-
-async fn get_optimism_sequencer(State(state): State<ApiState>) -> impl IntoResponse {
-    Json(state.app.get_sequencer_status("optimism"))
-}
-
-async fn get_zksync_sequencer(State(state): State<ApiState>) -> impl IntoResponse {
-    Json(state.app.get_sequencer_status("zksync"))
-}
-
-async fn get_all_sequencer(State(state): State<ApiState>) -> impl IntoResponse {
-    Json(serde_json::json!({
-        "sequencer": state.app.get_all_sequencer_statuses()
-    }))
-}
-
-// ------------------------------------------
-// WebSocket Endpoint
-// ------------------------------------------
-
-async fn ws_handler(ws: WebSocketUpgrade, State(state): State<ApiState>) -> impl IntoResponse {
-    ws.on_upgrade(|socket| handle_ws(socket, state))
-}
-
-async fn handle_ws(mut socket: WebSocket, state: ApiState) {
-    let mut rx = state.app.tx.subscribe();
-
-    tracing::info!("New WebSocket client connected");
-
-    // Send initial status to the client (including health and sequencer)
-    let statuses = state.app.get_all_statuses();
-    let health = state.health.evaluate_all();
-    let sequencer = state.app.get_all_sequencer_statuses();
-    let initial = serde_json::json!({
-        "type": "initial",
-        "statuses": statuses,
-        "health": health,
-        "sequencer": sequencer
-    });
-    if let Ok(json_msg) = serde_json::to_string(&initial) {
-        let _ = socket.send(Message::Text(json_msg.into())).await;
-    }
-
-    // Stream events as they arrive
-    while let Ok(event) = rx.recv().await {
-        if let Ok(json_msg) = serde_json::to_string(&event) {
-            if socket.send(Message::Text(json_msg.into())).await.is_err() {
-                break;
-            }
-        }
-    }
-
-    tracing::info!("WebSocket client disconnected");
-}
-
-// ------------------------------------------
-// Test Endpoint (for development only)
-// ------------------------------------------
-
-/// Request body for test event endpoint
-#[derive(serde::Deserialize)]
-struct TestEventRequest {
-    rollup: Option<String>,
-    event_type: Option<String>,
-    block_number: Option<u64>,
-    batch_number: Option<String>,
-    tx_hash: Option<String>,
-}
-
-/// POST /test/event - Broadcast a test event to all WebSocket clients
-async fn post_test_event(
-    State(state): State<ApiState>,
-    Json(req): Json<TestEventRequest>,
-) -> impl IntoResponse {
-    let event = RollupEvent {
-        rollup: req.rollup.unwrap_or_else(|| "arbitrum".to_string()),
-        event_type: req
-            .event_type
-            .unwrap_or_else(|| "BatchDelivered".to_string()),
-        block_number: req.block_number.unwrap_or(19_000_000),
-        tx_hash: req
-            .tx_hash
-            .unwrap_or_else(|| format!("0x{:064x}", rand::random::<u64>())),
-        batch_number: req.batch_number.or_else(|| Some("12345".to_string())),
-        timestamp: Some(
-            std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .map(|d| d.as_secs())
-                .unwrap_or(0),
-        ),
-    };
-
-    tracing::info!(
-        rollup = %event.rollup,
-        event_type = %event.event_type,
-        "Broadcasting test event"
-    );
-
-    state.app.broadcast(event.clone());
-
-    Json(serde_json::json!({
-        "status": "ok",
-        "event": event
-    }))
-}
@@ -0,0 +1,94 @@
+use crate::health::HealthStage;
+use serde::{Deserialize, Serialize};
+
+/// A planned period during which a rollup, or one of its stages, is not
+/// expected to make progress (upgrades, hard forks, paused contracts).
+///
+/// While active, the covered stages report `Maintenance` instead of
+/// escalating, their issues are annotated with the window, and no incident is
+/// opened for them. A window without a stage covers the whole rollup.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MaintenanceWindow {
+    /// Id of windows declared through the API; `None` for configured ones
+    #[serde(default)]
+    pub id: Option<u64>,
+    pub rollup: String,
+    /// Stage under maintenance, `None` for the whole rollup
+    #[serde(default)]
+    pub stage: Option<HealthStage>,
+    /// Unix timestamp the window opens
+    pub starts_at: u64,
+    /// Unix timestamp the window closes
+    pub ends_at: u64,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+impl MaintenanceWindow {
+    /// Whether the window is open at `now`
+    pub fn is_active(&self, now: u64) -> bool {
+        self.starts_at <= now && now < self.ends_at
+    }
+
+    /// Whether the window covers the given stage
+    pub fn covers(&self, stage: HealthStage) -> bool {
+        self.stage.is_none_or(|s| s == stage)
+    }
+
+    /// Check that the window closes after it opens
+    pub fn validate(&self) -> eyre::Result<()> {
+        if self.ends_at <= self.starts_at {
+            eyre::bail!(
+                "Maintenance window for {} ends ({}) before it starts ({})",
+                self.rollup,
+                self.ends_at,
+                self.starts_at
+            );
+        }
+        Ok(())
+    }
+
+    /// Health issue noted as expected during the window
+    pub fn annotate(&self, issue: &str) -> String {
+        match &self.reason {
+            Some(reason) => format!("{} (maintenance until {}: {})", issue, self.ends_at, reason),
+            None => format!("{} (maintenance until {})", issue, self.ends_at),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_coverage() {
+        let window = MaintenanceWindow {
+            id: None,
+            rollup: "arbitrum".to_string(),
+            stage: Some(HealthStage::Batching),
+            starts_at: 100,
+            ends_at: 200,
+            reason: Some("ArbOS upgrade".to_string()),
+        };
+        assert!(!window.is_active(99));
+        assert!(window.is_active(100));
+        assert!(!window.is_active(200));
+        assert!(window.covers(HealthStage::Batching));
+        assert!(!window.covers(HealthStage::Proving));
+        assert_eq!(
+            window.annotate("No batch for 900 seconds"),
+            "No batch for 900 seconds (maintenance until 200: ArbOS upgrade)"
+        );
+        window.validate().unwrap();
+
+        let rollup_wide = MaintenanceWindow {
+            stage: None,
+            ends_at: 100,
+            ..window
+        };
+        assert!(rollup_wide.covers(HealthStage::Sequencer));
+        assert!(rollup_wide.validate().is_err());
+    }
+}
@@ -0,0 +1,370 @@
+use crate::health::{HealthCheckResult, HealthMonitor};
+use crate::types::{AppState, HealthStatus, RollupEvent};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Content type of the Prometheus text exposition format
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Health statuses reported by the `rollup_health_status` enum gauge
+const STATUSES: [HealthStatus; 5] = [
+    HealthStatus::Healthy,
+    HealthStatus::Delayed,
+    HealthStatus::Halted,
+    HealthStatus::Disconnected,
+    HealthStatus::Maintenance,
+];
+
+/// Name, help text and value of a gauge reporting an age of each rollup
+type AgeGauge = (
+    &'static str,
+    &'static str,
+    fn(&HealthCheckResult) -> Option<u64>,
+);
+
+/// Gauges of the time since each stage last progressed
+const AGES: [AgeGauge; 3] = [
+    (
+        "rollup_seconds_since_last_batch",
+        "Seconds since the rollup's last batch",
+        |result| result.last_batch_age_secs,
+    ),
+    (
+        "rollup_seconds_since_last_proof",
+        "Seconds since the rollup's last proof",
+        |result| result.last_proof_age_secs,
+    ),
+    (
+        "rollup_seconds_since_last_finalization",
+        "Seconds since the rollup's last finalization",
+        |result| result.last_finalization_age_secs,
+    ),
+];
+
+/// Counters kept since startup for the `/metrics` endpoint. Gauges are not
+/// stored here but read from the live state on each scrape.
+#[derive(Debug, Clone, Default)]
+pub struct Metrics {
+    inner: Arc<Counters>,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    /// Events applied, per rollup and event type
+    events: Mutex<BTreeMap<(String, String), u64>>,
+    /// Connection attempts, per stream and outcome
+    connects: Mutex<BTreeMap<(String, &'static str), u64>>,
+    /// WebSocket clients connected
+    ws_clients: AtomicU64,
+    /// Stream messages skipped by WebSocket clients that fell behind
+    lagged: AtomicU64,
+}
+
+impl Metrics {
+    /// Count an event applied and broadcast
+    pub fn record_event(&self, event: &RollupEvent) {
+        let key = (event.rollup.clone(), event.event_type.clone());
+        *lock(&self.inner.events).entry(key).or_default() += 1;
+    }
+
+    /// Count an attempt of `connect_with_retry` to open a stream
+    pub fn record_connect(&self, stream: &str, connected: bool) {
+        let outcome = if connected { "success" } else { "failure" };
+        *lock(&self.inner.connects)
+            .entry((stream.to_string(), outcome))
+            .or_default() += 1;
+    }
+
+    /// Count messages a lagging WebSocket client skipped
+    pub fn record_lagged(&self, skipped: u64) {
+        self.inner.lagged.fetch_add(skipped, Ordering::Relaxed);
+    }
+
+    /// Count a WebSocket client until the returned guard is dropped
+    pub fn ws_client(&self) -> WsClientGuard {
+        self.inner.ws_clients.fetch_add(1, Ordering::Relaxed);
+        WsClientGuard {
+            metrics: self.clone(),
+        }
+    }
+}
+
+/// Keeps a WebSocket client counted while it is connected
+pub struct WsClientGuard {
+    metrics: Metrics,
+}
+
+impl Drop for WsClientGuard {
+    fn drop(&mut self) {
+        self.metrics
+            .inner
+            .ws_clients
+            .fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => {
+            tracing::error!("Mutex poisoned in metrics, recovering");
+            poisoned.into_inner()
+        }
+    }
+}
+
+/// Render the counters and the current health and sequencer state in the
+/// Prometheus text exposition format
+pub fn render(app: &AppState, health: &HealthMonitor) -> String {
+    let mut out = String::new();
+    let counters = &app.metrics.inner;
+
+    family(
+        &mut out,
+        "rollup_events_total",
+        "counter",
+        "Events applied since startup",
+    );
+    for ((rollup, event_type), count) in lock(&counters.events).iter() {
+        sample(
+            &mut out,
+            "rollup_events_total",
+            &[("rollup", rollup), ("event_type", event_type)],
+            count,
+        );
+    }
+
+    let results = health.evaluate_all();
+    for (name, help, age) in AGES {
+        family(&mut out, name, "gauge", help);
+        for result in &results {
+            if let Some(age) = age(result) {
+                sample(&mut out, name, &[("rollup", &result.rollup)], age);
+            }
+        }
+    }
+
+    family(
+        &mut out,
+        "rollup_health_status",
+        "gauge",
+        "1 for the rollup's current health status, 0 for the others",
+    );
+    for result in &results {
+        for status in &STATUSES {
+            let name = format!("{:?}", status);
+            let value = u8::from(&result.status == status);
+            sample(
+                &mut out,
+                "rollup_health_status",
+                &[("rollup", &result.rollup), ("status", &name)],
+                value,
+            );
+        }
+    }
+
+    let now = health.now();
+    let sequencers = app.get_all_sequencer_statuses();
+    let mut sequencers: Vec<_> = sequencers.iter().collect();
+    sequencers.sort_by_key(|(rollup, _)| *rollup);
+    family(
+        &mut out,
+        "rollup_sequencer_latest_block",
+        "gauge",
+        "Latest L2 block observed",
+    );
+    for (rollup, status) in &sequencers {
+        if let Some(block) = status.latest_block {
+            sample(
+                &mut out,
+                "rollup_sequencer_latest_block",
+                &[("rollup", rollup)],
+                block,
+            );
+        }
+    }
+    family(
+        &mut out,
+        "rollup_sequencer_blocks_per_second",
+        "gauge",
+        "Estimated L2 block production rate",
+    );
+    for (rollup, status) in &sequencers {
+        if let Some(rate) = status.blocks_per_second {
+            sample(
+                &mut out,
+                "rollup_sequencer_blocks_per_second",
+                &[("rollup", rollup)],
+                rate,
+            );
+        }
+    }
+    family(
+        &mut out,
+        "rollup_sequencer_seconds_since_last_block",
+        "gauge",
+        "Seconds since the latest L2 block was produced",
+    );
+    for (rollup, status) in &sequencers {
+        // Age the block from its timestamp; the stored age is as of the last poll
+        let age = status
+            .latest_block_timestamp
+            .map(|t| now.saturating_sub(t))
+            .or(status.seconds_since_last_block);
+        if let Some(age) = age {
+            sample(
+                &mut out,
+                "rollup_sequencer_seconds_since_last_block",
+                &[("rollup", rollup)],
+                age,
+            );
+        }
+    }
+
+    family(
+        &mut out,
+        "rollup_websocket_clients",
+        "gauge",
+        "WebSocket clients connected to the stream",
+    );
+    sample(
+        &mut out,
+        "rollup_websocket_clients",
+        &[],
+        counters.ws_clients.load(Ordering::Relaxed),
+    );
+    family(
+        &mut out,
+        "rollup_broadcast_lagged_messages_total",
+        "counter",
+        "Stream messages dropped for WebSocket clients that fell behind",
+    );
+    sample(
+        &mut out,
+        "rollup_broadcast_lagged_messages_total",
+        &[],
+        counters.lagged.load(Ordering::Relaxed),
+    );
+
+    family(
+        &mut out,
+        "rollup_connect_attempts_total",
+        "counter",
+        "Stream connection attempts, by outcome",
+    );
+    for ((stream, outcome), count) in lock(&counters.connects).iter() {
+        sample(
+            &mut out,
+            "rollup_connect_attempts_total",
+            &[("stream", stream), ("outcome", outcome)],
+            count,
+        );
+    }
+
+    out
+}
+
+/// `# HELP` and `# TYPE` lines opening a metric family
+fn family(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+/// One sample line, with its label values escaped
+fn sample(out: &mut String, name: &str, labels: &[(&str, &str)], value: impl Display) {
+    out.push_str(name);
+    if !labels.is_empty() {
+        let labels: Vec<String> = labels
+            .iter()
+            .map(|(label, value)| format!("{}=\"{}\"", label, escape(value)))
+            .collect();
+        let _ = write!(out, "{{{}}}", labels.join(","));
+    }
+    let _ = writeln!(out, " {}", value);
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::Clock;
+    use crate::types::{EventStatus, SequencerStatus};
+
+    fn event(rollup: &str, event_type: &str) -> RollupEvent {
+        RollupEvent {
+            rollup: rollup.to_string(),
+            event_type: event_type.to_string(),
+            block_number: 19_000_000,
+            tx_hash: "0xabc".to_string(),
+            batch_number: None,
+            timestamp: Some(1_000),
+            observed_at: Some(1_000),
+            status: EventStatus::Confirmed,
+        }
+    }
+
+    #[test]
+    fn test_render_counters_and_gauges() {
+        let app = AppState::new();
+        let health = HealthMonitor::with_rollups(vec!["arbitrum".to_string()])
+            .with_clock(Clock::manual(1_300));
+        app.broadcast(event("arbitrum", "BatchDelivered"));
+        app.broadcast(event("arbitrum", "BatchDelivered"));
+        health.record_event(&event("arbitrum", "BatchDelivered"));
+        app.update_sequencer_status("arbitrum", |s| {
+            *s = SequencerStatus {
+                latest_block: Some(42),
+                latest_block_timestamp: Some(1_290),
+                blocks_per_second: Some(4.0),
+                is_producing: true,
+                ..SequencerStatus::default()
+            }
+        });
+        app.metrics.record_connect("l1/logs", false);
+        app.metrics.record_connect("l1/logs", true);
+        app.metrics.record_lagged(7);
+        let client = app.metrics.ws_client();
+
+        let text = render(&app, &health);
+        for line in [
+            "# TYPE rollup_events_total counter",
+            "rollup_events_total{rollup=\"arbitrum\",event_type=\"BatchDelivered\"} 2",
+            "rollup_seconds_since_last_batch{rollup=\"arbitrum\"} 300",
+            "rollup_health_status{rollup=\"arbitrum\",status=\"Healthy\"} 1",
+            "rollup_health_status{rollup=\"arbitrum\",status=\"Halted\"} 0",
+            "rollup_sequencer_latest_block{rollup=\"arbitrum\"} 42",
+            "rollup_sequencer_blocks_per_second{rollup=\"arbitrum\"} 4",
+            "rollup_sequencer_seconds_since_last_block{rollup=\"arbitrum\"} 10",
+            "rollup_websocket_clients 1",
+            "rollup_broadcast_lagged_messages_total 7",
+            "rollup_connect_attempts_total{stream=\"l1/logs\",outcome=\"failure\"} 1",
+            "rollup_connect_attempts_total{stream=\"l1/logs\",outcome=\"success\"} 1",
+        ] {
+            assert!(
+                text.lines().any(|l| l == line),
+                "missing {}:\n{}",
+                line,
+                text
+            );
+        }
+        assert!(!text.contains("rollup_seconds_since_last_proof{"));
+
+        drop(client);
+        assert!(render(&app, &health).contains("rollup_websocket_clients 0\n"));
+    }
+
+    #[test]
+    fn test_label_values_escaped() {
+        let mut out = String::new();
+        sample(&mut out, "m", &[("l", "a\"b\\c\nd")], 1);
+        assert_eq!(out, "m{l=\"a\\\"b\\\\c\\nd\"} 1\n");
+    }
+}
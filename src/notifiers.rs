@@ -0,0 +1,259 @@
+use crate::alerts::{Alert, AlertKind, AlertSeverity, AlertState, WebhookTarget};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// Source reported to PagerDuty for every event
+const PAGERDUTY_SOURCE: &str = "rollup-proof-status";
+
+/// Embed and attachment colors
+const RED: u32 = 0xE0_1E_5A;
+const AMBER: u32 = 0xEC_B2_2E;
+const GREEN: u32 = 0x2E_B6_7D;
+
+/// Body a webhook target receives alerts as
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    /// The `Alert` itself
+    #[default]
+    Json,
+    /// Slack incoming webhook message with Block Kit blocks
+    Slack,
+    /// Discord webhook message with one embed
+    Discord,
+    /// PagerDuty Events API v2 trigger or resolve event
+    #[serde(rename = "pagerduty")]
+    PagerDuty,
+}
+
+/// Render an alert as the body the target expects
+pub fn render(target: &WebhookTarget, alert: &Alert) -> Value {
+    match target.format {
+        WebhookFormat::Json => json!(alert),
+        WebhookFormat::Slack => slack(alert),
+        WebhookFormat::Discord => discord(alert),
+        WebhookFormat::PagerDuty => pagerduty(alert, target.routing_key.as_deref().unwrap_or("")),
+    }
+}
+
+/// Slack message: a headline, the status fields, the reasons and a footer
+/// with the time and dedup key. `text` is the notification fallback.
+fn slack(alert: &Alert) -> Value {
+    let icon = match (alert.state, alert.severity) {
+        (AlertState::Resolved, _) => ":large_green_circle:",
+        (AlertState::Triggered, AlertSeverity::Warning) => ":large_yellow_circle:",
+        (AlertState::Triggered, AlertSeverity::Critical) => ":red_circle:",
+    };
+    let fields: Vec<Value> = fields(alert)
+        .into_iter()
+        .map(|(name, value)| {
+            let text = format!("*{}*\n{}", name, value);
+            json!({ "type": "mrkdwn", "text": text })
+        })
+        .collect();
+    let mut blocks = vec![
+        json!({
+            "type": "section",
+            "text": { "type": "mrkdwn", "text": format!("{} *{}*", icon, alert.summary) }
+        }),
+        json!({ "type": "section", "fields": fields }),
+    ];
+    if !alert.reasons.is_empty() {
+        let reasons: Vec<String> = alert.reasons.iter().map(|r| format!("• {}", r)).collect();
+        blocks.push(json!({
+            "type": "section",
+            "text": { "type": "mrkdwn", "text": reasons.join("\n") }
+        }));
+    }
+    blocks.push(json!({
+        "type": "context",
+        "elements": [{
+            "type": "mrkdwn",
+            "text": format!(
+                "<!date^{}^{{date_short_pretty}} {{time_secs}}|{}> · `{}`",
+                alert.at,
+                timestamp(alert.at),
+                alert.dedup_key
+            )
+        }]
+    }));
+    json!({ "text": alert.summary, "blocks": blocks })
+}
+
+/// Discord message with one embed colored by severity
+fn discord(alert: &Alert) -> Value {
+    let fields: Vec<Value> = fields(alert)
+        .into_iter()
+        .map(|(name, value)| json!({ "name": name, "value": value, "inline": true }))
+        .collect();
+    json!({
+        "embeds": [{
+            "title": alert.summary,
+            "description": alert.reasons.join("\n"),
+            "color": color(alert),
+            "fields": fields,
+            "timestamp": timestamp(alert.at),
+            "footer": { "text": alert.dedup_key }
+        }]
+    })
+}
+
+/// PagerDuty Events v2 event; resolves close the incident its trigger opened
+/// through the shared dedup key
+fn pagerduty(alert: &Alert, routing_key: &str) -> Value {
+    let action = match alert.state {
+        AlertState::Triggered => "trigger",
+        AlertState::Resolved => "resolve",
+    };
+    json!({
+        "routing_key": routing_key,
+        "event_action": action,
+        "dedup_key": alert.dedup_key,
+        "payload": {
+            "summary": alert.summary,
+            "source": PAGERDUTY_SOURCE,
+            "severity": alert.severity,
+            "timestamp": timestamp(alert.at),
+            "component": alert.rollup,
+            "group": alert.stage.map(|s| s.name()),
+            "class": alert.kind,
+            "custom_details": {
+                "previous": alert.previous,
+                "status": alert.status,
+                "reasons": alert.reasons,
+                "health": alert.health,
+                "sequencer": alert.sequencer,
+            }
+        }
+    })
+}
+
+/// Name and value of the fields shown in chat messages
+fn fields(alert: &Alert) -> Vec<(&'static str, String)> {
+    let subject = match alert.kind {
+        AlertKind::Health => "Health",
+        AlertKind::Sequencer => "Sequencer",
+    };
+    let mut fields = vec![
+        ("Rollup", alert.rollup.clone()),
+        (
+            subject,
+            format!("{} (was {})", alert.status, alert.previous),
+        ),
+        ("Severity", format!("{:?}", alert.severity)),
+    ];
+    if let Some(stage) = alert.stage {
+        fields.push(("Stage", stage.name().to_string()));
+    }
+    fields
+}
+
+fn color(alert: &Alert) -> u32 {
+    match (alert.state, alert.severity) {
+        (AlertState::Resolved, _) => GREEN,
+        (AlertState::Triggered, AlertSeverity::Warning) => AMBER,
+        (AlertState::Triggered, AlertSeverity::Critical) => RED,
+    }
+}
+
+/// RFC 3339 time of a unix timestamp
+fn timestamp(at: u64) -> String {
+    chrono::DateTime::from_timestamp(at as i64, 0)
+        .unwrap_or_default()
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alerts::AlertCause;
+    use crate::health::{HealthMonitor, HealthStage};
+    use std::collections::BTreeMap;
+
+    fn target(format: WebhookFormat) -> WebhookTarget {
+        WebhookTarget {
+            name: "ops".to_string(),
+            url: "http://127.0.0.1:1/hook".to_string(),
+            format,
+            routing_key: Some("R0UT1NGKEY".to_string()),
+            rollups: Vec::new(),
+            min_severity: AlertSeverity::Warning,
+            headers: BTreeMap::new(),
+        }
+    }
+
+    fn alert(state: AlertState) -> Alert {
+        Alert {
+            rollup: "base".to_string(),
+            kind: AlertKind::Health,
+            state,
+            cause: AlertCause::Change,
+            rule: "ops".to_string(),
+            severity: AlertSeverity::Critical,
+            summary: "base is Halted (was Delayed)".to_string(),
+            stage: Some(HealthStage::Proving),
+            dedup_key: "base/health/proving".to_string(),
+            previous: "Delayed".to_string(),
+            status: "Halted".to_string(),
+            reasons: vec!["No proof for 7300 seconds, 2 missed cadences".to_string()],
+            at: 1_700_000_000,
+            health: HealthMonitor::new().check_health("base"),
+            sequencer: None,
+        }
+    }
+
+    #[test]
+    fn test_slack_blocks() {
+        let body = render(&target(WebhookFormat::Slack), &alert(AlertState::Triggered));
+        assert_eq!(body["text"], "base is Halted (was Delayed)");
+        let blocks = body["blocks"].as_array().unwrap();
+        assert_eq!(
+            blocks[0]["text"]["text"],
+            ":red_circle: *base is Halted (was Delayed)*"
+        );
+        assert_eq!(
+            blocks[1]["fields"][1]["text"],
+            "*Health*\nHalted (was Delayed)"
+        );
+        assert_eq!(blocks[1]["fields"][3]["text"], "*Stage*\nproving");
+        assert_eq!(
+            blocks[2]["text"]["text"],
+            "• No proof for 7300 seconds, 2 missed cadences"
+        );
+        assert_eq!(
+            blocks[3]["elements"][0]["text"],
+            "<!date^1700000000^{date_short_pretty} {time_secs}|2023-11-14T22:13:20Z> · `base/health/proving`"
+        );
+    }
+
+    #[test]
+    fn test_discord_embed() {
+        let body = render(
+            &target(WebhookFormat::Discord),
+            &alert(AlertState::Resolved),
+        );
+        let embed = &body["embeds"][0];
+        assert_eq!(embed["color"], GREEN);
+        assert_eq!(embed["timestamp"], "2023-11-14T22:13:20Z");
+        assert_eq!(embed["fields"][0]["name"], "Rollup");
+        assert_eq!(embed["fields"][0]["value"], "base");
+        assert_eq!(embed["footer"]["text"], "base/health/proving");
+    }
+
+    #[test]
+    fn test_pagerduty_trigger_and_resolve_share_dedup_key() {
+        let pagerduty = target(WebhookFormat::PagerDuty);
+        let trigger = render(&pagerduty, &alert(AlertState::Triggered));
+        let resolve = render(&pagerduty, &alert(AlertState::Resolved));
+
+        assert_eq!(trigger["routing_key"], "R0UT1NGKEY");
+        assert_eq!(trigger["event_action"], "trigger");
+        assert_eq!(resolve["event_action"], "resolve");
+        assert_eq!(trigger["dedup_key"], resolve["dedup_key"]);
+        assert_eq!(trigger["payload"]["severity"], "critical");
+        assert_eq!(trigger["payload"]["component"], "base");
+        assert_eq!(trigger["payload"]["group"], "proving");
+        assert_eq!(trigger["payload"]["class"], "health");
+        assert_eq!(trigger["payload"]["custom_details"]["status"], "Halted");
+    }
+}
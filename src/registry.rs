@@ -0,0 +1,112 @@
+use crate::watcher::RollupWatcher;
+use std::sync::Arc;
+
+/// Set of rollups the service tracks.
+///
+/// `main.rs` iterates the registry to spawn L1 watchers, L2 sequencer pollers
+/// and to resolve `/rollups/{name}/...` routes, so adding a rollup only means
+/// registering one more `RollupWatcher` implementation.
+#[derive(Clone, Default)]
+pub struct RollupRegistry {
+    rollups: Vec<Arc<dyn RollupWatcher>>,
+}
+
+impl RollupRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a rollup (builder style)
+    pub fn with(mut self, rollup: impl RollupWatcher + 'static) -> Self {
+        self.register(Arc::new(rollup));
+        self
+    }
+
+    /// Register a rollup, replacing any existing entry with the same name
+    pub fn register(&mut self, rollup: Arc<dyn RollupWatcher>) {
+        self.rollups.retain(|r| r.name() != rollup.name());
+        self.rollups.push(rollup);
+    }
+
+    /// Look up a rollup by name
+    pub fn get(&self, name: &str) -> Option<Arc<dyn RollupWatcher>> {
+        self.rollups.iter().find(|r| r.name() == name).cloned()
+    }
+
+    /// Whether a rollup with this name is registered
+    pub fn contains(&self, name: &str) -> bool {
+        self.rollups.iter().any(|r| r.name() == name)
+    }
+
+    /// Names of all registered rollups, in registration order
+    pub fn names(&self) -> Vec<String> {
+        self.rollups.iter().map(|r| r.name().to_string()).collect()
+    }
+
+    /// Iterate over registered rollups, in registration order
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<dyn RollupWatcher>> {
+        self.rollups.iter()
+    }
+
+    /// Number of registered rollups
+    pub fn len(&self) -> usize {
+        self.rollups.len()
+    }
+
+    /// Whether the registry is empty
+    pub fn is_empty(&self) -> bool {
+        self.rollups.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::watcher::{Contract, EventStream};
+    use ethers::types::Log;
+
+    struct Named(&'static str);
+
+    impl RollupWatcher for Named {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn contracts(&self) -> eyre::Result<Vec<Contract>> {
+            Ok(Vec::new())
+        }
+
+        fn streams(&self) -> Vec<EventStream> {
+            Vec::new()
+        }
+
+        fn decode(&self, _stream: &EventStream, _log: &Log) -> eyre::Result<String> {
+            Ok(String::new())
+        }
+    }
+
+    #[test]
+    fn test_registry_order_and_lookup() {
+        let registry = RollupRegistry::new()
+            .with(Named("arbitrum"))
+            .with(Named("starknet"));
+
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.names(), vec!["arbitrum", "starknet"]);
+        assert!(registry.contains("starknet"));
+        assert!(!registry.contains("base"));
+        assert_eq!(registry.get("arbitrum").unwrap().name(), "arbitrum");
+        assert!(registry.get("base").is_none());
+    }
+
+    #[test]
+    fn test_registry_replaces_duplicates() {
+        let registry = RollupRegistry::new()
+            .with(Named("arbitrum"))
+            .with(Named("base"))
+            .with(Named("arbitrum"));
+
+        assert_eq!(registry.names(), vec!["base", "arbitrum"]);
+    }
+}
@@ -0,0 +1,336 @@
+use crate::types::{AppState, EventStatus, RollupEvent, RollupStatus, StreamMessage, StreamNotice};
+use crate::watcher::StatusSlot;
+use ethers::types::{Log, H256, U256};
+
+/// An event applied to state whose block is not yet buried deep enough
+#[derive(Debug, Clone)]
+struct PendingEvent {
+    /// Log identity: (transaction hash, log index)
+    id: (H256, U256),
+    /// Cursor key of the stream the log belongs to
+    stream: String,
+    /// Log the event was applied from
+    log: Log,
+    event: RollupEvent,
+    /// Status slots the event updated
+    slots: Vec<StatusSlot>,
+    /// Rollup status before the event was applied
+    previous: RollupStatus,
+    /// L1 block at which the event becomes confirmed
+    confirm_at: u64,
+}
+
+impl PendingEvent {
+    /// Slot value and transaction hash this event wrote
+    fn written(&self) -> (Option<String>, Option<String>) {
+        (
+            self.event.batch_number.clone(),
+            Some(self.event.tx_hash.clone()),
+        )
+    }
+}
+
+/// Tracks events that have not reached their rollup's confirmation depth.
+///
+/// Events are applied to `RollupStatus` as soon as their log arrives and
+/// reported as pending. Once the L1 head is `confirmations` blocks past the
+/// event's block it is confirmed; if its log is removed by a reorg first, the
+/// status slots it wrote are rolled back to their previous values and clients
+/// are told to undo it.
+#[derive(Debug, Default)]
+pub struct PendingEvents {
+    /// Latest known L1 head
+    head: Option<u64>,
+    events: Vec<PendingEvent>,
+}
+
+impl PendingEvents {
+    /// Number of events awaiting confirmation
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Whether no events are awaiting confirmation
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Record a newer L1 head (never moves backward)
+    pub fn observe_head(&mut self, head: u64) {
+        self.head = Some(self.head.map_or(head, |h| h.max(head)));
+    }
+
+    /// Status of an event emitted in `block` given the latest known head
+    pub fn status_for(&self, block: u64, confirmations: u64) -> EventStatus {
+        match self.head {
+            _ if confirmations == 0 => EventStatus::Confirmed,
+            Some(head) if head >= block.saturating_add(confirmations) => EventStatus::Confirmed,
+            _ => EventStatus::Pending,
+        }
+    }
+
+    /// Track a pending event applied from a stream's `log`; `previous` is the
+    /// rollup status before it was applied
+    pub fn track(
+        &mut self,
+        stream: &str,
+        log: &Log,
+        event: RollupEvent,
+        slots: Vec<StatusSlot>,
+        previous: RollupStatus,
+        confirmations: u64,
+    ) {
+        let (Some(tx_hash), Some(log_index)) = (log.transaction_hash, log.log_index) else {
+            return;
+        };
+        let confirm_at = event.block_number.saturating_add(confirmations);
+        self.events.push(PendingEvent {
+            id: (tx_hash, log_index),
+            stream: stream.to_string(),
+            log: log.clone(),
+            event,
+            slots,
+            previous,
+            confirm_at,
+        });
+    }
+
+    /// Earliest L1 block of a stream's pending events
+    pub fn first_block(&self, stream: &str) -> Option<u64> {
+        self.events
+            .iter()
+            .filter(|p| p.stream == stream)
+            .map(|p| p.event.block_number)
+            .min()
+    }
+
+    /// Logs of the events `confirm` would confirm at `head`
+    pub fn due(&self, head: u64) -> Vec<Log> {
+        self.events
+            .iter()
+            .filter(|p| p.confirm_at <= head)
+            .map(|p| p.log.clone())
+            .collect()
+    }
+
+    /// Confirm every event buried under its confirmation depth at `head`,
+    /// notifying clients. Returns the confirmed events.
+    pub fn confirm(&mut self, head: u64, state: &AppState) -> Vec<RollupEvent> {
+        self.observe_head(head);
+
+        let (confirmed, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.events)
+            .into_iter()
+            .partition(|p| p.confirm_at <= head);
+        self.events = pending;
+
+        confirmed
+            .into_iter()
+            .map(|p| {
+                let mut event = p.event;
+                event.status = EventStatus::Confirmed;
+                if let Some(store) = &state.store {
+                    if let Err(e) = store.set_event_status(&event, EventStatus::Confirmed) {
+                        tracing::error!(rollup = %event.rollup, error = ?e, "Failed to persist event status");
+                    }
+                }
+                state.publish(StreamMessage::Notice(StreamNotice::EventConfirmed(
+                    event.clone(),
+                )));
+                event
+            })
+            .collect()
+    }
+
+    /// Undo the pending event whose log was removed by a reorg.
+    ///
+    /// Status slots still holding the event's value are restored to what they
+    /// held before it; if a later pending event has since overwritten a slot,
+    /// that event's remembered previous value is patched instead. Returns the
+    /// retracted event, or `None` if the log was not pending.
+    pub fn retract(&mut self, log: &Log, state: &AppState) -> Option<RollupEvent> {
+        let (Some(tx_hash), Some(log_index)) = (log.transaction_hash, log.log_index) else {
+            return None;
+        };
+        let Some(index) = self
+            .events
+            .iter()
+            .position(|p| p.id == (tx_hash, log_index))
+        else {
+            tracing::warn!(
+                tx = ?tx_hash,
+                block = ?log.block_number,
+                "Removed log is not pending (already confirmed or unknown), ignoring"
+            );
+            return None;
+        };
+        let retracted = self.events.remove(index);
+        let written = retracted.written();
+        let rollup = retracted.event.rollup.clone();
+
+        state.update_status(&rollup, |status| {
+            for slot in &retracted.slots {
+                let (value, tx) = slot.get(status);
+                if (value.clone(), tx.clone()) == written {
+                    slot.copy_from(status, &retracted.previous);
+                    continue;
+                }
+                // A later pending event overwrote the slot; fix what it would restore
+                for later in self.events[index..]
+                    .iter_mut()
+                    .filter(|p| p.event.rollup == rollup && p.slots.contains(slot))
+                {
+                    let (value, tx) = slot.get(&later.previous);
+                    if (value.clone(), tx.clone()) == written {
+                        slot.copy_from(&mut later.previous, &retracted.previous);
+                    }
+                }
+            }
+        });
+
+        let mut event = retracted.event;
+        event.status = EventStatus::Pending;
+        if let Some(store) = &state.store {
+            if let Err(e) = store.delete_event(&event) {
+                tracing::error!(rollup = %event.rollup, error = ?e, "Failed to delete retracted event");
+            }
+        }
+        state.publish(StreamMessage::Notice(StreamNotice::EventRetracted(
+            event.clone(),
+        )));
+
+        tracing::warn!(
+            rollup = %event.rollup,
+            event = %event.event_type,
+            block = event.block_number,
+            tx = %event.tx_hash,
+            "Event retracted by L1 reorg"
+        );
+        Some(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::U64;
+
+    fn log(block: u64, tx: u64) -> Log {
+        Log {
+            block_number: Some(U64::from(block)),
+            transaction_hash: Some(H256::from_low_u64_be(tx)),
+            log_index: Some(U256::zero()),
+            ..Default::default()
+        }
+    }
+
+    /// Apply a pending batch event the way the watcher does
+    fn apply(pending: &mut PendingEvents, state: &AppState, block: u64, tx: u64, batch: &str) {
+        let log = log(block, tx);
+        let event = RollupEvent {
+            rollup: "arbitrum".to_string(),
+            event_type: "BatchDelivered".to_string(),
+            block_number: block,
+            tx_hash: format!("{:?}", log.transaction_hash.unwrap()),
+            batch_number: Some(batch.to_string()),
+            timestamp: None,
+            observed_at: None,
+            status: EventStatus::Pending,
+        };
+        let previous = state.get_status("arbitrum");
+        state.update_status("arbitrum", |s| {
+            StatusSlot::Batch.apply(s, batch, &event.tx_hash)
+        });
+        pending.track(
+            "arbitrum/batch",
+            &log,
+            event,
+            vec![StatusSlot::Batch],
+            previous,
+            2,
+        );
+    }
+
+    #[test]
+    fn test_status_for() {
+        let mut pending = PendingEvents::default();
+        assert_eq!(pending.status_for(100, 0), EventStatus::Confirmed);
+        assert_eq!(pending.status_for(100, 2), EventStatus::Pending);
+
+        pending.observe_head(101);
+        assert_eq!(pending.status_for(100, 2), EventStatus::Pending);
+        pending.observe_head(102);
+        assert_eq!(pending.status_for(100, 2), EventStatus::Confirmed);
+        pending.observe_head(50);
+        assert_eq!(pending.status_for(100, 2), EventStatus::Confirmed);
+    }
+
+    #[test]
+    fn test_confirm_at_depth() {
+        let state = AppState::new();
+        let mut rx = state.tx.subscribe();
+        let mut pending = PendingEvents::default();
+        apply(&mut pending, &state, 100, 1, "1");
+        apply(&mut pending, &state, 101, 2, "2");
+
+        assert!(pending.confirm(101, &state).is_empty());
+        let confirmed = pending.confirm(102, &state);
+        assert_eq!(confirmed.len(), 1);
+        assert_eq!(confirmed[0].batch_number, Some("1".to_string()));
+        assert_eq!(confirmed[0].status, EventStatus::Confirmed);
+        assert_eq!(pending.len(), 1);
+
+        match rx.try_recv().unwrap() {
+            StreamMessage::Notice(StreamNotice::EventConfirmed(event)) => {
+                assert_eq!(event.batch_number, Some("1".to_string()))
+            }
+            other => panic!("Expected EventConfirmed, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_retract_rolls_back_status() {
+        let state = AppState::new();
+        state.update_status("arbitrum", |s| StatusSlot::Batch.apply(s, "0", "0x0"));
+        let mut pending = PendingEvents::default();
+        apply(&mut pending, &state, 100, 1, "1");
+        let mut rx = state.tx.subscribe();
+
+        let retracted = pending.retract(&log(100, 1), &state).unwrap();
+        assert_eq!(retracted.batch_number, Some("1".to_string()));
+        assert!(pending.is_empty());
+        assert_eq!(
+            state.get_status("arbitrum").latest_batch,
+            Some("0".to_string())
+        );
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            StreamMessage::Notice(StreamNotice::EventRetracted(_))
+        ));
+
+        // Unknown or already handled logs are ignored
+        assert!(pending.retract(&log(100, 1), &state).is_none());
+    }
+
+    #[test]
+    fn test_retract_under_later_event() {
+        let state = AppState::new();
+        state.update_status("arbitrum", |s| StatusSlot::Batch.apply(s, "0", "0x0"));
+        let mut pending = PendingEvents::default();
+        apply(&mut pending, &state, 100, 1, "1");
+        apply(&mut pending, &state, 101, 2, "2");
+
+        // Retracting the older event keeps the newer value in place...
+        pending.retract(&log(100, 1), &state).unwrap();
+        assert_eq!(
+            state.get_status("arbitrum").latest_batch,
+            Some("2".to_string())
+        );
+
+        // ...and retracting the newer one then restores the original
+        pending.retract(&log(101, 2), &state).unwrap();
+        assert_eq!(
+            state.get_status("arbitrum").latest_batch,
+            Some("0".to_string())
+        );
+    }
+}
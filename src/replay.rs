@@ -0,0 +1,281 @@
+use crate::backfill::{apply_log, LogTracker};
+use crate::capture::{CaptureEntry, CaptureRecord};
+use crate::clock::Clock;
+use crate::health::HealthMonitor;
+use crate::l1::LogRouter;
+use crate::sequencer::SequencerTracker;
+use crate::types::AppState;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// Feeds a capture back through the live log processing, `AppState` and
+/// `HealthMonitor` code on a virtual clock.
+///
+/// `health` must run on `clock` (see `HealthMonitor::with_clock`) so ages are
+/// measured against the recording's time rather than the wall clock.
+pub struct Replay {
+    pub router: Arc<LogRouter>,
+    pub state: AppState,
+    pub health: HealthMonitor,
+    /// Virtual clock moved to each entry's time
+    pub clock: Clock,
+    /// Virtual time between health re-evaluations
+    pub check_interval: Duration,
+    /// Threshold before declaring sequencer downtime
+    pub downtime_threshold: Duration,
+    /// Playback speed relative to the recording; 0 replays as fast as possible
+    pub speed: f64,
+}
+
+impl Replay {
+    /// Replay `entries` in order, re-evaluating health every `check_interval`
+    /// of virtual time in between, as the background monitor would. Returns
+    /// the number of entries replayed.
+    pub async fn run(&self, entries: &[CaptureEntry], cancel_token: &CancellationToken) -> usize {
+        let check_secs = self.check_interval.as_secs().max(1);
+        let mut next_check = self.clock.now() + check_secs;
+        let mut previous_at: Option<u64> = None;
+        let mut tracker = LogTracker::default();
+        let mut sequencers: HashMap<String, SequencerTracker> = HashMap::new();
+
+        for (replayed, entry) in entries.iter().enumerate() {
+            if let (Some(previous), true) = (previous_at, self.speed > 0.0) {
+                let gap = entry.at.saturating_sub(previous) as f64 / self.speed;
+                tokio::select! {
+                    _ = tokio::time::sleep(Duration::from_secs_f64(gap)) => {}
+                    _ = cancel_token.cancelled() => {
+                        tracing::info!("Replay cancelled");
+                        return replayed;
+                    }
+                }
+            }
+            previous_at = Some(entry.at);
+
+            // Health checks that fell due before this entry
+            while next_check <= entry.at {
+                self.clock.set(next_check);
+                self.health.reevaluate();
+                next_check += check_secs;
+            }
+            self.clock.set(entry.at);
+            self.apply(&entry.record, &mut tracker, &mut sequencers);
+        }
+
+        self.health.reevaluate();
+        entries.len()
+    }
+
+    /// Apply one captured input the way its watcher would have
+    fn apply(
+        &self,
+        record: &CaptureRecord,
+        tracker: &mut LogTracker,
+        sequencers: &mut HashMap<String, SequencerTracker>,
+    ) {
+        match record {
+            CaptureRecord::L1Log {
+                log,
+                block_timestamp,
+                refill,
+            } => {
+                if let (Some(block), Some(timestamp)) = (log.block_number, block_timestamp) {
+                    tracker.block_times.insert(block.as_u64(), *timestamp);
+                }
+                apply_log(
+                    log,
+                    &self.router,
+                    tracker,
+                    *refill,
+                    &self.state,
+                    &self.health,
+                );
+            }
+            CaptureRecord::L1Head { block, timestamp } => {
+                if let Some(timestamp) = timestamp {
+                    tracker.block_times.insert(*block, *timestamp);
+                }
+                tracker.pending.confirm(*block, &self.state);
+            }
+            CaptureRecord::L2Poll { rollup, block } => {
+                sequencers.entry(rollup.clone()).or_default().apply(
+                    rollup,
+                    *block,
+                    self.downtime_threshold,
+                    &self.state,
+                    &self.health,
+                );
+            }
+        }
+    }
+}
+
+/// Replay a capture until it ends or `cancel_token` is cancelled
+pub async fn start_replay(
+    entries: Vec<CaptureEntry>,
+    replay: Replay,
+    cancel_token: CancellationToken,
+) {
+    tracing::info!(
+        entries = entries.len(),
+        speed = replay.speed,
+        "Starting capture replay"
+    );
+    let replayed = replay.run(&entries, &cancel_token).await;
+    tracing::info!(
+        replayed = replayed,
+        virtual_time = replay.clock.now(),
+        "Capture replay finished"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture::{read_capture, Recorder};
+    use crate::health::HealthCategory;
+    use crate::registry::RollupRegistry;
+    use crate::sequencer::L2Block;
+    use crate::store::Store;
+    use crate::types::HealthStatus;
+    use crate::watcher::{Contract, EventStream, RollupWatcher, StatusSlot};
+    use ethers::types::{Address, Log, H256, U64};
+
+    struct TestRollup;
+
+    impl RollupWatcher for TestRollup {
+        fn name(&self) -> &str {
+            "test"
+        }
+
+        fn contracts(&self) -> eyre::Result<Vec<Contract>> {
+            Ok(vec![Contract {
+                name: "inbox".to_string(),
+                address: Address::repeat_byte(0xaa),
+            }])
+        }
+
+        fn streams(&self) -> Vec<EventStream> {
+            vec![EventStream {
+                name: "batch".to_string(),
+                event_type: "BatchDelivered".to_string(),
+                contract: "inbox".to_string(),
+                topic: H256::repeat_byte(0x01),
+                slots: vec![StatusSlot::Batch],
+                category: HealthCategory::Batch,
+                sequential: false,
+            }]
+        }
+
+        fn decode(&self, _stream: &EventStream, log: &Log) -> eyre::Result<String> {
+            Ok(log.block_number.unwrap_or_default().to_string())
+        }
+    }
+
+    fn batch_log(block: u64) -> Log {
+        Log {
+            address: Address::repeat_byte(0xaa),
+            topics: vec![H256::repeat_byte(0x01)],
+            block_number: Some(U64::from(block)),
+            transaction_hash: Some(H256::from_low_u64_be(block)),
+            log_index: Some(0.into()),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replay_reproduces_health_transitions() {
+        const START: u64 = 1_700_000_000;
+        let path = std::env::temp_dir().join(format!("replay-{}.ndjson", rand::random::<u64>()));
+
+        // Record a batch, a failed poll, two hours of silence with the sequencer
+        // stalling, then recovery
+        let clock = Clock::manual(START);
+        let recorder = Recorder::create(&path).unwrap().with_clock(clock.clone());
+        let record_at = |at: u64, record: CaptureRecord| {
+            clock.set(at);
+            recorder.record(record);
+        };
+        let poll = |timestamp| CaptureRecord::L2Poll {
+            rollup: "test".to_string(),
+            block: Some(L2Block {
+                number: Some(timestamp),
+                timestamp,
+            }),
+        };
+        record_at(
+            START,
+            CaptureRecord::L1Log {
+                log: Box::new(batch_log(100)),
+                block_timestamp: Some(START - 12),
+                refill: false,
+            },
+        );
+        record_at(START + 10, poll(START + 10));
+        // A failed poll inside the threshold says nothing about the sequencer
+        record_at(
+            START + 20,
+            CaptureRecord::L2Poll {
+                rollup: "test".to_string(),
+                block: None,
+            },
+        );
+        record_at(START + 120, poll(START + 10));
+        record_at(START + 7200, poll(START + 7200));
+        record_at(
+            START + 7212,
+            CaptureRecord::L1Log {
+                log: Box::new(batch_log(700)),
+                block_timestamp: Some(START + 7200),
+                refill: false,
+            },
+        );
+        let entries = read_capture(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(entries.len(), 6);
+
+        let store = Store::open_in_memory().unwrap();
+        let replay_clock = Clock::manual(entries[0].at);
+        let replay = Replay {
+            router: Arc::new(LogRouter::from_registry(
+                &RollupRegistry::new().with(TestRollup),
+            )),
+            state: AppState::new(),
+            health: HealthMonitor::with_rollups(vec!["test".to_string()])
+                .with_clock(replay_clock.clone())
+                .with_store(store.clone()),
+            clock: replay_clock,
+            check_interval: Duration::from_secs(60),
+            downtime_threshold: Duration::from_secs(30),
+            speed: 0.0,
+        };
+
+        assert_eq!(replay.run(&entries, &CancellationToken::new()).await, 6);
+        assert_eq!(replay.clock.now(), START + 7212);
+
+        // Ages were measured in recorded time, not wall-clock time: the
+        // sequencer is down from the stale poll until batches resume
+        let mut transitions = store.transitions("test", 10).unwrap();
+        transitions.reverse();
+        let steps: Vec<_> = transitions
+            .iter()
+            .map(|t| (t.from.clone(), t.to.clone(), t.at - START))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                (HealthStatus::Disconnected, HealthStatus::Healthy, 0),
+                (HealthStatus::Healthy, HealthStatus::Halted, 120),
+                (HealthStatus::Halted, HealthStatus::Healthy, 7212),
+            ]
+        );
+
+        let status = replay.state.get_status("test");
+        assert_eq!(status.latest_batch, Some("700".to_string()));
+        assert_eq!(status.last_updated, Some(START + 7212));
+        let sequencer = replay.state.get_sequencer_status("test");
+        assert!(sequencer.is_producing);
+        assert_eq!(sequencer.last_polled, Some(START + 7200));
+    }
+}
@@ -0,0 +1,412 @@
+use crate::health::HealthCategory;
+use crate::registry::RollupRegistry;
+use crate::sequencer::SequencerKind;
+use crate::watcher::{Contract, EventStream, RollupWatcher, StatusSlot};
+use ethers::abi::{self, RawLog, Token};
+use ethers::types::{Address, Log, I256};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{env, fs};
+
+/// Top-level layout of the rollup definitions file
+#[derive(Debug, Clone, Deserialize)]
+pub struct RollupsFile {
+    /// Rollup definitions, in registration order
+    #[serde(rename = "rollup", default)]
+    pub rollups: Vec<RollupDefinition>,
+}
+
+/// Declarative definition of a rollup tracked on L1
+#[derive(Debug, Clone, Deserialize)]
+pub struct RollupDefinition {
+    /// Rollup name used for routes, state keys and events
+    pub name: String,
+    /// L2 sequencer polling settings
+    #[serde(default)]
+    pub sequencer: SequencerDefinition,
+    /// L1 blocks an event must be buried under before it is confirmed
+    #[serde(default)]
+    pub confirmations: u64,
+    /// L1 contracts emitting the watched events
+    pub contracts: Vec<ContractDefinition>,
+    /// Events to subscribe to
+    pub events: Vec<EventDefinition>,
+}
+
+/// L2 sequencer polling settings for a rollup
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SequencerDefinition {
+    /// JSON-RPC dialect of the L2 node
+    #[serde(default)]
+    pub kind: SequencerKind,
+    /// Default polling interval in milliseconds (overridable via `<ROLLUP>_L2_POLL_MS`)
+    pub poll_ms: Option<u64>,
+}
+
+/// An L1 contract and the ABI used to decode its events
+#[derive(Debug, Clone, Deserialize)]
+pub struct ContractDefinition {
+    /// Contract key referenced by events
+    pub name: String,
+    /// Contract address (optional if `address_env` is set)
+    pub address: Option<Address>,
+    /// Environment variable overriding `address`
+    pub address_env: Option<String>,
+    /// Path to the contract ABI JSON, relative to the definitions file
+    pub abi: PathBuf,
+}
+
+/// An event subscription and how it maps onto status and health
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventDefinition {
+    /// Stream name used in logs
+    pub stream: String,
+    /// Key of the contract emitting the event
+    pub contract: String,
+    /// Event name in the contract ABI
+    pub event: String,
+    /// Event type reported on `RollupEvent` (defaults to the ABI event name)
+    pub event_type: Option<String>,
+    /// Decoded field that becomes `batch_number`
+    pub id_field: String,
+    /// `RollupStatus` slots updated by the event
+    #[serde(default)]
+    pub slots: Vec<StatusSlot>,
+    /// Health category the event counts toward
+    #[serde(default)]
+    pub category: HealthCategory,
+    /// Whether `id_field` increases by exactly one per event (enables gap detection)
+    #[serde(default)]
+    pub sequential: bool,
+}
+
+/// A rollup built from a `RollupDefinition`, decoding logs with its runtime ABIs
+pub struct ConfiguredRollup {
+    name: String,
+    sequencer: SequencerDefinition,
+    confirmations: u64,
+    contracts: Vec<ContractDefinition>,
+    streams: Vec<ConfiguredStream>,
+}
+
+/// An event stream together with its ABI event and identifier field
+struct ConfiguredStream {
+    stream: EventStream,
+    event: abi::Event,
+    id_field: String,
+}
+
+impl ConfiguredRollup {
+    /// Build a rollup from its definition, loading ABIs relative to `base_dir`
+    pub fn from_definition(definition: RollupDefinition, base_dir: &Path) -> eyre::Result<Self> {
+        let mut abis = Vec::new();
+        for contract in &definition.contracts {
+            let path = base_dir.join(&contract.abi);
+            let file = fs::File::open(&path)
+                .map_err(|e| eyre::eyre!("Failed to open ABI {}: {}", path.display(), e))?;
+            let parsed = abi::Contract::load(file)
+                .map_err(|e| eyre::eyre!("Invalid ABI {}: {}", path.display(), e))?;
+            abis.push((contract.name.clone(), parsed));
+        }
+
+        let mut streams = Vec::new();
+        let mut stream_names = HashSet::new();
+        for event_def in &definition.events {
+            if !stream_names.insert(event_def.stream.clone()) {
+                eyre::bail!(
+                    "Rollup {} defines stream {} twice",
+                    definition.name,
+                    event_def.stream
+                );
+            }
+
+            let (_, contract_abi) = abis
+                .iter()
+                .find(|(name, _)| *name == event_def.contract)
+                .ok_or_else(|| {
+                    eyre::eyre!(
+                        "Stream {} of rollup {} references unknown contract {}",
+                        event_def.stream,
+                        definition.name,
+                        event_def.contract
+                    )
+                })?;
+
+            let event = contract_abi
+                .event(&event_def.event)
+                .map_err(|_| {
+                    eyre::eyre!(
+                        "Event {} not found in ABI of contract {}",
+                        event_def.event,
+                        event_def.contract
+                    )
+                })?
+                .clone();
+
+            if !event.inputs.iter().any(|i| i.name == event_def.id_field) {
+                eyre::bail!(
+                    "Event {} has no field {} (stream {} of rollup {})",
+                    event_def.event,
+                    event_def.id_field,
+                    event_def.stream,
+                    definition.name
+                );
+            }
+
+            streams.push(ConfiguredStream {
+                stream: EventStream {
+                    name: event_def.stream.clone(),
+                    event_type: event_def
+                        .event_type
+                        .clone()
+                        .unwrap_or_else(|| event_def.event.clone()),
+                    contract: event_def.contract.clone(),
+                    topic: event.signature(),
+                    slots: event_def.slots.clone(),
+                    category: event_def.category,
+                    sequential: event_def.sequential,
+                },
+                event,
+                id_field: event_def.id_field.clone(),
+            });
+        }
+
+        Ok(Self {
+            name: definition.name,
+            sequencer: definition.sequencer,
+            confirmations: definition.confirmations,
+            contracts: definition.contracts,
+            streams,
+        })
+    }
+}
+
+impl RollupWatcher for ConfiguredRollup {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn contracts(&self) -> eyre::Result<Vec<Contract>> {
+        self.contracts
+            .iter()
+            .map(|c| {
+                let from_env = c.address_env.as_ref().and_then(|var| env::var(var).ok());
+                let address = match (from_env, c.address) {
+                    (Some(value), _) => value.parse().map_err(|e| {
+                        eyre::eyre!(
+                            "Invalid {} address: {}",
+                            c.address_env.as_deref().unwrap_or_default(),
+                            e
+                        )
+                    })?,
+                    (None, Some(address)) => address,
+                    (None, None) => eyre::bail!(
+                        "No address for contract {} of rollup {} (set {})",
+                        c.name,
+                        self.name,
+                        c.address_env.as_deref().unwrap_or("address")
+                    ),
+                };
+                Ok(Contract {
+                    name: c.name.clone(),
+                    address,
+                })
+            })
+            .collect()
+    }
+
+    fn streams(&self) -> Vec<EventStream> {
+        self.streams.iter().map(|s| s.stream.clone()).collect()
+    }
+
+    fn decode(&self, stream: &EventStream, log: &Log) -> eyre::Result<String> {
+        let configured = self
+            .streams
+            .iter()
+            .find(|s| s.stream.name == stream.name)
+            .ok_or_else(|| eyre::eyre!("Unknown {} stream: {}", self.name, stream.name))?;
+
+        let decoded = configured.event.parse_log(RawLog::from(log.clone()))?;
+        let param = decoded
+            .params
+            .into_iter()
+            .find(|p| p.name == configured.id_field)
+            .ok_or_else(|| eyre::eyre!("Decoded log has no field {}", configured.id_field))?;
+
+        Ok(format_token(&param.value))
+    }
+
+    fn sequencer_kind(&self) -> SequencerKind {
+        self.sequencer.kind
+    }
+
+    fn confirmations(&self) -> u64 {
+        self.confirmations
+    }
+
+    fn sequencer_poll_interval(&self) -> Duration {
+        Duration::from_millis(self.sequencer.poll_ms.unwrap_or(5000))
+    }
+}
+
+/// Render a decoded ABI value as a batch/assertion identifier
+fn format_token(token: &Token) -> String {
+    match token {
+        Token::Uint(value) => value.to_string(),
+        Token::Int(value) => I256::from_raw(*value).to_string(),
+        Token::FixedBytes(bytes) | Token::Bytes(bytes) => format!("0x{}", hex::encode(bytes)),
+        Token::Address(address) => format!("{:?}", address),
+        Token::Bool(value) => value.to_string(),
+        Token::String(value) => value.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Parse rollup definitions, resolving ABI paths relative to `base_dir`
+pub fn parse_registry(contents: &str, base_dir: &Path) -> eyre::Result<RollupRegistry> {
+    let file: RollupsFile = toml::from_str(contents)?;
+    if file.rollups.is_empty() {
+        eyre::bail!("No rollups defined");
+    }
+
+    let mut names = HashSet::new();
+    let mut registry = RollupRegistry::new();
+    for definition in file.rollups {
+        if !names.insert(definition.name.clone()) {
+            eyre::bail!("Rollup {} defined twice", definition.name);
+        }
+        let name = definition.name.clone();
+        let rollup = ConfiguredRollup::from_definition(definition, base_dir)
+            .map_err(|e| eyre::eyre!("Invalid definition for rollup {}: {}", name, e))?;
+        registry = registry.with(rollup);
+    }
+
+    Ok(registry)
+}
+
+/// Load rollup definitions from a TOML file
+pub fn load_registry(path: &Path) -> eyre::Result<RollupRegistry> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| eyre::eyre!("Failed to read {}: {}", path.display(), e))?;
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    parse_registry(&contents, base_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::{H256, U256, U64};
+
+    fn repo_dir() -> &'static Path {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+    }
+
+    const ZKSYNC: &str = r#"
+        [[rollup]]
+        name = "zksync"
+
+        [[rollup.contracts]]
+        name = "diamond_proxy"
+        address = "0x32400084C286CF3E17e7B677ea9583e60a000324"
+        abi = "abi/zksync_era_diamond.json"
+
+        [[rollup.events]]
+        stream = "block_commit"
+        contract = "diamond_proxy"
+        event = "BlockCommit"
+        id_field = "batchNumber"
+        slots = ["latest_batch"]
+        category = "batch"
+    "#;
+
+    #[test]
+    fn test_parse_registry() {
+        let registry = parse_registry(ZKSYNC, repo_dir()).unwrap();
+        assert_eq!(registry.names(), vec!["zksync"]);
+
+        let zksync = registry.get("zksync").unwrap();
+        let streams = zksync.streams();
+        assert_eq!(streams.len(), 1);
+        assert_eq!(streams[0].event_type, "BlockCommit");
+        assert_eq!(streams[0].slots, vec![StatusSlot::Batch]);
+        assert_eq!(streams[0].category, HealthCategory::Batch);
+        assert_eq!(zksync.sequencer_kind(), SequencerKind::Evm);
+
+        let contracts = zksync.contracts().unwrap();
+        assert_eq!(
+            contracts[0].address,
+            "0x32400084C286CF3E17e7B677ea9583e60a000324"
+                .parse::<Address>()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_decode_indexed_field() {
+        let registry = parse_registry(ZKSYNC, repo_dir()).unwrap();
+        let zksync = registry.get("zksync").unwrap();
+        let stream = zksync.streams().remove(0);
+
+        let log = Log {
+            topics: vec![
+                stream.topic,
+                H256::from_low_u64_be(491_234),
+                H256::repeat_byte(0x11),
+                H256::repeat_byte(0x22),
+            ],
+            block_number: Some(U64::from(19_000_000)),
+            ..Default::default()
+        };
+
+        assert_eq!(zksync.decode(&stream, &log).unwrap(), "491234");
+    }
+
+    #[test]
+    fn test_unknown_event_rejected() {
+        let contents = ZKSYNC.replace("event = \"BlockCommit\"", "event = \"NoSuchEvent\"");
+        let err = parse_registry(&contents, repo_dir()).err().unwrap();
+        assert!(err.to_string().contains("NoSuchEvent"));
+    }
+
+    #[test]
+    fn test_unknown_field_rejected() {
+        let contents = ZKSYNC.replace("id_field = \"batchNumber\"", "id_field = \"nope\"");
+        let err = parse_registry(&contents, repo_dir()).err().unwrap();
+        assert!(err.to_string().contains("nope"));
+    }
+
+    #[test]
+    fn test_format_token() {
+        assert_eq!(format_token(&Token::Uint(U256::from(42))), "42");
+        assert_eq!(format_token(&Token::Int(I256::from(-5).into_raw())), "-5");
+        assert_eq!(format_token(&Token::FixedBytes(vec![0xab, 0xcd])), "0xabcd");
+    }
+
+    #[test]
+    fn test_bundled_definitions() {
+        let registry = load_registry(&repo_dir().join("rollups.toml")).unwrap();
+        assert_eq!(
+            registry.names(),
+            vec!["arbitrum", "starknet", "base", "optimism", "zksync"]
+        );
+        assert_eq!(
+            registry.get("starknet").unwrap().sequencer_kind(),
+            SequencerKind::Starknet
+        );
+
+        // Only strictly sequential batch numbers are checked for gaps
+        let sequential: Vec<String> = registry
+            .iter()
+            .flat_map(|r| {
+                r.streams()
+                    .into_iter()
+                    .filter(|s| s.sequential)
+                    .map(|s| format!("{}/{}", r.name(), s.name))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(sequential, vec!["arbitrum/batch", "zksync/block_commit"]);
+    }
+}
@@ -6,8 +6,20 @@ use reqwest::Client as HttpClient;
 use serde_json::json;
 use tokio_util::sync::CancellationToken;
 
+use crate::capture::CaptureRecord;
 use crate::health::HealthMonitor;
-use crate::types::AppState;
+use crate::types::{AppState, SequencerChange, SequencerStatus, StreamMessage, StreamNotice};
+
+/// JSON-RPC dialect spoken by an L2 chain's RPC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SequencerKind {
+    /// Standard Ethereum JSON-RPC (`eth_getBlockByNumber`)
+    #[default]
+    Evm,
+    /// Starknet JSON-RPC (`starknet_getBlockWithTxHashes`)
+    Starknet,
+}
 
 /// Configuration for an L2 chain sequencer poller
 #[derive(Debug, Clone)]
@@ -22,6 +34,175 @@ pub struct L2ChainConfig {
     pub downtime_threshold: Duration,
 }
 
+/// Latest L2 block reported by a sequencer poll
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct L2Block {
+    /// Block number, if the node reported one
+    pub number: Option<u64>,
+    /// Unix timestamp of the block
+    pub timestamp: u64,
+}
+
+/// Poll-to-poll state of one rollup's sequencer.
+///
+/// Turns each poll result into the sequencer status and health activity; shared
+/// by the pollers of every RPC dialect and by capture replays.
+#[derive(Debug, Default)]
+pub struct SequencerTracker {
+    prev_block: Option<u64>,
+    prev_poll_time: Option<u64>,
+    /// Status as of the last `SequencerChanged` notice
+    reported: Option<SequencerStatus>,
+}
+
+impl SequencerTracker {
+    /// Apply a poll result, `None` if the poll failed, at the health monitor's
+    /// current time
+    pub fn apply(
+        &mut self,
+        rollup: &str,
+        block: Option<L2Block>,
+        downtime_threshold: Duration,
+        state: &AppState,
+        health: &HealthMonitor,
+    ) {
+        state.record(|| CaptureRecord::L2Poll {
+            rollup: rollup.to_string(),
+            block,
+        });
+        let now = health.now();
+
+        let Some(block) = block else {
+            // A failed poll hides the chain rather than showing it stalled: the
+            // sequencer only counts as down once the last block seen is too old
+            let last_block = state.get_sequencer_status(rollup).latest_block_timestamp;
+            let stalled_secs = last_block
+                .map(|t| now.saturating_sub(t))
+                .filter(|secs| *secs >= downtime_threshold.as_secs());
+            state.update_sequencer_status(rollup, |s| {
+                if stalled_secs.is_some() {
+                    s.is_producing = false;
+                }
+                s.last_polled = Some(now);
+            });
+            if let Some(secs) = stalled_secs {
+                health.record_sequencer_downtime(rollup, secs);
+                let reason = format!(
+                    "Sequencer RPC poll failed, no L2 block seen for {} seconds (threshold: {})",
+                    secs,
+                    downtime_threshold.as_secs()
+                );
+                self.notify_change(rollup, state, now, reason);
+            }
+            return;
+        };
+
+        // Calculate blocks per second from previous poll
+        let blocks_per_second = match (block.number, self.prev_block, self.prev_poll_time) {
+            (Some(current), Some(previous), Some(prev_time))
+                if current > previous && now > prev_time =>
+            {
+                let block_delta = (current - previous) as f64;
+                let time_delta = (now - prev_time) as f64;
+                Some(block_delta / time_delta)
+            }
+            _ => None,
+        };
+
+        // Detect downtime: now - block_timestamp > threshold
+        let seconds_since_last_block = now.saturating_sub(block.timestamp);
+        let is_producing = seconds_since_last_block < downtime_threshold.as_secs();
+
+        state.update_sequencer_status(rollup, |s| {
+            s.latest_block = block.number;
+            s.latest_block_timestamp = Some(block.timestamp);
+            if let Some(bps) = blocks_per_second {
+                s.blocks_per_second = Some(bps);
+            }
+            s.is_producing = is_producing;
+            s.seconds_since_last_block = Some(seconds_since_last_block);
+            s.last_polled = Some(now);
+        });
+
+        if is_producing {
+            health.record_sequencer_activity(rollup);
+        } else {
+            health.record_sequencer_downtime(rollup, seconds_since_last_block);
+        }
+        let reason = if is_producing {
+            format!(
+                "Producing blocks, latest {} seconds ago",
+                seconds_since_last_block
+            )
+        } else {
+            format!(
+                "No new L2 block for {} seconds (threshold: {})",
+                seconds_since_last_block,
+                downtime_threshold.as_secs()
+            )
+        };
+        self.notify_change(rollup, state, now, reason);
+
+        tracing::debug!(
+            rollup = rollup,
+            block = ?block.number,
+            timestamp = block.timestamp,
+            bps = ?blocks_per_second,
+            producing = is_producing,
+            "L2 sequencer poll"
+        );
+
+        if let Some(number) = block.number {
+            self.prev_block = Some(number);
+        }
+        self.prev_poll_time = Some(now);
+    }
+
+    /// Publish a `SequencerChanged` notice on the first poll and whenever the
+    /// sequencer starts or stops producing
+    fn notify_change(&mut self, rollup: &str, state: &AppState, now: u64, reason: String) {
+        let status = state.get_sequencer_status(rollup);
+        if self
+            .reported
+            .as_ref()
+            .is_some_and(|reported| reported.is_producing == status.is_producing)
+        {
+            return;
+        }
+        let previous = self.reported.replace(status.clone()).unwrap_or_default();
+        state.publish(StreamMessage::Notice(StreamNotice::SequencerChanged(
+            SequencerChange {
+                rollup: rollup.to_string(),
+                previous,
+                status,
+                reasons: vec![reason],
+                at: now,
+            },
+        )));
+    }
+}
+
+/// Start the sequencer poller matching the chain's RPC dialect
+pub async fn start_poller(
+    kind: SequencerKind,
+    config: L2ChainConfig,
+    state: AppState,
+    health: HealthMonitor,
+    cancel_token: CancellationToken,
+) {
+    match kind {
+        SequencerKind::Evm => start_sequencer_poller(config, state, health, cancel_token).await,
+        SequencerKind::Starknet => {
+            let starknet_config = StarknetChainConfig {
+                rpc_url: config.rpc_url,
+                poll_interval: config.poll_interval,
+                downtime_threshold: config.downtime_threshold,
+            };
+            start_starknet_sequencer_poller(starknet_config, state, health, cancel_token).await
+        }
+    }
+}
+
 /// Start polling an L2 chain's sequencer for latest block info.
 ///
 /// Updates `AppState` sequencer status and records activity/downtime on `HealthMonitor`.
@@ -52,8 +233,7 @@ pub async fn start_sequencer_poller(
     );
 
     let mut interval = tokio::time::interval(config.poll_interval);
-    let mut prev_block: Option<u64> = None;
-    let mut prev_poll_time: Option<u64> = None;
+    let mut tracker = SequencerTracker::default();
 
     loop {
         tokio::select! {
@@ -64,74 +244,17 @@ pub async fn start_sequencer_poller(
             }
         }
 
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
-
-        match provider.get_block(BlockNumber::Latest).await {
-            Ok(Some(block)) => {
-                let block_number = block.number.map(|n| n.as_u64());
-                let block_timestamp = block.timestamp.as_u64();
-
-                // Calculate blocks per second from previous poll
-                let blocks_per_second = match (block_number, prev_block, prev_poll_time) {
-                    (Some(current), Some(previous), Some(prev_time))
-                        if current > previous && now > prev_time =>
-                    {
-                        let block_delta = (current - previous) as f64;
-                        let time_delta = (now - prev_time) as f64;
-                        Some(block_delta / time_delta)
-                    }
-                    _ => None,
-                };
-
-                // Detect downtime: now - block_timestamp > threshold
-                let seconds_since_last_block = now.saturating_sub(block_timestamp);
-                let is_producing = seconds_since_last_block < config.downtime_threshold.as_secs();
-
-                state.update_sequencer_status(&config.name, |s| {
-                    s.latest_block = block_number;
-                    s.latest_block_timestamp = Some(block_timestamp);
-                    if let Some(bps) = blocks_per_second {
-                        s.blocks_per_second = Some(bps);
-                    }
-                    s.is_producing = is_producing;
-                    s.seconds_since_last_block = Some(seconds_since_last_block);
-                    s.last_polled = Some(now);
-                });
-
-                if is_producing {
-                    health.record_sequencer_activity(&config.name);
-                } else {
-                    health.record_sequencer_downtime(&config.name, seconds_since_last_block);
-                }
-
-                tracing::debug!(
-                    rollup = %config.name,
-                    block = ?block_number,
-                    timestamp = block_timestamp,
-                    bps = ?blocks_per_second,
-                    producing = is_producing,
-                    "L2 sequencer poll"
-                );
-
-                if let Some(bn) = block_number {
-                    prev_block = Some(bn);
-                }
-                prev_poll_time = Some(now);
-            }
+        let block = match provider.get_block(BlockNumber::Latest).await {
+            Ok(Some(block)) => Some(L2Block {
+                number: block.number.map(|n| n.as_u64()),
+                timestamp: block.timestamp.as_u64(),
+            }),
             Ok(None) => {
                 tracing::warn!(
                     rollup = %config.name,
                     "L2 latest block returned None"
                 );
-
-                state.update_sequencer_status(&config.name, |s| {
-                    s.is_producing = false;
-                    s.last_polled = Some(now);
-                });
-                health.record_sequencer_downtime(&config.name, 0);
+                None
             }
             Err(e) => {
                 tracing::warn!(
@@ -139,14 +262,16 @@ pub async fn start_sequencer_poller(
                     error = ?e,
                     "Failed to fetch L2 latest block"
                 );
-
-                state.update_sequencer_status(&config.name, |s| {
-                    s.is_producing = false;
-                    s.last_polled = Some(now);
-                });
-                health.record_sequencer_downtime(&config.name, 0);
+                None
             }
-        }
+        };
+        tracker.apply(
+            &config.name,
+            block,
+            config.downtime_threshold,
+            &state,
+            &health,
+        );
     }
 }
 
@@ -181,8 +306,7 @@ pub async fn start_starknet_sequencer_poller(
     );
 
     let mut interval = tokio::time::interval(config.poll_interval);
-    let mut prev_block: Option<u64> = None;
-    let mut prev_poll_time: Option<u64> = None;
+    let mut tracker = SequencerTracker::default();
 
     loop {
         tokio::select! {
@@ -193,11 +317,6 @@ pub async fn start_starknet_sequencer_poller(
             }
         }
 
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
-
         let body = json!({
             "jsonrpc": "2.0",
             "method": "starknet_getBlockWithTxHashes",
@@ -205,89 +324,18 @@ pub async fn start_starknet_sequencer_poller(
             "id": 1
         });
 
-        match client.post(&config.rpc_url).json(&body).send().await {
+        let block = match client.post(&config.rpc_url).json(&body).send().await {
             Ok(resp) => match resp.json::<serde_json::Value>().await {
                 Ok(json) => {
-                    let result = &json["result"];
-
-                    // Parse block number (hex string in Starknet)
-                    let block_number = result["block_number"]
-                        .as_u64()
-                        .or_else(|| {
-                            result["block_number"]
-                                .as_str()
-                                .and_then(|s| u64::from_str_radix(s.trim_start_matches("0x"), 16).ok())
-                        });
-
-                    // Parse timestamp (unix seconds)
-                    let block_timestamp = result["timestamp"]
-                        .as_u64()
-                        .or_else(|| {
-                            result["timestamp"]
-                                .as_str()
-                                .and_then(|s| u64::from_str_radix(s.trim_start_matches("0x"), 16).ok())
-                        });
-
-                    if block_number.is_none() {
+                    let block = parse_starknet_block(&json["result"], health.now());
+                    if block.is_none() {
                         tracing::warn!(
                             rollup = "starknet",
                             response = %json,
                             "Could not parse Starknet block"
                         );
-                        state.update_sequencer_status("starknet", |s| {
-                            s.is_producing = false;
-                            s.last_polled = Some(now);
-                        });
-                        health.record_sequencer_downtime("starknet", 0);
-                        continue;
                     }
-
-                    let bn = block_number.unwrap();
-                    let ts = block_timestamp.unwrap_or(now);
-
-                    let blocks_per_second = match (prev_block, prev_poll_time) {
-                        (Some(previous), Some(prev_time))
-                            if bn > previous && now > prev_time =>
-                        {
-                            let block_delta = (bn - previous) as f64;
-                            let time_delta = (now - prev_time) as f64;
-                            Some(block_delta / time_delta)
-                        }
-                        _ => None,
-                    };
-
-                    let seconds_since_last_block = now.saturating_sub(ts);
-                    let is_producing =
-                        seconds_since_last_block < config.downtime_threshold.as_secs();
-
-                    state.update_sequencer_status("starknet", |s| {
-                        s.latest_block = Some(bn);
-                        s.latest_block_timestamp = Some(ts);
-                        if let Some(bps) = blocks_per_second {
-                            s.blocks_per_second = Some(bps);
-                        }
-                        s.is_producing = is_producing;
-                        s.seconds_since_last_block = Some(seconds_since_last_block);
-                        s.last_polled = Some(now);
-                    });
-
-                    if is_producing {
-                        health.record_sequencer_activity("starknet");
-                    } else {
-                        health.record_sequencer_downtime("starknet", seconds_since_last_block);
-                    }
-
-                    tracing::debug!(
-                        rollup = "starknet",
-                        block = bn,
-                        timestamp = ts,
-                        bps = ?blocks_per_second,
-                        producing = is_producing,
-                        "Starknet sequencer poll"
-                    );
-
-                    prev_block = Some(bn);
-                    prev_poll_time = Some(now);
+                    block
                 }
                 Err(e) => {
                     tracing::warn!(
@@ -295,11 +343,7 @@ pub async fn start_starknet_sequencer_poller(
                         error = ?e,
                         "Failed to parse Starknet RPC response"
                     );
-                    state.update_sequencer_status("starknet", |s| {
-                        s.is_producing = false;
-                        s.last_polled = Some(now);
-                    });
-                    health.record_sequencer_downtime("starknet", 0);
+                    None
                 }
             },
             Err(e) => {
@@ -308,12 +352,33 @@ pub async fn start_starknet_sequencer_poller(
                     error = ?e,
                     "Failed to reach Starknet RPC"
                 );
-                state.update_sequencer_status("starknet", |s| {
-                    s.is_producing = false;
-                    s.last_polled = Some(now);
-                });
-                health.record_sequencer_downtime("starknet", 0);
+                None
             }
-        }
+        };
+        tracker.apply(
+            "starknet",
+            block,
+            config.downtime_threshold,
+            &state,
+            &health,
+        );
     }
 }
+
+/// Parse a `starknet_getBlockWithTxHashes` result; numbers may be integers or
+/// hex strings. A missing timestamp defaults to `now`.
+fn parse_starknet_block(result: &serde_json::Value, now: u64) -> Option<L2Block> {
+    let parse = |value: &serde_json::Value| {
+        value.as_u64().or_else(|| {
+            value
+                .as_str()
+                .and_then(|s| u64::from_str_radix(s.trim_start_matches("0x"), 16).ok())
+        })
+    };
+
+    let number = parse(&result["block_number"])?;
+    Some(L2Block {
+        number: Some(number),
+        timestamp: parse(&result["timestamp"]).unwrap_or(now),
+    })
+}
@@ -0,0 +1,1045 @@
+use crate::alerts::{AlertDelivery, OpenAlert};
+use crate::health::{HealthStage, RollupHealthState};
+use crate::incidents::Incident;
+use crate::maintenance::MaintenanceWindow;
+use crate::types::{EventStatus, HealthStatus, RollupEvent, RollupStatus, SequencerStatus};
+use crate::uptime::StatusTimeline;
+use rusqlite::{params, Connection, ToSql};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Schema migrations, applied in order; `PRAGMA user_version` records how many ran
+const MIGRATIONS: &[&str] = &[
+    "
+CREATE TABLE IF NOT EXISTS events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    rollup TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    block_number INTEGER NOT NULL,
+    tx_hash TEXT NOT NULL,
+    batch_number TEXT,
+    timestamp INTEGER,
+    UNIQUE (rollup, event_type, tx_hash, batch_number)
+);
+CREATE INDEX IF NOT EXISTS events_rollup_block ON events (rollup, block_number);
+
+CREATE TABLE IF NOT EXISTS status_snapshots (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    rollup TEXT NOT NULL,
+    taken_at INTEGER NOT NULL,
+    status TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS status_snapshots_rollup ON status_snapshots (rollup, id);
+
+CREATE TABLE IF NOT EXISTS sequencer_statuses (
+    rollup TEXT PRIMARY KEY,
+    status TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS health_states (
+    rollup TEXT PRIMARY KEY,
+    state TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS health_transitions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    rollup TEXT NOT NULL,
+    from_status TEXT NOT NULL,
+    to_status TEXT NOT NULL,
+    at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS health_transitions_rollup ON health_transitions (rollup, at);
+
+CREATE TABLE IF NOT EXISTS stream_cursors (
+    stream TEXT PRIMARY KEY,
+    block_number INTEGER NOT NULL
+);
+",
+    "ALTER TABLE events ADD COLUMN status TEXT NOT NULL DEFAULT 'confirmed';",
+    "ALTER TABLE events ADD COLUMN observed_at INTEGER;",
+    "
+CREATE TABLE IF NOT EXISTS incidents (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    rollup TEXT NOT NULL,
+    started_at INTEGER NOT NULL,
+    ended_at INTEGER,
+    stage TEXT,
+    peak TEXT NOT NULL,
+    issues TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS incidents_rollup ON incidents (rollup, started_at);
+CREATE INDEX IF NOT EXISTS incidents_ended ON incidents (ended_at);
+",
+    "
+CREATE TABLE IF NOT EXISTS stage_transitions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    rollup TEXT NOT NULL,
+    stage TEXT NOT NULL,
+    from_status TEXT NOT NULL,
+    to_status TEXT NOT NULL,
+    at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS stage_transitions_rollup ON stage_transitions (rollup, stage, at);
+",
+    "
+CREATE TABLE IF NOT EXISTS maintenance_windows (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    rollup TEXT NOT NULL,
+    stage TEXT,
+    starts_at INTEGER NOT NULL,
+    ends_at INTEGER NOT NULL,
+    reason TEXT
+);
+CREATE INDEX IF NOT EXISTS maintenance_windows_ends ON maintenance_windows (ends_at);
+",
+    "
+CREATE TABLE IF NOT EXISTS alert_deliveries (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    target TEXT NOT NULL,
+    rollup TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    state TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    summary TEXT NOT NULL,
+    attempts INTEGER NOT NULL,
+    delivered INTEGER NOT NULL,
+    status_code INTEGER,
+    error TEXT,
+    at INTEGER NOT NULL,
+    finished_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS alert_deliveries_rollup ON alert_deliveries (rollup, id);
+",
+    "
+CREATE TABLE IF NOT EXISTS open_alerts (
+    key TEXT PRIMARY KEY,
+    alert TEXT NOT NULL
+);
+",
+    "DELETE FROM status_snapshots WHERE id NOT IN (SELECT MAX(id) FROM status_snapshots GROUP BY rollup);",
+    "ALTER TABLE incidents ADD COLUMN resolution TEXT;",
+];
+
+/// A recorded change of a rollup's health status
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct HealthTransition {
+    pub rollup: String,
+    pub from: HealthStatus,
+    pub to: HealthStatus,
+    /// Unix timestamp of the transition
+    pub at: u64,
+}
+
+/// A recorded change of one pipeline stage's health status
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StageTransition {
+    pub rollup: String,
+    pub stage: HealthStage,
+    pub from: HealthStatus,
+    pub to: HealthStatus,
+    /// Unix timestamp of the transition
+    pub at: u64,
+}
+
+/// Embedded SQLite store for events, status snapshots, health history and
+/// backfill cursors.
+///
+/// Cloning is cheap; all clones share one connection.
+#[derive(Clone)]
+pub struct Store {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl Store {
+    /// Open (or create) a database file and apply the schema
+    pub fn open(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        Self::init(conn)
+    }
+
+    /// Open a private in-memory database (used in tests)
+    pub fn open_in_memory() -> eyre::Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(mut conn: Connection) -> eyre::Result<Self> {
+        let applied: usize = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        for (version, migration) in MIGRATIONS.iter().enumerate().skip(applied) {
+            let tx = conn.transaction()?;
+            tx.execute_batch(migration)?;
+            tx.pragma_update(None, "user_version", version + 1)?;
+            tx.commit()?;
+        }
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+        })
+    }
+
+    fn conn(&self) -> MutexGuard<'_, Connection> {
+        match self.conn.lock() {
+            Ok(conn) => conn,
+            Err(poisoned) => {
+                tracing::error!("Mutex poisoned in Store, recovering");
+                poisoned.into_inner()
+            }
+        }
+    }
+
+    /// Persist an event; returns false if it was already stored
+    pub fn insert_event(&self, event: &RollupEvent) -> eyre::Result<bool> {
+        let inserted = self.conn().execute(
+            "INSERT OR IGNORE INTO events
+                (rollup, event_type, block_number, tx_hash, batch_number, timestamp, status,
+                 observed_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                event.rollup,
+                event.event_type,
+                event.block_number as i64,
+                event.tx_hash,
+                event.batch_number,
+                event.timestamp.map(|t| t as i64),
+                event.status.as_str(),
+                event.observed_at.map(|t| t as i64),
+            ],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    /// Update the confirmation status of a stored event
+    pub fn set_event_status(&self, event: &RollupEvent, status: EventStatus) -> eyre::Result<()> {
+        self.conn().execute(
+            "UPDATE events SET status = ?5
+             WHERE rollup = ?1 AND event_type = ?2 AND tx_hash = ?3 AND batch_number IS ?4",
+            params![
+                event.rollup,
+                event.event_type,
+                event.tx_hash,
+                event.batch_number,
+                status.as_str(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Delete a stored event (its log was removed by a reorg)
+    pub fn delete_event(&self, event: &RollupEvent) -> eyre::Result<()> {
+        self.conn().execute(
+            "DELETE FROM events
+             WHERE rollup = ?1 AND event_type = ?2 AND tx_hash = ?3 AND batch_number IS ?4",
+            params![
+                event.rollup,
+                event.event_type,
+                event.tx_hash,
+                event.batch_number
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Most recent events of a rollup, newest first, optionally only those
+    /// emitted before an L1 block
+    pub fn events(
+        &self,
+        rollup: &str,
+        before_block: Option<u64>,
+        limit: usize,
+    ) -> eyre::Result<Vec<RollupEvent>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT rollup, event_type, block_number, tx_hash, batch_number, timestamp, status,
+                    observed_at
+             FROM events
+             WHERE rollup = ?1 AND block_number < ?2
+             ORDER BY block_number DESC, id DESC
+             LIMIT ?3",
+        )?;
+        let rows = stmt.query_map(
+            params![
+                rollup,
+                before_block.map_or(i64::MAX, |b| b as i64),
+                limit as i64
+            ],
+            |row| {
+                Ok(RollupEvent {
+                    rollup: row.get(0)?,
+                    event_type: row.get(1)?,
+                    block_number: row.get::<_, i64>(2)? as u64,
+                    tx_hash: row.get(3)?,
+                    batch_number: row.get(4)?,
+                    timestamp: row.get::<_, Option<i64>>(5)?.map(|t| t as u64),
+                    status: match row.get::<_, String>(6)?.as_str() {
+                        "pending" => EventStatus::Pending,
+                        _ => EventStatus::Confirmed,
+                    },
+                    observed_at: row.get::<_, Option<i64>>(7)?.map(|t| t as u64),
+                })
+            },
+        )?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Save a snapshot of a rollup's status, dropping the ones it supersedes
+    pub fn save_status(&self, rollup: &str, status: &RollupStatus, at: u64) -> eyre::Result<()> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT INTO status_snapshots (rollup, taken_at, status) VALUES (?1, ?2, ?3)",
+            params![rollup, at as i64, serde_json::to_string(status)?],
+        )?;
+        tx.execute(
+            "DELETE FROM status_snapshots WHERE rollup = ?1 AND id < ?2",
+            params![rollup, tx.last_insert_rowid()],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Latest status snapshot of every rollup
+    pub fn latest_statuses(&self) -> eyre::Result<HashMap<String, RollupStatus>> {
+        self.load_json(
+            "SELECT rollup, status FROM status_snapshots
+             WHERE id IN (SELECT MAX(id) FROM status_snapshots GROUP BY rollup)",
+        )
+    }
+
+    /// Replace the stored sequencer status of a rollup
+    pub fn save_sequencer_status(
+        &self,
+        rollup: &str,
+        status: &SequencerStatus,
+    ) -> eyre::Result<()> {
+        self.conn().execute(
+            "INSERT OR REPLACE INTO sequencer_statuses (rollup, status) VALUES (?1, ?2)",
+            params![rollup, serde_json::to_string(status)?],
+        )?;
+        Ok(())
+    }
+
+    /// Stored sequencer status of every rollup
+    pub fn sequencer_statuses(&self) -> eyre::Result<HashMap<String, SequencerStatus>> {
+        self.load_json("SELECT rollup, status FROM sequencer_statuses")
+    }
+
+    /// Replace the stored health state of a rollup
+    pub fn save_health_state(&self, rollup: &str, state: &RollupHealthState) -> eyre::Result<()> {
+        self.conn().execute(
+            "INSERT OR REPLACE INTO health_states (rollup, state) VALUES (?1, ?2)",
+            params![rollup, serde_json::to_string(state)?],
+        )?;
+        Ok(())
+    }
+
+    /// Stored health state of every rollup
+    pub fn health_states(&self) -> eyre::Result<HashMap<String, RollupHealthState>> {
+        self.load_json("SELECT rollup, state FROM health_states")
+    }
+
+    /// Record a health status transition
+    pub fn insert_transition(&self, transition: &HealthTransition) -> eyre::Result<()> {
+        self.conn().execute(
+            "INSERT INTO health_transitions (rollup, from_status, to_status, at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                transition.rollup,
+                serde_json::to_string(&transition.from)?,
+                serde_json::to_string(&transition.to)?,
+                transition.at as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Record a stage health status transition
+    pub fn insert_stage_transition(&self, transition: &StageTransition) -> eyre::Result<()> {
+        self.conn().execute(
+            "INSERT INTO stage_transitions (rollup, stage, from_status, to_status, at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                transition.rollup,
+                serde_json::to_string(&transition.stage)?,
+                serde_json::to_string(&transition.from)?,
+                serde_json::to_string(&transition.to)?,
+                transition.at as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Status of a rollup, or of one of its stages, at `from` and every change
+    /// after it until `to`
+    pub fn status_timeline(
+        &self,
+        rollup: &str,
+        stage: Option<HealthStage>,
+        from: u64,
+        to: u64,
+    ) -> eyre::Result<StatusTimeline> {
+        let (table, filter) = match stage {
+            Some(_) => ("stage_transitions", "AND stage = :stage"),
+            None => ("health_transitions", ""),
+        };
+        let stage = stage.map(|s| serde_json::to_string(&s)).transpose()?;
+        let (from, to) = (from as i64, to as i64);
+        let mut before: Vec<(&str, &dyn ToSql)> = vec![(":rollup", &rollup), (":from", &from)];
+        if let Some(stage) = &stage {
+            before.push((":stage", stage));
+        }
+        let mut within = before.clone();
+        within.push((":to", &to));
+
+        let conn = self.conn();
+        let initial: Option<String> = conn
+            .prepare(&format!(
+                "SELECT to_status FROM {table}
+                 WHERE rollup = :rollup AND at < :from {filter}
+                 ORDER BY at DESC, id DESC
+                 LIMIT 1"
+            ))?
+            .query_map(&*before, |row| row.get(0))?
+            .next()
+            .transpose()?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT at, to_status FROM {table}
+             WHERE rollup = :rollup AND at >= :from AND at <= :to {filter}
+             ORDER BY at, id"
+        ))?;
+        let rows = stmt.query_map(&*within, |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?;
+        let mut changes = Vec::new();
+        for row in rows {
+            let (at, status) = row?;
+            changes.push((at as u64, serde_json::from_str(&status)?));
+        }
+
+        Ok(StatusTimeline {
+            initial: initial.map(|s| serde_json::from_str(&s)).transpose()?,
+            changes,
+        })
+    }
+
+    /// Most recent health transitions of a rollup, newest first
+    pub fn transitions(&self, rollup: &str, limit: usize) -> eyre::Result<Vec<HealthTransition>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT rollup, from_status, to_status, at FROM health_transitions
+             WHERE rollup = ?1
+             ORDER BY at DESC, id DESC
+             LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![rollup, limit as i64], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, i64>(3)?,
+            ))
+        })?;
+
+        let mut transitions = Vec::new();
+        for row in rows {
+            let (rollup, from, to, at) = row?;
+            transitions.push(HealthTransition {
+                rollup,
+                from: serde_json::from_str(&from)?,
+                to: serde_json::from_str(&to)?,
+                at: at as u64,
+            });
+        }
+        Ok(transitions)
+    }
+
+    /// Insert or update an incident; returns its row id
+    pub fn save_incident(&self, incident: &Incident) -> eyre::Result<u64> {
+        let conn = self.conn();
+        let stage = incident
+            .stage
+            .map(|s| serde_json::to_string(&s))
+            .transpose()?;
+        let peak = serde_json::to_string(&incident.peak)?;
+        let issues = serde_json::to_string(&incident.issues)?;
+        let ended_at = incident.ended_at.map(|t| t as i64);
+        let resolution = incident
+            .resolution
+            .map(|r| serde_json::to_string(&r))
+            .transpose()?;
+
+        if let Some(id) = incident.id {
+            conn.execute(
+                "UPDATE incidents SET ended_at = ?1, stage = ?2, peak = ?3, issues = ?4,
+                    resolution = ?5
+                 WHERE id = ?6",
+                params![ended_at, stage, peak, issues, resolution, id as i64],
+            )?;
+            return Ok(id);
+        }
+        conn.execute(
+            "INSERT INTO incidents (rollup, started_at, ended_at, stage, peak, issues, resolution)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                incident.rollup,
+                incident.started_at as i64,
+                ended_at,
+                stage,
+                peak,
+                issues,
+                resolution
+            ],
+        )?;
+        Ok(conn.last_insert_rowid() as u64)
+    }
+
+    /// Most recent incidents first, of one rollup or all; `since` keeps those
+    /// still open or resolved at or after that unix timestamp
+    pub fn incidents(
+        &self,
+        rollup: Option<&str>,
+        since: Option<u64>,
+        limit: usize,
+    ) -> eyre::Result<Vec<Incident>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, rollup, started_at, ended_at, stage, peak, issues, resolution
+             FROM incidents
+             WHERE (?1 IS NULL OR rollup = ?1)
+               AND (?2 IS NULL OR ended_at IS NULL OR ended_at >= ?2)
+             ORDER BY started_at DESC, id DESC
+             LIMIT ?3",
+        )?;
+        let rows = stmt.query_map(
+            params![rollup, since.map(|t| t as i64), limit as i64],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                    row.get::<_, Option<i64>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                    row.get::<_, String>(5)?,
+                    row.get::<_, String>(6)?,
+                    row.get::<_, Option<String>>(7)?,
+                ))
+            },
+        )?;
+
+        let mut incidents = Vec::new();
+        for row in rows {
+            let (id, rollup, started_at, ended_at, stage, peak, issues, resolution) = row?;
+            incidents.push(Incident {
+                id: Some(id as u64),
+                rollup,
+                started_at: started_at as u64,
+                ended_at: ended_at.map(|t| t as u64),
+                stage: stage.map(|s| serde_json::from_str(&s)).transpose()?,
+                peak: serde_json::from_str(&peak)?,
+                issues: serde_json::from_str(&issues)?,
+                resolution: resolution.map(|r| serde_json::from_str(&r)).transpose()?,
+            });
+        }
+        Ok(incidents)
+    }
+
+    /// Record a declared maintenance window; returns its row id
+    pub fn insert_maintenance(&self, window: &MaintenanceWindow) -> eyre::Result<u64> {
+        let conn = self.conn();
+        let stage = window
+            .stage
+            .map(|s| serde_json::to_string(&s))
+            .transpose()?;
+        conn.execute(
+            "INSERT INTO maintenance_windows (rollup, stage, starts_at, ends_at, reason)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                window.rollup,
+                stage,
+                window.starts_at as i64,
+                window.ends_at as i64,
+                window.reason
+            ],
+        )?;
+        Ok(conn.last_insert_rowid() as u64)
+    }
+
+    /// Declared maintenance windows still open after `after`, earliest first
+    pub fn maintenance_windows(&self, after: u64) -> eyre::Result<Vec<MaintenanceWindow>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, rollup, stage, starts_at, ends_at, reason FROM maintenance_windows
+             WHERE ends_at > ?1
+             ORDER BY starts_at, id",
+        )?;
+        let rows = stmt.query_map(params![after as i64], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, i64>(3)?,
+                row.get::<_, i64>(4)?,
+                row.get::<_, Option<String>>(5)?,
+            ))
+        })?;
+
+        let mut windows = Vec::new();
+        for row in rows {
+            let (id, rollup, stage, starts_at, ends_at, reason) = row?;
+            windows.push(MaintenanceWindow {
+                id: Some(id as u64),
+                rollup,
+                stage: stage.map(|s| serde_json::from_str(&s)).transpose()?,
+                starts_at: starts_at as u64,
+                ends_at: ends_at as u64,
+                reason,
+            });
+        }
+        Ok(windows)
+    }
+
+    /// Delete a declared maintenance window; returns whether it existed
+    pub fn delete_maintenance(&self, id: u64) -> eyre::Result<bool> {
+        let deleted = self.conn().execute(
+            "DELETE FROM maintenance_windows WHERE id = ?1",
+            params![id as i64],
+        )?;
+        Ok(deleted > 0)
+    }
+
+    /// Record the outcome of an alert delivery; returns its row id
+    pub fn insert_delivery(&self, delivery: &AlertDelivery) -> eyre::Result<u64> {
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO alert_deliveries (target, rollup, kind, state, severity, summary,
+                 attempts, delivered, status_code, error, at, finished_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                delivery.target,
+                delivery.rollup,
+                serde_json::to_string(&delivery.kind)?,
+                serde_json::to_string(&delivery.state)?,
+                serde_json::to_string(&delivery.severity)?,
+                delivery.summary,
+                delivery.attempts,
+                delivery.delivered,
+                delivery.status_code,
+                delivery.error,
+                delivery.at as i64,
+                delivery.finished_at as i64,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid() as u64)
+    }
+
+    /// Most recent alert deliveries first, of one rollup or all
+    pub fn deliveries(
+        &self,
+        rollup: Option<&str>,
+        limit: usize,
+    ) -> eyre::Result<Vec<AlertDelivery>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, target, rollup, kind, state, severity, summary, attempts, delivered,
+                    status_code, error, at, finished_at
+             FROM alert_deliveries
+             WHERE (?1 IS NULL OR rollup = ?1)
+             ORDER BY id DESC
+             LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![rollup, limit as i64], |row| {
+            Ok((
+                (
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, String>(5)?,
+                    row.get::<_, String>(6)?,
+                ),
+                (
+                    row.get::<_, u32>(7)?,
+                    row.get::<_, bool>(8)?,
+                    row.get::<_, Option<u16>>(9)?,
+                    row.get::<_, Option<String>>(10)?,
+                    row.get::<_, i64>(11)?,
+                    row.get::<_, i64>(12)?,
+                ),
+            ))
+        })?;
+
+        let mut deliveries = Vec::new();
+        for row in rows {
+            let (
+                (id, target, rollup, kind, state, severity, summary),
+                (attempts, delivered, status_code, error, at, finished_at),
+            ) = row?;
+            deliveries.push(AlertDelivery {
+                id: Some(id as u64),
+                target,
+                rollup,
+                kind: serde_json::from_str(&kind)?,
+                state: serde_json::from_str(&state)?,
+                severity: serde_json::from_str(&severity)?,
+                summary,
+                attempts,
+                delivered,
+                status_code,
+                error,
+                at: at as u64,
+                finished_at: finished_at as u64,
+            });
+        }
+        Ok(deliveries)
+    }
+
+    /// Replace the stored state of an open alert
+    pub fn save_open_alert(&self, key: &str, alert: &OpenAlert) -> eyre::Result<()> {
+        self.conn().execute(
+            "INSERT OR REPLACE INTO open_alerts (key, alert) VALUES (?1, ?2)",
+            params![key, serde_json::to_string(alert)?],
+        )?;
+        Ok(())
+    }
+
+    /// Forget an alert once resolved
+    pub fn delete_open_alert(&self, key: &str) -> eyre::Result<()> {
+        self.conn()
+            .execute("DELETE FROM open_alerts WHERE key = ?1", params![key])?;
+        Ok(())
+    }
+
+    /// Stored open alerts, keyed as saved
+    pub fn open_alerts(&self) -> eyre::Result<HashMap<String, OpenAlert>> {
+        self.load_json("SELECT key, alert FROM open_alerts")
+    }
+
+    /// Save backfill cursors (last processed L1 block per stream)
+    pub fn save_cursors(&self, cursors: &HashMap<String, u64>) -> eyre::Result<()> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        for (stream, block) in cursors {
+            tx.execute(
+                "INSERT OR REPLACE INTO stream_cursors (stream, block_number) VALUES (?1, ?2)",
+                params![stream, *block as i64],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Saved backfill cursors of every stream
+    pub fn cursors(&self) -> eyre::Result<HashMap<String, u64>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare("SELECT stream, block_number FROM stream_cursors")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as u64))
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Load `(rollup, json)` rows into a map, skipping rows that no longer parse
+    fn load_json<T: serde::de::DeserializeOwned>(
+        &self,
+        sql: &str,
+    ) -> eyre::Result<HashMap<String, T>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(sql)?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        let mut values = HashMap::new();
+        for row in rows {
+            let (rollup, json) = row?;
+            match serde_json::from_str(&json) {
+                Ok(value) => {
+                    values.insert(rollup, value);
+                }
+                Err(e) => {
+                    tracing::warn!(rollup = %rollup, error = ?e, "Skipping unreadable stored row")
+                }
+            }
+        }
+        Ok(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::health::HealthStage;
+    use crate::incidents::IncidentResolution;
+
+    fn event(block: u64, batch: &str) -> RollupEvent {
+        RollupEvent {
+            rollup: "arbitrum".to_string(),
+            event_type: "BatchDelivered".to_string(),
+            block_number: block,
+            tx_hash: format!("0x{:064x}", block),
+            batch_number: Some(batch.to_string()),
+            timestamp: Some(1_700_000_000 + block),
+            observed_at: Some(1_700_000_012 + block),
+            status: EventStatus::Confirmed,
+        }
+    }
+
+    #[test]
+    fn test_events_roundtrip_and_dedup() {
+        let store = Store::open_in_memory().unwrap();
+        assert!(store.insert_event(&event(100, "1")).unwrap());
+        assert!(store.insert_event(&event(101, "2")).unwrap());
+        assert!(!store.insert_event(&event(100, "1")).unwrap());
+
+        let events = store.events("arbitrum", None, 10).unwrap();
+        assert_eq!(events, vec![event(101, "2"), event(100, "1")]);
+        assert_eq!(
+            store.events("arbitrum", Some(101), 10).unwrap(),
+            vec![event(100, "1")]
+        );
+        assert!(store.events("base", None, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_event_status_and_delete() {
+        let store = Store::open_in_memory().unwrap();
+        let mut pending = event(100, "1");
+        pending.status = EventStatus::Pending;
+        store.insert_event(&pending).unwrap();
+
+        store
+            .set_event_status(&pending, EventStatus::Confirmed)
+            .unwrap();
+        assert_eq!(
+            store.events("arbitrum", None, 10).unwrap(),
+            vec![event(100, "1")]
+        );
+
+        store.delete_event(&pending).unwrap();
+        assert!(store.events("arbitrum", None, 10).unwrap().is_empty());
+        // A re-included event can be stored again
+        assert!(store.insert_event(&pending).unwrap());
+    }
+
+    #[test]
+    fn test_migrations_recorded() {
+        let store = Store::open_in_memory().unwrap();
+        let conn = store.conn.lock().unwrap();
+        let version: usize = conn
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, MIGRATIONS.len());
+    }
+
+    #[test]
+    fn test_latest_status_snapshot() {
+        let store = Store::open_in_memory().unwrap();
+        let mut status = RollupStatus {
+            latest_batch: Some("1".to_string()),
+            ..Default::default()
+        };
+        store.save_status("arbitrum", &status, 1).unwrap();
+        status.latest_batch = Some("2".to_string());
+        store.save_status("arbitrum", &status, 2).unwrap();
+        store.save_status("base", &status, 2).unwrap();
+
+        let statuses = store.latest_statuses().unwrap();
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(statuses["arbitrum"].latest_batch, Some("2".to_string()));
+
+        // Superseded snapshots are dropped
+        let rows: i64 = store
+            .conn()
+            .query_row("SELECT COUNT(*) FROM status_snapshots", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(rows, 2);
+    }
+
+    #[test]
+    fn test_health_state_and_transitions() {
+        let store = Store::open_in_memory().unwrap();
+        let state = RollupHealthState {
+            status: HealthStatus::Delayed,
+            last_event_time: Some(42),
+            ..Default::default()
+        };
+        store.save_health_state("base", &state).unwrap();
+        assert_eq!(store.health_states().unwrap()["base"], state);
+
+        for (from, to, at) in [
+            (HealthStatus::Disconnected, HealthStatus::Healthy, 10),
+            (HealthStatus::Healthy, HealthStatus::Delayed, 20),
+        ] {
+            store
+                .insert_transition(&HealthTransition {
+                    rollup: "base".to_string(),
+                    from,
+                    to,
+                    at,
+                })
+                .unwrap();
+        }
+        let transitions = store.transitions("base", 10).unwrap();
+        assert_eq!(transitions.len(), 2);
+        assert_eq!(transitions[0].to, HealthStatus::Delayed);
+        assert_eq!(transitions[0].at, 20);
+    }
+
+    #[test]
+    fn test_incidents_saved_and_filtered() {
+        let store = Store::open_in_memory().unwrap();
+        let mut incident = Incident {
+            id: None,
+            rollup: "base".to_string(),
+            started_at: 100,
+            ended_at: None,
+            stage: Some(HealthStage::Proving),
+            peak: HealthStatus::Delayed,
+            issues: vec!["No proof".to_string()],
+            resolution: None,
+        };
+        incident.id = Some(store.save_incident(&incident).unwrap());
+        incident.peak = HealthStatus::Halted;
+        incident.resolve(IncidentResolution::Recovered, 200);
+        assert_eq!(
+            store.save_incident(&incident).unwrap(),
+            incident.id.unwrap()
+        );
+
+        let mut open = Incident {
+            id: None,
+            rollup: "zksync".to_string(),
+            started_at: 300,
+            ended_at: None,
+            stage: None,
+            peak: HealthStatus::Delayed,
+            issues: Vec::new(),
+            resolution: None,
+        };
+        open.id = Some(store.save_incident(&open).unwrap());
+
+        assert_eq!(
+            store.incidents(None, None, 10).unwrap(),
+            vec![open.clone(), incident.clone()]
+        );
+        assert_eq!(
+            store.incidents(Some("base"), None, 10).unwrap(),
+            vec![incident]
+        );
+        // Resolved before `since`: dropped; still open: kept
+        assert_eq!(store.incidents(None, Some(250), 10).unwrap(), vec![open]);
+    }
+
+    #[test]
+    fn test_maintenance_windows() {
+        let store = Store::open_in_memory().unwrap();
+        let mut window = MaintenanceWindow {
+            id: None,
+            rollup: "arbitrum".to_string(),
+            stage: Some(HealthStage::Batching),
+            starts_at: 100,
+            ends_at: 200,
+            reason: Some("ArbOS upgrade".to_string()),
+        };
+        window.id = Some(store.insert_maintenance(&window).unwrap());
+        let mut rollup_wide = MaintenanceWindow {
+            id: None,
+            rollup: "base".to_string(),
+            stage: None,
+            starts_at: 50,
+            ends_at: 300,
+            reason: None,
+        };
+        rollup_wide.id = Some(store.insert_maintenance(&rollup_wide).unwrap());
+
+        assert_eq!(
+            store.maintenance_windows(0).unwrap(),
+            vec![rollup_wide.clone(), window.clone()]
+        );
+        // Windows closed by `after` are dropped
+        assert_eq!(
+            store.maintenance_windows(200).unwrap(),
+            vec![rollup_wide.clone()]
+        );
+        assert!(store.delete_maintenance(rollup_wide.id.unwrap()).unwrap());
+        assert!(!store.delete_maintenance(rollup_wide.id.unwrap()).unwrap());
+        assert_eq!(store.maintenance_windows(0).unwrap(), vec![window]);
+    }
+
+    #[test]
+    fn test_alert_deliveries() {
+        use crate::alerts::{AlertKind, AlertSeverity, AlertState};
+
+        let store = Store::open_in_memory().unwrap();
+        let mut failed = AlertDelivery {
+            id: None,
+            target: "pager".to_string(),
+            rollup: "base".to_string(),
+            kind: AlertKind::Sequencer,
+            state: AlertState::Triggered,
+            severity: AlertSeverity::Critical,
+            summary: "base sequencer stopped producing blocks".to_string(),
+            attempts: 5,
+            delivered: false,
+            status_code: Some(503),
+            error: Some("HTTP 503 Service Unavailable".to_string()),
+            at: 100,
+            finished_at: 130,
+        };
+        failed.id = Some(store.insert_delivery(&failed).unwrap());
+        let mut delivered = AlertDelivery {
+            id: None,
+            target: "chat".to_string(),
+            rollup: "arbitrum".to_string(),
+            kind: AlertKind::Health,
+            state: AlertState::Resolved,
+            severity: AlertSeverity::Warning,
+            summary: "arbitrum recovered: Healthy (was Delayed)".to_string(),
+            attempts: 1,
+            delivered: true,
+            status_code: Some(200),
+            error: None,
+            at: 200,
+            finished_at: 200,
+        };
+        delivered.id = Some(store.insert_delivery(&delivered).unwrap());
+
+        assert_eq!(
+            store.deliveries(None, 10).unwrap(),
+            vec![delivered.clone(), failed.clone()]
+        );
+        assert_eq!(store.deliveries(Some("base"), 10).unwrap(), vec![failed]);
+        assert_eq!(store.deliveries(None, 1).unwrap(), vec![delivered]);
+    }
+
+    #[test]
+    fn test_cursors_roundtrip() {
+        let store = Store::open_in_memory().unwrap();
+        store
+            .save_cursors(&HashMap::from([("arbitrum/batch".to_string(), 100)]))
+            .unwrap();
+        store
+            .save_cursors(&HashMap::from([("arbitrum/batch".to_string(), 120)]))
+            .unwrap();
+
+        let cursors = store.cursors().unwrap();
+        assert_eq!(cursors.len(), 1);
+        assert_eq!(cursors["arbitrum/batch"], 120);
+    }
+
+    #[test]
+    fn test_open_file_persists() {
+        let path = std::env::temp_dir().join(format!("rollup-status-{}.db", rand::random::<u64>()));
+        {
+            let store = Store::open(&path).unwrap();
+            store.insert_event(&event(100, "1")).unwrap();
+        }
+        let store = Store::open(&path).unwrap();
+        assert_eq!(store.events("arbitrum", None, 10).unwrap().len(), 1);
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -3,7 +3,12 @@ use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 use tokio::sync::broadcast;
 
+use crate::capture::{CaptureRecord, Recorder};
 use crate::config::BroadcastConfig;
+use crate::gaps::BatchGap;
+use crate::liveness::WatcherRegistry;
+use crate::metrics::Metrics;
+use crate::store::Store;
 
 /// Represents an event from a rollup posted to L1
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -18,8 +23,103 @@ pub struct RollupEvent {
     pub tx_hash: String,
     /// Batch/assertion identifier (rollup-specific)
     pub batch_number: Option<String>,
-    /// Unix timestamp when the event was detected
+    /// Unix timestamp of the L1 block that emitted the event (chain time)
     pub timestamp: Option<u64>,
+    /// Unix timestamp when this service observed the event
+    #[serde(default)]
+    pub observed_at: Option<u64>,
+    /// Whether the event has reached its rollup's L1 confirmation depth
+    #[serde(default)]
+    pub status: EventStatus,
+}
+
+/// L1 confirmation state of an event
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum EventStatus {
+    /// Seen on L1 but not yet buried under the rollup's confirmation depth
+    Pending,
+    /// Buried under the rollup's confirmation depth
+    #[default]
+    Confirmed,
+}
+
+impl EventStatus {
+    /// Lowercase name, as serialized
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventStatus::Pending => "pending",
+            EventStatus::Confirmed => "confirmed",
+        }
+    }
+}
+
+/// Message sent to WebSocket stream clients.
+///
+/// Every message carries a `type` tag: `Event` for a new `RollupEvent`, the
+/// notice's variant name otherwise.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamMessage {
+    /// A new rollup event
+    Event(RollupEvent),
+    /// A change to a previously sent event
+    Notice(StreamNotice),
+}
+
+impl Serialize for StreamMessage {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        /// Events tagged like the notices they sit alongside
+        #[derive(Serialize)]
+        #[serde(tag = "type")]
+        enum Tagged<'a> {
+            Event(&'a RollupEvent),
+        }
+
+        match self {
+            Self::Event(event) => Tagged::Event(event).serialize(serializer),
+            Self::Notice(notice) => notice.serialize(serializer),
+        }
+    }
+}
+
+/// Tagged stream notices about previously sent events, stream integrity and
+/// rollup state changes
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum StreamNotice {
+    /// A pending event reached its confirmation depth
+    EventConfirmed(RollupEvent),
+    /// An event's log was removed by an L1 reorg; clients should undo it
+    EventRetracted(RollupEvent),
+    /// A sequential batch stream skipped or repeated numbers
+    BatchGap(BatchGap),
+    /// A rollup's overall health status changed
+    HealthChanged(HealthChange),
+    /// An L2 sequencer started or stopped producing blocks
+    SequencerChanged(SequencerChange),
+}
+
+/// Transition of a rollup's health status
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct HealthChange {
+    pub rollup: String,
+    pub previous: HealthStatus,
+    pub status: HealthStatus,
+    /// Health issues at the time of the change
+    pub reasons: Vec<String>,
+    /// Unix timestamp of the change
+    pub at: u64,
+}
+
+/// Transition of an L2 sequencer between producing and not producing
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SequencerChange {
+    pub rollup: String,
+    pub previous: SequencerStatus,
+    pub status: SequencerStatus,
+    pub reasons: Vec<String>,
+    /// Unix timestamp of the change
+    pub at: u64,
 }
 
 /// Current status of a rollup
@@ -53,6 +153,8 @@ pub enum HealthStatus {
     Halted,
     /// Rollup appears disconnected from L1
     Disconnected,
+    /// Rollup is in a declared maintenance window
+    Maintenance,
 }
 
 /// Current L2 sequencer status for a rollup
@@ -80,7 +182,15 @@ pub struct AppState {
     /// Current L2 sequencer status for each rollup
     pub sequencer_statuses: Arc<RwLock<HashMap<String, SequencerStatus>>>,
     /// Broadcast channel for real-time events
-    pub tx: broadcast::Sender<RollupEvent>,
+    pub tx: broadcast::Sender<StreamMessage>,
+    /// Persistent store, if enabled
+    pub store: Option<Store>,
+    /// Capture of raw watcher inputs, if recording
+    pub recorder: Option<Recorder>,
+    /// Connection state of the event stream watchers
+    pub watchers: WatcherRegistry,
+    /// Counters exported on `/metrics`
+    pub metrics: Metrics,
 }
 
 impl AppState {
@@ -91,11 +201,75 @@ impl AppState {
 
     /// Create a new AppState with custom configuration
     pub fn with_config(config: BroadcastConfig) -> Self {
-        let (tx, _rx) = broadcast::channel::<RollupEvent>(config.channel_capacity);
+        let (tx, _rx) = broadcast::channel::<StreamMessage>(config.channel_capacity);
         Self {
             statuses: Arc::new(RwLock::new(HashMap::new())),
             sequencer_statuses: Arc::new(RwLock::new(HashMap::new())),
             tx,
+            store: None,
+            recorder: None,
+            watchers: WatcherRegistry::default(),
+            metrics: Metrics::default(),
+        }
+    }
+
+    /// Persist events and statuses to the given store
+    pub fn with_store(mut self, store: Store) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Record raw watcher inputs to the given capture
+    pub fn with_recorder(mut self, recorder: Recorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Append a raw input to the capture, if recording; the record is only
+    /// built when needed
+    pub fn record(&self, record: impl FnOnce() -> CaptureRecord) {
+        if let Some(recorder) = &self.recorder {
+            recorder.record(record());
+        }
+    }
+
+    /// Restore rollup and sequencer statuses from the store
+    pub fn restore(&self) -> eyre::Result<()> {
+        let Some(store) = &self.store else {
+            return Ok(());
+        };
+        let statuses = store.latest_statuses()?;
+        let sequencer_statuses = store.sequencer_statuses()?;
+
+        match self.statuses.write() {
+            Ok(mut current) => current.extend(statuses),
+            Err(poisoned) => poisoned.into_inner().extend(statuses),
+        }
+        match self.sequencer_statuses.write() {
+            Ok(mut current) => current.extend(sequencer_statuses),
+            Err(poisoned) => poisoned.into_inner().extend(sequencer_statuses),
+        }
+        Ok(())
+    }
+
+    /// Persist an event; returns false if the store already had it
+    pub fn persist_event(&self, event: &RollupEvent) -> bool {
+        let Some(store) = &self.store else {
+            return true;
+        };
+        let inserted = store.insert_event(event).and_then(|inserted| {
+            // A re-fetched event may have been stored while still pending
+            if !inserted && event.status == EventStatus::Confirmed {
+                store.set_event_status(event, EventStatus::Confirmed)?;
+            }
+            Ok(inserted)
+        });
+        match inserted {
+            Ok(inserted) => inserted,
+            Err(e) => {
+                tracing::error!(rollup = %event.rollup, error = ?e, "Failed to persist event");
+                true
+            }
         }
     }
 
@@ -104,10 +278,11 @@ impl AppState {
     where
         F: FnOnce(&mut RollupStatus),
     {
-        match self.statuses.write() {
+        let snapshot = match self.statuses.write() {
             Ok(mut statuses) => {
                 let entry = statuses.entry(rollup.to_string()).or_default();
                 updater(entry);
+                entry.clone()
             }
             Err(poisoned) => {
                 tracing::error!(
@@ -117,6 +292,14 @@ impl AppState {
                 let mut statuses = poisoned.into_inner();
                 let entry = statuses.entry(rollup.to_string()).or_default();
                 updater(entry);
+                entry.clone()
+            }
+        };
+
+        if let Some(store) = &self.store {
+            let at = snapshot.last_updated.unwrap_or_default();
+            if let Err(e) = store.save_status(rollup, &snapshot, at) {
+                tracing::error!(rollup = rollup, error = ?e, "Failed to persist status");
             }
         }
     }
@@ -152,10 +335,11 @@ impl AppState {
     where
         F: FnOnce(&mut SequencerStatus),
     {
-        match self.sequencer_statuses.write() {
+        let snapshot = match self.sequencer_statuses.write() {
             Ok(mut statuses) => {
                 let entry = statuses.entry(rollup.to_string()).or_default();
                 updater(entry);
+                entry.clone()
             }
             Err(poisoned) => {
                 tracing::error!(
@@ -165,6 +349,13 @@ impl AppState {
                 let mut statuses = poisoned.into_inner();
                 let entry = statuses.entry(rollup.to_string()).or_default();
                 updater(entry);
+                entry.clone()
+            }
+        };
+
+        if let Some(store) = &self.store {
+            if let Err(e) = store.save_sequencer_status(rollup, &snapshot) {
+                tracing::error!(rollup = rollup, error = ?e, "Failed to persist sequencer status");
             }
         }
     }
@@ -200,7 +391,13 @@ impl AppState {
 
     /// Broadcast an event to all WebSocket clients
     pub fn broadcast(&self, event: RollupEvent) {
-        let _ = self.tx.send(event);
+        self.metrics.record_event(&event);
+        self.publish(StreamMessage::Event(event));
+    }
+
+    /// Send any stream message to all WebSocket clients
+    pub fn publish(&self, message: StreamMessage) {
+        let _ = self.tx.send(message);
     }
 }
 
@@ -278,12 +475,53 @@ mod tests {
             tx_hash: "0xabc".to_string(),
             batch_number: Some("100".to_string()),
             timestamp: Some(1234567890),
+            observed_at: None,
+            status: EventStatus::Confirmed,
         };
 
         state.broadcast(event.clone());
 
         let received = rx.try_recv().unwrap();
-        assert_eq!(received, event);
+        assert_eq!(received, StreamMessage::Event(event));
+    }
+
+    #[test]
+    fn test_stream_message_serialization() {
+        let event = RollupEvent {
+            rollup: "base".to_string(),
+            event_type: "DisputeGameCreated".to_string(),
+            block_number: 1,
+            tx_hash: "0xabc".to_string(),
+            batch_number: None,
+            timestamp: None,
+            observed_at: None,
+            status: EventStatus::Pending,
+        };
+
+        let tagged = serde_json::to_value(StreamMessage::Event(event.clone())).unwrap();
+        assert_eq!(tagged["type"], "Event");
+        assert_eq!(tagged["rollup"], "base");
+        assert_eq!(tagged["status"], "pending");
+
+        let notice =
+            serde_json::to_value(StreamMessage::Notice(StreamNotice::EventRetracted(event)))
+                .unwrap();
+        assert_eq!(notice["type"], "EventRetracted");
+        assert_eq!(notice["tx_hash"], "0xabc");
+
+        let change = serde_json::to_value(StreamMessage::Notice(StreamNotice::HealthChanged(
+            HealthChange {
+                rollup: "base".to_string(),
+                previous: HealthStatus::Healthy,
+                status: HealthStatus::Delayed,
+                reasons: vec!["No events for 700 seconds".to_string()],
+                at: 42,
+            },
+        )))
+        .unwrap();
+        assert_eq!(change["type"], "HealthChanged");
+        assert_eq!(change["previous"], "Healthy");
+        assert_eq!(change["status"], "Delayed");
     }
 
     #[test]
@@ -346,6 +584,29 @@ mod tests {
         assert_eq!(all.get("base").unwrap().latest_block, Some(20_000_000));
     }
 
+    #[test]
+    fn test_app_state_restore_from_store() {
+        let store = Store::open_in_memory().unwrap();
+        let state = AppState::new().with_store(store.clone());
+        state.update_status("arbitrum", |s| {
+            s.latest_batch = Some("100".to_string());
+        });
+        state.update_sequencer_status("arbitrum", |s| {
+            s.latest_block = Some(50_000_000);
+        });
+
+        let restored = AppState::new().with_store(store);
+        restored.restore().unwrap();
+        assert_eq!(
+            restored.get_status("arbitrum").latest_batch,
+            Some("100".to_string())
+        );
+        assert_eq!(
+            restored.get_sequencer_status("arbitrum").latest_block,
+            Some(50_000_000)
+        );
+    }
+
     #[test]
     fn test_rollup_status_default() {
         let status = RollupStatus::default();
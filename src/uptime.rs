@@ -0,0 +1,280 @@
+use crate::health::HealthStage;
+use crate::incidents::Incident;
+use crate::store::Store;
+use crate::types::HealthStatus;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Rolling windows reported by default, ending now
+pub const ROLLING_WINDOWS: [(&str, u64); 3] = [
+    ("24h", 24 * 3600),
+    ("7d", 7 * 24 * 3600),
+    ("30d", 30 * 24 * 3600),
+];
+
+/// Status of a rollup or stage at the start of a window and its changes
+/// during it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatusTimeline {
+    /// Status when the window opened, `None` before anything was recorded
+    pub initial: Option<HealthStatus>,
+    /// Unix timestamp and new status of each change, oldest first
+    pub changes: Vec<(u64, HealthStatus)>,
+}
+
+/// Availability over a window.
+///
+/// Time before anything was recorded or while `Disconnected` is unknown and
+/// left out of the percentages, as is time under `Maintenance`. An outage is
+/// a stretch of `Delayed` or `Halted`, carrying on while `Disconnected`;
+/// outages already under way when the window opened are measured from its
+/// start, and outages cut short by maintenance are counted but not recovered
+/// from.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UptimeStats {
+    pub healthy_secs: u64,
+    pub delayed_secs: u64,
+    pub halted_secs: u64,
+    pub unknown_secs: u64,
+    pub maintenance_secs: u64,
+    /// Share of the known time spent `Healthy`
+    pub availability_pct: Option<f64>,
+    /// Share of the known time not `Halted`
+    pub uptime_pct: Option<f64>,
+    /// Outages overlapping the window
+    pub incidents: u32,
+    /// Mean time to recovery of the outages that ended within the window
+    pub mttr_secs: Option<u64>,
+}
+
+impl StatusTimeline {
+    /// Availability between `from` and `to`
+    pub fn stats(&self, from: u64, to: u64) -> UptimeStats {
+        let mut stats = UptimeStats::default();
+        let mut status = self.initial.clone();
+        let mut since = from;
+        let mut outage_start = status
+            .as_ref()
+            .filter(|s| Incident::is_incident(s))
+            .map(|_| from);
+        let mut recoveries = Vec::new();
+        stats.incidents = u32::from(outage_start.is_some());
+
+        for (at, next) in &self.changes {
+            let at = (*at).clamp(since, to);
+            stats.add(status.as_ref(), at - since);
+            since = at;
+
+            match (outage_start, Incident::is_incident(next)) {
+                (None, true) => {
+                    stats.incidents += 1;
+                    outage_start = Some(at);
+                }
+                (Some(_), false) if *next == HealthStatus::Disconnected => {}
+                (Some(_), false) if *next == HealthStatus::Maintenance => {
+                    outage_start = None;
+                }
+                (Some(start), false) => {
+                    recoveries.push(at - start);
+                    outage_start = None;
+                }
+                _ => {}
+            }
+            status = Some(next.clone());
+        }
+        stats.add(status.as_ref(), to.saturating_sub(since));
+
+        let known = stats.healthy_secs + stats.delayed_secs + stats.halted_secs;
+        if known > 0 {
+            stats.availability_pct = Some(percent(stats.healthy_secs, known));
+            stats.uptime_pct = Some(percent(known - stats.halted_secs, known));
+        }
+        if !recoveries.is_empty() {
+            stats.mttr_secs = Some(recoveries.iter().sum::<u64>() / recoveries.len() as u64);
+        }
+        stats
+    }
+}
+
+impl UptimeStats {
+    fn add(&mut self, status: Option<&HealthStatus>, secs: u64) {
+        match status {
+            Some(HealthStatus::Healthy) => self.healthy_secs += secs,
+            Some(HealthStatus::Delayed) => self.delayed_secs += secs,
+            Some(HealthStatus::Halted) => self.halted_secs += secs,
+            Some(HealthStatus::Maintenance) => self.maintenance_secs += secs,
+            Some(HealthStatus::Disconnected) | None => self.unknown_secs += secs,
+        }
+    }
+}
+
+/// Percentage rounded to two decimals
+fn percent(part: u64, total: u64) -> f64 {
+    (part as f64 * 10_000.0 / total as f64).round() / 100.0
+}
+
+/// Availability of a rollup and each of its stages over one window
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UptimeWindow {
+    /// `24h`, `7d`, `30d` or `custom`
+    pub window: String,
+    pub from: u64,
+    pub to: u64,
+    pub overall: UptimeStats,
+    pub stages: BTreeMap<HealthStage, UptimeStats>,
+}
+
+impl UptimeWindow {
+    /// Compute a window from the stored health and stage transitions
+    pub fn compute(
+        store: &Store,
+        rollup: &str,
+        window: &str,
+        from: u64,
+        to: u64,
+    ) -> eyre::Result<Self> {
+        let overall = store
+            .status_timeline(rollup, None, from, to)?
+            .stats(from, to);
+        let mut stages = BTreeMap::new();
+        for stage in HealthStage::ALL {
+            let timeline = store.status_timeline(rollup, Some(stage), from, to)?;
+            stages.insert(stage, timeline.stats(from, to));
+        }
+        Ok(Self {
+            window: window.to_string(),
+            from,
+            to,
+            overall,
+            stages,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::{HealthTransition, StageTransition};
+
+    #[test]
+    fn test_stats_over_window() {
+        let timeline = StatusTimeline {
+            initial: Some(HealthStatus::Healthy),
+            changes: vec![
+                (200, HealthStatus::Delayed),
+                (300, HealthStatus::Halted),
+                (400, HealthStatus::Healthy),
+                (900, HealthStatus::Delayed),
+            ],
+        };
+        let stats = timeline.stats(100, 1000);
+        assert_eq!(stats.healthy_secs, 600);
+        assert_eq!(stats.delayed_secs, 200);
+        assert_eq!(stats.halted_secs, 100);
+        assert_eq!(stats.unknown_secs, 0);
+        assert_eq!(stats.availability_pct, Some(66.67));
+        assert_eq!(stats.uptime_pct, Some(88.89));
+        assert_eq!(stats.incidents, 2);
+        assert_eq!(stats.mttr_secs, Some(200));
+    }
+
+    #[test]
+    fn test_unknown_time_left_out() {
+        let timeline = StatusTimeline {
+            initial: None,
+            changes: vec![(500, HealthStatus::Healthy)],
+        };
+        let stats = timeline.stats(0, 1000);
+        assert_eq!(stats.unknown_secs, 500);
+        assert_eq!(stats.availability_pct, Some(100.0));
+        assert_eq!(stats.incidents, 0);
+        assert_eq!(stats.mttr_secs, None);
+
+        let stats = StatusTimeline::default().stats(0, 1000);
+        assert_eq!(stats.unknown_secs, 1000);
+        assert_eq!(stats.availability_pct, None);
+    }
+
+    #[test]
+    fn test_maintenance_excluded() {
+        let timeline = StatusTimeline {
+            initial: Some(HealthStatus::Healthy),
+            changes: vec![
+                (200, HealthStatus::Halted),
+                (300, HealthStatus::Maintenance),
+                (800, HealthStatus::Healthy),
+            ],
+        };
+        let stats = timeline.stats(100, 1000);
+        assert_eq!(stats.maintenance_secs, 500);
+        assert_eq!(stats.availability_pct, Some(75.0));
+        assert_eq!(stats.incidents, 1);
+        assert_eq!(stats.mttr_secs, None);
+    }
+
+    #[test]
+    fn test_outage_carries_on_while_disconnected() {
+        let timeline = StatusTimeline {
+            initial: Some(HealthStatus::Healthy),
+            changes: vec![
+                (200, HealthStatus::Halted),
+                (300, HealthStatus::Disconnected),
+                (500, HealthStatus::Healthy),
+            ],
+        };
+        let stats = timeline.stats(100, 1000);
+        assert_eq!(stats.unknown_secs, 200);
+        assert_eq!(stats.incidents, 1);
+        assert_eq!(stats.mttr_secs, Some(300));
+    }
+
+    #[test]
+    fn test_outage_under_way_counts_from_window_start() {
+        let timeline = StatusTimeline {
+            initial: Some(HealthStatus::Halted),
+            changes: vec![(400, HealthStatus::Healthy)],
+        };
+        let stats = timeline.stats(100, 1000);
+        assert_eq!(stats.incidents, 1);
+        assert_eq!(stats.mttr_secs, Some(300));
+    }
+
+    #[test]
+    fn test_window_from_stored_transitions() {
+        let store = Store::open_in_memory().unwrap();
+        for (from, to, at) in [
+            (HealthStatus::Disconnected, HealthStatus::Healthy, 0),
+            (HealthStatus::Healthy, HealthStatus::Halted, 1000),
+            (HealthStatus::Halted, HealthStatus::Healthy, 1500),
+        ] {
+            let transition = HealthTransition {
+                rollup: "base".to_string(),
+                from,
+                to,
+                at,
+            };
+            store.insert_transition(&transition).unwrap();
+        }
+        store
+            .insert_stage_transition(&StageTransition {
+                rollup: "base".to_string(),
+                stage: HealthStage::Sequencer,
+                from: HealthStatus::Healthy,
+                to: HealthStatus::Halted,
+                at: 1000,
+            })
+            .unwrap();
+
+        let window = UptimeWindow::compute(&store, "base", "custom", 500, 2000).unwrap();
+        assert_eq!(window.overall.halted_secs, 500);
+        assert_eq!(window.overall.healthy_secs, 1000);
+        assert_eq!(window.overall.mttr_secs, Some(500));
+
+        let sequencer = &window.stages[&HealthStage::Sequencer];
+        assert_eq!(sequencer.unknown_secs, 500);
+        assert_eq!(sequencer.halted_secs, 1000);
+        assert_eq!(sequencer.incidents, 1);
+        assert_eq!(sequencer.mttr_secs, None);
+        assert_eq!(window.stages[&HealthStage::Proving].availability_pct, None);
+    }
+}
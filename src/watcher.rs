@@ -0,0 +1,382 @@
+use crate::health::{HealthCategory, HealthMonitor};
+use crate::sequencer::SequencerKind;
+use crate::types::{AppState, EventStatus, RollupEvent, RollupStatus};
+use ethers::types::{Address, Log, H256};
+use std::time::Duration;
+
+/// Slot in `RollupStatus` that an event updates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+pub enum StatusSlot {
+    /// `latest_batch` / `latest_batch_tx`
+    #[serde(rename = "latest_batch")]
+    Batch,
+    /// `latest_proof` / `latest_proof_tx`
+    #[serde(rename = "latest_proof")]
+    Proof,
+    /// `latest_finalized` / `latest_finalized_tx`
+    #[serde(rename = "latest_finalized")]
+    Finalized,
+}
+
+impl StatusSlot {
+    /// Write a value and its transaction hash into the matching status fields
+    pub fn apply(&self, status: &mut RollupStatus, value: &str, tx_hash: &str) {
+        let (slot, slot_tx) = match self {
+            StatusSlot::Batch => (&mut status.latest_batch, &mut status.latest_batch_tx),
+            StatusSlot::Proof => (&mut status.latest_proof, &mut status.latest_proof_tx),
+            StatusSlot::Finalized => (
+                &mut status.latest_finalized,
+                &mut status.latest_finalized_tx,
+            ),
+        };
+        *slot = Some(value.to_string());
+        *slot_tx = Some(tx_hash.to_string());
+    }
+
+    /// Current value and transaction hash of the slot
+    pub fn get<'a>(&self, status: &'a RollupStatus) -> (&'a Option<String>, &'a Option<String>) {
+        match self {
+            StatusSlot::Batch => (&status.latest_batch, &status.latest_batch_tx),
+            StatusSlot::Proof => (&status.latest_proof, &status.latest_proof_tx),
+            StatusSlot::Finalized => (&status.latest_finalized, &status.latest_finalized_tx),
+        }
+    }
+
+    /// Copy the slot's value and transaction hash from another status
+    pub fn copy_from(&self, status: &mut RollupStatus, from: &RollupStatus) {
+        let (value, tx_hash) = self.get(from);
+        let (value, tx_hash) = (value.clone(), tx_hash.clone());
+        match self {
+            StatusSlot::Batch => {
+                status.latest_batch = value;
+                status.latest_batch_tx = tx_hash;
+            }
+            StatusSlot::Proof => {
+                status.latest_proof = value;
+                status.latest_proof_tx = tx_hash;
+            }
+            StatusSlot::Finalized => {
+                status.latest_finalized = value;
+                status.latest_finalized_tx = tx_hash;
+            }
+        }
+    }
+}
+
+/// An L1 contract watched by a rollup
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contract {
+    /// Contract key referenced by event streams (e.g., "sequencer_inbox")
+    pub name: String,
+    /// Contract address on L1
+    pub address: Address,
+}
+
+/// A single L1 event subscription belonging to a rollup
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventStream {
+    /// Stream name used in logs (e.g., "batch")
+    pub name: String,
+    /// Event type reported on `RollupEvent` (e.g., "BatchDelivered")
+    pub event_type: String,
+    /// Key of the contract emitting the event
+    pub contract: String,
+    /// Event signature hash (topic0)
+    pub topic: H256,
+    /// Status slots updated when the event arrives
+    pub slots: Vec<StatusSlot>,
+    /// Health category the event counts toward
+    pub category: HealthCategory,
+    /// Whether the decoded identifier is a strictly sequential number
+    pub sequential: bool,
+}
+
+/// Describes a rollup tracked on L1.
+///
+/// Implementors only declare their contracts, event streams and how to pull
+/// the batch/assertion identifier out of a log; the shared L1 subscription,
+/// status updates, health recording and broadcasting are handled generically.
+pub trait RollupWatcher: Send + Sync {
+    /// Rollup name used for routes, state keys and events (e.g., "arbitrum")
+    fn name(&self) -> &str;
+
+    /// L1 contracts watched by this rollup
+    fn contracts(&self) -> eyre::Result<Vec<Contract>>;
+
+    /// L1 event streams to subscribe to
+    fn streams(&self) -> Vec<EventStream>;
+
+    /// Decode a log received on `stream` into its batch/assertion identifier
+    fn decode(&self, stream: &EventStream, log: &Log) -> eyre::Result<String>;
+
+    /// Kind of L2 JSON-RPC the sequencer poller should speak
+    fn sequencer_kind(&self) -> SequencerKind {
+        SequencerKind::Evm
+    }
+
+    /// L1 blocks an event must be buried under before it is confirmed
+    fn confirmations(&self) -> u64 {
+        0
+    }
+
+    /// Default L2 sequencer polling interval
+    fn sequencer_poll_interval(&self) -> Duration {
+        Duration::from_millis(5000)
+    }
+
+    /// Event types emitted by this rollup
+    fn event_types(&self) -> Vec<String> {
+        self.streams().into_iter().map(|s| s.event_type).collect()
+    }
+}
+
+/// Shorten long hex identifiers for log output
+fn short_id(id: &str) -> &str {
+    if id.starts_with("0x") && id.len() >= 18 {
+        &id[..18]
+    } else {
+        id
+    }
+}
+
+/// Decode a log and apply it to shared state, health and WebSocket clients.
+///
+/// `block_timestamp` is the L1 time of the log's block; when unknown the
+/// event is stamped with the time it was observed.
+pub fn handle_log(
+    rollup: &dyn RollupWatcher,
+    stream: &EventStream,
+    log: &Log,
+    block_timestamp: Option<u64>,
+    status: EventStatus,
+    state: &AppState,
+    health: &HealthMonitor,
+) -> eyre::Result<RollupEvent> {
+    let id = rollup.decode(stream, log)?;
+    let block_number = log.block_number.map(|n| n.as_u64()).unwrap_or_default();
+    let tx_hash = log
+        .transaction_hash
+        .map(|h| format!("{:?}", h))
+        .unwrap_or_default();
+    let observed_at = health.now();
+
+    let rollup_event = RollupEvent {
+        rollup: rollup.name().to_string(),
+        event_type: stream.event_type.clone(),
+        block_number,
+        tx_hash: tx_hash.clone(),
+        batch_number: Some(id.clone()),
+        timestamp: Some(block_timestamp.unwrap_or(observed_at)),
+        observed_at: Some(observed_at),
+        status,
+    };
+
+    // Skip events already persisted (e.g. re-fetched by a backfill after a restart)
+    if !state.persist_event(&rollup_event) {
+        tracing::debug!(
+            rollup = rollup.name(),
+            event = %stream.event_type,
+            block = block_number,
+            "Event already recorded, skipping"
+        );
+        return Ok(rollup_event);
+    }
+
+    // Update shared state
+    state.update_status(rollup.name(), |status| {
+        for slot in &stream.slots {
+            slot.apply(status, &id, &tx_hash);
+        }
+        status.last_updated = Some(observed_at);
+    });
+
+    // Record event for health monitoring
+    health.record_event_as(&rollup_event, stream.category);
+
+    // Broadcast to WebSocket clients
+    state.broadcast(rollup_event.clone());
+
+    tracing::info!(
+        rollup = rollup.name(),
+        event = %stream.event_type,
+        id = %short_id(&id),
+        block = block_number,
+        status = status.as_str(),
+        latency_secs = block_timestamp.map(|t| observed_at.saturating_sub(t)),
+        "Event received"
+    );
+
+    Ok(rollup_event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::StreamMessage;
+    use ethers::types::{U256, U64};
+
+    struct TestRollup;
+
+    impl RollupWatcher for TestRollup {
+        fn name(&self) -> &str {
+            "test"
+        }
+
+        fn contracts(&self) -> eyre::Result<Vec<Contract>> {
+            Ok(vec![Contract {
+                name: "inbox".to_string(),
+                address: Address::zero(),
+            }])
+        }
+
+        fn streams(&self) -> Vec<EventStream> {
+            vec![EventStream {
+                name: "batch".to_string(),
+                event_type: "BatchDelivered".to_string(),
+                contract: "inbox".to_string(),
+                topic: H256::zero(),
+                slots: vec![StatusSlot::Batch, StatusSlot::Proof],
+                category: HealthCategory::Batch,
+                sequential: false,
+            }]
+        }
+
+        fn decode(&self, _stream: &EventStream, log: &Log) -> eyre::Result<String> {
+            let topic = log
+                .topics
+                .get(1)
+                .ok_or_else(|| eyre::eyre!("missing indexed batch number"))?;
+            Ok(U256::from_big_endian(topic.as_bytes()).to_string())
+        }
+    }
+
+    fn test_log(batch: u64) -> Log {
+        Log {
+            topics: vec![H256::zero(), H256::from_low_u64_be(batch)],
+            block_number: Some(U64::from(12345)),
+            transaction_hash: Some(H256::from_low_u64_be(0xabc)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_status_slot_apply() {
+        let mut status = RollupStatus::default();
+        StatusSlot::Finalized.apply(&mut status, "42", "0xabc");
+        assert_eq!(status.latest_finalized, Some("42".to_string()));
+        assert_eq!(status.latest_finalized_tx, Some("0xabc".to_string()));
+        assert!(status.latest_batch.is_none());
+    }
+
+    #[test]
+    fn test_status_slot_copy_from() {
+        let mut from = RollupStatus::default();
+        StatusSlot::Proof.apply(&mut from, "7", "0x7");
+        let mut status = RollupStatus::default();
+        StatusSlot::Proof.apply(&mut status, "8", "0x8");
+        StatusSlot::Batch.apply(&mut status, "9", "0x9");
+
+        StatusSlot::Proof.copy_from(&mut status, &from);
+        assert_eq!(StatusSlot::Proof.get(&status), StatusSlot::Proof.get(&from));
+        assert_eq!(status.latest_batch, Some("9".to_string()));
+    }
+
+    #[test]
+    fn test_short_id() {
+        assert_eq!(short_id("12345"), "12345");
+        assert_eq!(
+            short_id("0x1234567890abcdef1234567890abcdef"),
+            "0x1234567890abcdef"
+        );
+    }
+
+    #[test]
+    fn test_handle_log_updates_state() {
+        let state = AppState::new();
+        let health = HealthMonitor::new();
+        let mut rx = state.tx.subscribe();
+        let rollup = TestRollup;
+        let stream = rollup.streams().remove(0);
+
+        let event = handle_log(
+            &rollup,
+            &stream,
+            &test_log(100),
+            None,
+            EventStatus::Confirmed,
+            &state,
+            &health,
+        )
+        .unwrap();
+        assert_eq!(event.rollup, "test");
+        assert_eq!(event.event_type, "BatchDelivered");
+        assert_eq!(event.block_number, 12345);
+        assert_eq!(event.batch_number, Some("100".to_string()));
+        // Without a block timestamp the event is stamped when observed
+        assert!(event.observed_at.is_some());
+        assert_eq!(event.timestamp, event.observed_at);
+
+        let status = state.get_status("test");
+        assert_eq!(status.latest_batch, Some("100".to_string()));
+        assert_eq!(status.latest_proof, Some("100".to_string()));
+        assert_eq!(status.latest_batch_tx, Some(event.tx_hash.clone()));
+        assert!(status.latest_finalized.is_none());
+
+        assert_eq!(rx.try_recv().unwrap(), StreamMessage::Event(event));
+        let result = health.check_health("test");
+        assert!(result.last_batch_age_secs.is_some());
+        assert!(result.last_proof_age_secs.is_none());
+    }
+
+    #[test]
+    fn test_handle_log_uses_block_timestamp() {
+        let state = AppState::new();
+        let health = HealthMonitor::new();
+        let rollup = TestRollup;
+        let stream = rollup.streams().remove(0);
+        let block_time = health.now() - 600;
+
+        let event = handle_log(
+            &rollup,
+            &stream,
+            &test_log(100),
+            Some(block_time),
+            EventStatus::Confirmed,
+            &state,
+            &health,
+        )
+        .unwrap();
+        assert_eq!(event.timestamp, Some(block_time));
+        assert!(event.observed_at.unwrap() >= block_time + 600);
+
+        // Health ages are measured from the L1 block, not from detection
+        let age = health.check_health("test").last_batch_age_secs.unwrap();
+        assert!(age >= 600);
+    }
+
+    #[test]
+    fn test_handle_log_decode_error() {
+        let state = AppState::new();
+        let health = HealthMonitor::new();
+        let rollup = TestRollup;
+        let stream = rollup.streams().remove(0);
+        let log = Log::default();
+
+        assert!(handle_log(
+            &rollup,
+            &stream,
+            &log,
+            None,
+            EventStatus::Confirmed,
+            &state,
+            &health
+        )
+        .is_err());
+        assert_eq!(state.get_status("test"), RollupStatus::default());
+    }
+
+    #[test]
+    fn test_event_types() {
+        assert_eq!(TestRollup.event_types(), vec!["BatchDelivered".to_string()]);
+    }
+}
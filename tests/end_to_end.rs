@@ -0,0 +1,603 @@
+//! End-to-end tests running the L1 watcher, sequencer pollers and API against
+//! in-process JSON-RPC nodes.
+
+mod support;
+
+use ethers::abi::Token;
+use ethers::types::Address;
+use rollup_proof_status::{
+    AlertSeverity, EventStatus, HealthConfig, L1Config, L1Mode, RollupEvent, SequencerKind,
+    WebhookFormat, WebhookTarget,
+};
+use std::time::Duration;
+use support::{
+    bytes32, encode_log, l1_config, now, reconnect_config, uint, wait_for, MockL1, MockStarknet,
+    MockWebhook, TestApp,
+};
+
+const ARBITRUM_INBOX: &str = "0x1c479675ad559DC151F6Ec7ed3FbF8ceE79582B6";
+const BASE_DISPUTE_GAME_FACTORY: &str = "0x43edB88C4B80fDD2AdFF2412A7BebF9dF42cB40e";
+const OPTIMISM_DISPUTE_GAME_FACTORY: &str = "0xe5965Ab5962eDc7477C8520243A95517CD252fA9";
+const STARKNET_CORE: &str = "0xc662c410C0ECf747543f5bA90660f6ABeBD9C8c4";
+const ZKSYNC_DIAMOND: &str = "0x32400084C286CF3E17e7B677ea9583e60a000324";
+
+fn address(value: &str) -> Address {
+    value.parse().unwrap()
+}
+
+fn batch_delivered(sequence_number: u64) -> ethers::types::Log {
+    encode_log(
+        "abi/arbitrum_sequencer_inbox.json",
+        "SequencerBatchDelivered",
+        address(ARBITRUM_INBOX),
+        &[("batchSequenceNumber", uint(sequence_number))],
+    )
+}
+
+/// Start the backend over WebSocket and wait for its live subscription
+async fn start_ws(l1: &MockL1) -> TestApp {
+    let app = TestApp::start(
+        l1_config(l1, L1Mode::Ws),
+        reconnect_config(Duration::from_secs(600)),
+    )
+    .await;
+    wait_for("log subscription", || async {
+        (l1.subscriptions("logs") > 0 && l1.calls("eth_blockNumber") > 0).then_some(())
+    })
+    .await;
+    app
+}
+
+/// Wait until a rollup status field has the expected value
+async fn wait_for_status(app: &TestApp, rollup: &str, field: &str, expected: &str) {
+    let path = format!("/rollups/{}/status", rollup);
+    wait_for(&format!("{} {}", rollup, field), || async {
+        let (_, status) = app.get(&path).await;
+        (status[field] == expected).then_some(())
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_batch_reaches_rest_and_stream() {
+    let l1 = MockL1::start(1_000).await;
+    let app = start_ws(&l1).await;
+    let (mut stream, initial) = app.stream().await;
+    assert!(initial["statuses"].is_object());
+
+    let log = l1.emit(batch_delivered(42));
+
+    // The first batch brings arbitrum's health up before the event is sent
+    let change = stream.next().await;
+    assert_eq!(change["type"], "HealthChanged");
+    assert_eq!(change["rollup"], "arbitrum");
+    assert_eq!(change["previous"], "Disconnected");
+    assert_eq!(change["status"], "Healthy");
+
+    let event = stream.next().await;
+    assert_eq!(event["type"], "Event");
+    assert_eq!(event["rollup"], "arbitrum");
+    assert_eq!(event["event_type"], "BatchDelivered");
+    assert_eq!(event["batch_number"], "42");
+    assert_eq!(event["block_number"], log.block_number.unwrap().as_u64());
+    assert_eq!(event["status"], "pending");
+
+    let (code, status) = app.get("/rollups/arbitrum/status").await;
+    assert_eq!(code, 200);
+    assert_eq!(status["latest_batch"], "42");
+    assert_eq!(
+        status["latest_batch_tx"],
+        format!("{:?}", log.transaction_hash.unwrap())
+    );
+
+    // Arbitrum events confirm two blocks deep
+    l1.mine(2);
+    let confirmed = stream.next().await;
+    assert_eq!(confirmed["type"], "EventConfirmed");
+    assert_eq!(confirmed["batch_number"], "42");
+
+    let (_, events) = app.get("/rollups/arbitrum/events").await;
+    assert_eq!(events["events"][0]["batch_number"], "42");
+    let (_, health) = app.get("/rollups/arbitrum/health").await;
+    assert_eq!(health["status"], "Healthy");
+    assert!(health["last_batch_age_secs"].as_u64().unwrap() < 60);
+}
+
+#[tokio::test]
+async fn test_metrics_exported() {
+    let l1 = MockL1::start(1_000).await;
+    let app = start_ws(&l1).await;
+    let (mut stream, _) = app.stream().await;
+    l1.emit(batch_delivered(7));
+    stream.next().await;
+    assert_eq!(stream.next().await["type"], "Event");
+
+    let (content_type, metrics) = app.get_text("/metrics").await;
+    assert!(content_type.starts_with("text/plain; version=0.0.4"));
+    for line in [
+        "rollup_events_total{rollup=\"arbitrum\",event_type=\"BatchDelivered\"} 1",
+        "rollup_health_status{rollup=\"arbitrum\",status=\"Healthy\"} 1",
+        "rollup_health_status{rollup=\"base\",status=\"Disconnected\"} 1",
+        "rollup_websocket_clients 1",
+        "rollup_connect_attempts_total{stream=\"l1/logs\",outcome=\"success\"} 1",
+    ] {
+        assert!(
+            metrics.lines().any(|l| l == line),
+            "missing {}:\n{}",
+            line,
+            metrics
+        );
+    }
+    assert!(metrics
+        .lines()
+        .any(|l| l.starts_with("rollup_seconds_since_last_batch{rollup=\"arbitrum\"}")));
+}
+
+#[tokio::test]
+async fn test_decodes_each_rollup_abi() {
+    let l1 = MockL1::start(1_000).await;
+    let app = start_ws(&l1).await;
+
+    l1.emit(encode_log(
+        "abi/base_dispute_game_factory.json",
+        "DisputeGameCreated",
+        address(BASE_DISPUTE_GAME_FACTORY),
+        &[
+            ("disputeProxy", Token::Address(Address::repeat_byte(0x11))),
+            ("rootClaim", bytes32(0xab)),
+        ],
+    ));
+    l1.emit(encode_log(
+        "abi/zksync_era_diamond.json",
+        "BlockCommit",
+        address(ZKSYNC_DIAMOND),
+        &[("batchNumber", uint(7)), ("batchHash", bytes32(0x01))],
+    ));
+    l1.emit(encode_log(
+        "abi/starknet_core_contract.json",
+        "LogStateUpdate",
+        address(STARKNET_CORE),
+        &[
+            ("blockNumber", Token::Int(900.into())),
+            ("blockHash", uint(0xbeef)),
+        ],
+    ));
+
+    wait_for_status(
+        &app,
+        "base",
+        "latest_proof",
+        &format!("0x{}", "ab".repeat(32)),
+    )
+    .await;
+    wait_for_status(&app, "zksync", "latest_batch", "7").await;
+    wait_for_status(&app, "starknet", "latest_finalized", "48879").await;
+
+    // Unrelated rollups on the shared subscription are untouched
+    let (_, optimism) = app.get("/rollups/optimism/status").await;
+    assert!(optimism["latest_proof"].is_null());
+}
+
+#[tokio::test]
+async fn test_backfills_logs_emitted_before_startup() {
+    let l1 = MockL1::start(1_000).await;
+    l1.emit(batch_delivered(41));
+    l1.mine(5);
+
+    let app = start_ws(&l1).await;
+    wait_for_status(&app, "arbitrum", "latest_batch", "41").await;
+    assert!(l1.calls("eth_getLogs") > 0);
+}
+
+#[tokio::test]
+async fn test_stale_filter_resubscribes() {
+    let l1 = MockL1::start(1_000).await;
+    let app = TestApp::start(
+        l1_config(&l1, L1Mode::Ws),
+        reconnect_config(Duration::from_millis(300)),
+    )
+    .await;
+
+    // New heads keep arriving, but only logs count as activity
+    wait_for("stale filter reconnect", || async {
+        l1.mine(1);
+        (l1.subscriptions("logs") >= 2).then_some(())
+    })
+    .await;
+    assert!(l1.subscriptions("newHeads") >= 2);
+
+    l1.emit(batch_delivered(43));
+    wait_for_status(&app, "arbitrum", "latest_batch", "43").await;
+}
+
+#[tokio::test]
+async fn test_http_polling_mode() {
+    let l1 = MockL1::start(1_000).await;
+    let app = TestApp::start(
+        l1_config(&l1, L1Mode::Http),
+        reconnect_config(Duration::from_secs(600)),
+    )
+    .await;
+
+    l1.emit(batch_delivered(44));
+    wait_for_status(&app, "arbitrum", "latest_batch", "44").await;
+    assert_eq!(l1.subscriptions("logs"), 0);
+
+    let (_, endpoints) = app.get("/l1/endpoints").await;
+    assert_eq!(endpoints["active"], l1.http_url());
+
+    let (_, watchers) = app.get("/watchers").await;
+    let arbitrum = watchers["watchers"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|w| w["rollup"] == "arbitrum" && w["stream"] == "batch")
+        .unwrap();
+    assert_eq!(arbitrum["state"], "polling");
+    assert!(arbitrum["last_message_at"].as_u64().is_some());
+}
+
+#[tokio::test]
+async fn test_http_polling_reorg() {
+    let l1 = MockL1::start(1_000).await;
+    let app = TestApp::start(
+        l1_config(&l1, L1Mode::Http),
+        reconnect_config(Duration::from_secs(600)),
+    )
+    .await;
+    let (mut stream, _) = app.stream().await;
+
+    let log = l1.emit(batch_delivered(44));
+    assert_eq!(stream.next().await["type"], "HealthChanged");
+    let event = stream.next().await;
+    assert_eq!(event["type"], "Event");
+    assert_eq!(event["batch_number"], "44");
+    assert_eq!(event["status"], "pending");
+
+    // Polling never sees the removed log, only the replacement block
+    l1.reorg(&log);
+    l1.mine(2);
+    let retracted = stream.next().await;
+    assert_eq!(retracted["type"], "EventRetracted");
+    assert_eq!(retracted["batch_number"], "44");
+
+    // The re-included log is fetched again though its block is behind the cursor
+    let event = stream.next().await;
+    assert_eq!(event["type"], "Event");
+    assert_eq!(event["batch_number"], "44");
+    assert_eq!(event["status"], "confirmed");
+    wait_for_status(&app, "arbitrum", "latest_batch", "44").await;
+}
+
+#[tokio::test]
+async fn test_dead_watcher_reports_disconnected() {
+    let l1 = MockL1::start(1_000).await;
+    let config = L1Config {
+        ws_urls: vec!["ws://127.0.0.1:1".to_string()],
+        http_urls: Vec::new(),
+        ..l1_config(&l1, L1Mode::Ws)
+    };
+    let app = TestApp::start(config, reconnect_config(Duration::from_secs(600))).await;
+
+    let watchers = wait_for("watcher gives up", || async {
+        let (_, watchers) = app.get("/watchers").await;
+        (watchers["watchers"][0]["state"] == "dead").then_some(watchers)
+    })
+    .await;
+    assert!(watchers["watchers"][0]["last_error"]
+        .as_str()
+        .unwrap()
+        .contains("RPC_HTTP not set"));
+
+    // An old event would halt the rollup, but nobody is listening for newer ones
+    app.health.record_event(&RollupEvent {
+        rollup: "arbitrum".to_string(),
+        event_type: "BatchDelivered".to_string(),
+        block_number: 1,
+        tx_hash: "0xabc".to_string(),
+        batch_number: Some("1".to_string()),
+        timestamp: Some(now() - 3600),
+        observed_at: None,
+        status: EventStatus::Confirmed,
+    });
+    let (_, health) = app.get("/rollups/arbitrum/health").await;
+    assert_eq!(health["status"], "Disconnected");
+    assert!(health["issues"][0]
+        .as_str()
+        .unwrap()
+        .starts_with("L1 watcher down for"));
+}
+
+#[tokio::test]
+async fn test_maintenance_window_silences_health() {
+    let l1 = MockL1::start(1_000).await;
+    let app = start_ws(&l1).await;
+
+    app.health.record_event(&RollupEvent {
+        rollup: "arbitrum".to_string(),
+        event_type: "BatchDelivered".to_string(),
+        block_number: 1,
+        tx_hash: "0xabc".to_string(),
+        batch_number: Some("1".to_string()),
+        timestamp: Some(now() - 3600),
+        observed_at: None,
+        status: EventStatus::Confirmed,
+    });
+    let (_, health) = app.get("/rollups/arbitrum/health").await;
+    assert_eq!(health["status"], "Halted");
+
+    // Maintenance silences alerting, so only the admin may declare it
+    let request = serde_json::json!({ "ends_at": now() + 3600 });
+    for token in [None, Some("wrong-token")] {
+        let (status, _) = app
+            .post_as("/rollups/arbitrum/maintenance", request.clone(), token)
+            .await;
+        assert_eq!(status, 401);
+    }
+    let (_, health) = app.get("/rollups/arbitrum/health").await;
+    assert_eq!(health["status"], "Halted");
+
+    let (status, window) = app
+        .post(
+            "/rollups/arbitrum/maintenance",
+            serde_json::json!({ "ends_at": now() + 3600, "reason": "ArbOS upgrade" }),
+        )
+        .await;
+    assert_eq!(status, 201);
+    let id = window["id"].as_u64().unwrap();
+
+    let (_, health) = app.get("/rollups/arbitrum/health").await;
+    assert_eq!(health["status"], "Maintenance");
+    assert!(health["issues"][0]
+        .as_str()
+        .unwrap()
+        .ends_with("ArbOS upgrade)"));
+    let (_, maintenance) = app.get("/maintenance").await;
+    assert_eq!(maintenance["maintenance"][0]["rollup"], "arbitrum");
+
+    let (status, _) = app
+        .post(
+            "/rollups/arbitrum/maintenance",
+            serde_json::json!({ "starts_at": now(), "ends_at": now() - 60 }),
+        )
+        .await;
+    assert_eq!(status, 400);
+    let (status, _) = app
+        .post(
+            "/rollups/polygon/maintenance",
+            serde_json::json!({ "ends_at": now() + 60 }),
+        )
+        .await;
+    assert_eq!(status, 404);
+
+    assert_eq!(
+        app.delete_as(&format!("/maintenance/{}", id), None).await,
+        401
+    );
+    assert_eq!(app.delete(&format!("/maintenance/{}", id)).await, 204);
+    assert_eq!(app.delete(&format!("/maintenance/{}", id)).await, 404);
+    let (_, health) = app.get("/rollups/arbitrum/health").await;
+    assert_eq!(health["status"], "Halted");
+}
+
+#[tokio::test]
+async fn test_health_transitions_alert_webhooks() {
+    let l1 = MockL1::start(1_000).await;
+    let app = start_ws(&l1).await;
+    let webhook = MockWebhook::start().await;
+    webhook.fail_next(1);
+    let pagerduty = MockWebhook::start().await;
+    pagerduty.fail_next(2);
+    app.health.set_config(HealthConfig {
+        webhooks: vec![
+            WebhookTarget {
+                name: "ops".to_string(),
+                url: webhook.url(),
+                format: WebhookFormat::Json,
+                routing_key: None,
+                rollups: vec!["arbitrum".to_string()],
+                min_severity: AlertSeverity::Warning,
+                headers: [("Authorization".to_string(), "Bearer test".to_string())].into(),
+            },
+            WebhookTarget {
+                name: "pager".to_string(),
+                url: pagerduty.url(),
+                format: WebhookFormat::PagerDuty,
+                routing_key: Some("R0UT1NGKEY".to_string()),
+                rollups: Vec::new(),
+                min_severity: AlertSeverity::Critical,
+                headers: Default::default(),
+            },
+        ],
+        ..HealthConfig::default()
+    });
+
+    let event = |timestamp| RollupEvent {
+        rollup: "arbitrum".to_string(),
+        event_type: "BatchDelivered".to_string(),
+        block_number: 1,
+        tx_hash: "0xabc".to_string(),
+        batch_number: Some("1".to_string()),
+        timestamp: Some(timestamp),
+        observed_at: None,
+        status: EventStatus::Confirmed,
+    };
+    // The rollup recovers while the first attempts are still being retried
+    app.health.record_event(&event(now() - 3600));
+    app.health.record_event(&event(now()));
+
+    // Each resolution waits for its trigger to be delivered
+    let received = wait_for("resolved alert", || async {
+        let received = webhook.received();
+        (received.len() == 3).then_some(received)
+    })
+    .await;
+    let alert = &received[1].body;
+    assert_eq!(alert, &received[0].body);
+    assert_eq!(alert["state"], "triggered");
+    assert_eq!(alert["severity"], "critical");
+    assert_eq!(alert["summary"], "arbitrum is Halted (was Disconnected)");
+    assert_eq!(alert["status"], "Halted");
+    assert_eq!(received[1].headers["authorization"], "Bearer test");
+    assert_eq!(received[2].body["state"], "resolved");
+    assert_eq!(received[2].body["status"], "Healthy");
+    assert_eq!(received[2].body["dedup_key"], alert["dedup_key"]);
+
+    // PagerDuty gets a trigger/resolve pair closing the same incident
+    let events = wait_for("pagerduty resolve", || async {
+        let received = pagerduty.received();
+        (received.len() == 4).then_some(received)
+    })
+    .await;
+    let (trigger, resolve) = (&events[2].body, &events[3].body);
+    assert_eq!(&events[0].body, trigger);
+    assert_eq!(trigger["event_action"], "trigger");
+    assert_eq!(trigger["routing_key"], "R0UT1NGKEY");
+    assert_eq!(trigger["payload"]["component"], "arbitrum");
+    assert_eq!(resolve["event_action"], "resolve");
+    assert_eq!(resolve["dedup_key"], trigger["dedup_key"]);
+    assert!(trigger["dedup_key"]
+        .as_str()
+        .unwrap()
+        .starts_with("arbitrum/health/"));
+
+    let deliveries = wait_for("deliveries logged", || async {
+        let (_, deliveries) = app.get("/alerts/deliveries?rollup=arbitrum").await;
+        (deliveries["deliveries"].as_array().unwrap().len() == 4).then_some(deliveries)
+    })
+    .await;
+    let deliveries: Vec<_> = deliveries["deliveries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|d| d["target"] == "ops")
+        .collect();
+    assert_eq!(deliveries[0]["state"], "resolved");
+    assert_eq!(deliveries[0]["attempts"], 1);
+    assert_eq!(deliveries[1]["attempts"], 2);
+    assert_eq!(deliveries[1]["delivered"], true);
+    assert_eq!(deliveries[1]["status_code"], 200);
+    assert_eq!(deliveries[1]["state"], "triggered");
+}
+
+#[tokio::test]
+async fn test_evm_sequencer_poller() {
+    let l1 = MockL1::start(1_000).await;
+    let app = start_ws(&l1).await;
+
+    let l2 = MockL1::start(5_000_000).await;
+    app.start_sequencer("base", SequencerKind::Evm, l2.http_url());
+
+    let sequencer = wait_for("base sequencer poll", || async {
+        let (_, sequencer) = app.get("/rollups/base/sequencer").await;
+        (!sequencer["last_polled"].is_null()).then_some(sequencer)
+    })
+    .await;
+    assert_eq!(sequencer["latest_block"], 5_000_000);
+    assert_eq!(sequencer["is_producing"], true);
+
+    // A stalled chain is reported down
+    let stalled = MockL1::start_at(7_000_000, now() - 600).await;
+    app.start_sequencer("optimism", SequencerKind::Evm, stalled.http_url());
+    let sequencer = wait_for("optimism sequencer poll", || async {
+        let (_, sequencer) = app.get("/rollups/optimism/sequencer").await;
+        (!sequencer["last_polled"].is_null()).then_some(sequencer)
+    })
+    .await;
+    assert_eq!(sequencer["is_producing"], false);
+    assert!(sequencer["seconds_since_last_block"].as_u64().unwrap() >= 600);
+}
+
+#[tokio::test]
+async fn test_sequencer_and_health_changes_streamed() {
+    let l1 = MockL1::start(1_000).await;
+    let app = start_ws(&l1).await;
+    l1.emit(encode_log(
+        "abi/base_dispute_game_factory.json",
+        "DisputeGameCreated",
+        address(OPTIMISM_DISPUTE_GAME_FACTORY),
+        &[("rootClaim", bytes32(0xcd))],
+    ));
+    wait_for("optimism healthy", || async {
+        let (_, health) = app.get("/rollups/optimism/health").await;
+        (health["status"] == "Healthy").then_some(())
+    })
+    .await;
+    let (mut stream, _) = app.stream().await;
+
+    let stalled = MockL1::start_at(7_000_000, now() - 600).await;
+    app.start_sequencer("optimism", SequencerKind::Evm, stalled.http_url());
+
+    // A stuck sequencer halts the rollup, then is reported itself
+    let change = stream.next().await;
+    assert_eq!(change["type"], "HealthChanged");
+    assert_eq!(change["rollup"], "optimism");
+    assert_eq!(change["previous"], "Healthy");
+    assert_eq!(change["status"], "Halted");
+    assert!(change["reasons"][0]
+        .as_str()
+        .unwrap()
+        .starts_with("Sequencer not producing blocks"));
+
+    let change = stream.next().await;
+    assert_eq!(change["type"], "SequencerChanged");
+    assert_eq!(change["rollup"], "optimism");
+    assert_eq!(change["previous"]["is_producing"], false);
+    assert_eq!(change["status"]["is_producing"], false);
+    assert_eq!(change["status"]["latest_block"], 7_000_000);
+    assert!(change["reasons"][0]
+        .as_str()
+        .unwrap()
+        .starts_with("No new L2 block"));
+
+    let (_, incidents) = app.get("/rollups/optimism/incidents").await;
+    assert_eq!(incidents["incidents"][0]["stage"], "sequencer");
+    assert_eq!(incidents["incidents"][0]["peak"], "Halted");
+    assert!(incidents["incidents"][0]["ended_at"].is_null());
+
+    stalled.mine(1);
+    let change = stream.next().await;
+    assert_eq!(change["type"], "HealthChanged");
+    assert_eq!(change["previous"], "Halted");
+    assert_eq!(change["status"], "Healthy");
+    let change = stream.next().await;
+    assert_eq!(change["type"], "SequencerChanged");
+    assert_eq!(change["status"]["is_producing"], true);
+
+    let (_, incidents) = app.get(&format!("/incidents?since={}", now() - 60)).await;
+    assert_eq!(incidents["incidents"][0]["rollup"], "optimism");
+    assert!(incidents["incidents"][0]["ended_at"].as_u64().is_some());
+
+    let (status, uptime) = app.get("/rollups/optimism/uptime").await;
+    assert_eq!(status, 200);
+    let windows = uptime["windows"].as_array().unwrap();
+    assert_eq!(windows.len(), 3);
+    assert_eq!(windows[0]["window"], "24h");
+    assert_eq!(windows[0]["overall"]["incidents"], 1);
+    assert!(windows[0]["overall"]["mttr_secs"].as_u64().is_some());
+    assert_eq!(windows[0]["stages"]["sequencer"]["incidents"], 1);
+
+    let (status, _) = app.get("/rollups/optimism/uptime?from=100&to=50").await;
+    assert_eq!(status, 400);
+}
+
+#[tokio::test]
+async fn test_starknet_sequencer_poller() {
+    let l1 = MockL1::start(1_000).await;
+    let app = start_ws(&l1).await;
+
+    let starknet = MockStarknet::start(600_000, now() - 120).await;
+    app.start_sequencer("starknet", SequencerKind::Starknet, starknet.url());
+    wait_for("starknet sequencer stalled", || async {
+        let (_, sequencer) = app.get("/rollups/starknet/sequencer").await;
+        (sequencer["latest_block"] == 600_000 && sequencer["is_producing"] == false).then_some(())
+    })
+    .await;
+
+    starknet.set_latest(600_001, now());
+    wait_for("starknet sequencer producing", || async {
+        let (_, sequencer) = app.get("/rollups/starknet/sequencer").await;
+        (sequencer["latest_block"] == 600_001 && sequencer["is_producing"] == true).then_some(())
+    })
+    .await;
+}
@@ -0,0 +1,261 @@
+use super::rpc::MockL1;
+use futures_util::{SinkExt, StreamExt};
+use rollup_proof_status::alerts::{self, AlertDispatcher};
+use rollup_proof_status::api::{self, ApiState};
+use rollup_proof_status::config::{AlertConfig, BackfillConfig, L1Config, L1Mode, ReconnectConfig};
+use rollup_proof_status::l1::{self, LogRouter};
+use rollup_proof_status::sequencer::{self, L2ChainConfig, SequencerKind};
+use rollup_proof_status::{load_registry, AppState, EndpointPool, HealthMonitor, Store};
+use serde_json::Value;
+use std::future::Future;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use tokio_util::sync::CancellationToken;
+
+/// How long `wait_for` and `StreamClient::next` wait before failing a test
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Admin token the test API requires on its write routes
+pub const ADMIN_TOKEN: &str = "test-admin-token";
+
+/// L1 settings pointing both transports at `l1`
+pub fn l1_config(l1: &MockL1, mode: L1Mode) -> L1Config {
+    L1Config {
+        mode,
+        ws_urls: vec![l1.ws_url()],
+        http_urls: vec![l1.http_url()],
+        probe_interval: Duration::from_secs(30),
+        poll_interval: Duration::from_millis(100),
+        backfill: BackfillConfig {
+            lookback_blocks: 100,
+            chunk_size: 1000,
+        },
+    }
+}
+
+/// Reconnect settings forcing a resubscribe after `stale_timeout` without logs
+pub fn reconnect_config(stale_timeout: Duration) -> ReconnectConfig {
+    ReconnectConfig {
+        max_retries: 3,
+        base_backoff: Duration::from_millis(100),
+        max_backoff: Duration::from_secs(1),
+        stale_timeout,
+    }
+}
+
+/// The backend wired as in `main`, with the rollups from `rollups.toml`, an
+/// in-memory store and the API served on a local port
+pub struct TestApp {
+    pub state: AppState,
+    pub health: HealthMonitor,
+    addr: std::net::SocketAddr,
+    client: reqwest::Client,
+    cancel_token: CancellationToken,
+}
+
+impl TestApp {
+    /// Start the backend with the shared L1 watcher ingesting from `l1_config`
+    pub async fn start(l1_config: L1Config, reconnect_config: ReconnectConfig) -> Self {
+        let registry = load_registry(Path::new("rollups.toml")).unwrap();
+        let store = Store::open_in_memory().unwrap();
+        let state = AppState::new().with_store(store.clone());
+        let health = HealthMonitor::with_rollups(registry.names())
+            .with_store(store.clone())
+            .with_stream(state.tx.clone())
+            .with_watchers(state.watchers.clone());
+        let pool = EndpointPool::new(&l1_config.ws_urls, &l1_config.http_urls);
+        let cancel_token = CancellationToken::new();
+
+        let router = Arc::new(LogRouter::from_registry(&registry));
+        tokio::spawn(l1::start_l1_watcher(
+            pool.clone(),
+            router,
+            state.clone(),
+            health.clone(),
+            l1_config,
+            reconnect_config,
+            cancel_token.child_token(),
+        ));
+
+        // Fast retries so webhook tests need not wait out real backoffs
+        let alert_config = AlertConfig {
+            max_attempts: 3,
+            base_backoff: Duration::from_millis(20),
+            max_backoff: Duration::from_millis(100),
+            timeout: Duration::from_secs(2),
+            policy_interval: Duration::from_millis(100),
+        };
+        let dispatcher = AlertDispatcher::new(health.clone(), alert_config).with_store(store);
+        tokio::spawn(alerts::start_alerting(
+            dispatcher,
+            state.tx.subscribe(),
+            cancel_token.child_token(),
+        ));
+
+        let app = api::router(ApiState {
+            app: state.clone(),
+            health: health.clone(),
+            registry,
+            endpoints: pool,
+            admin_token: Some(ADMIN_TOKEN.to_string()),
+        });
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let shutdown = cancel_token.child_token();
+        tokio::spawn(async move {
+            axum::serve(listener, app)
+                .with_graceful_shutdown(async move { shutdown.cancelled().await })
+                .await
+                .ok();
+        });
+
+        Self {
+            state,
+            health,
+            addr,
+            client: reqwest::Client::new(),
+            cancel_token,
+        }
+    }
+
+    /// Start polling an L2 sequencer RPC for `rollup`
+    pub fn start_sequencer(&self, rollup: &str, kind: SequencerKind, rpc_url: String) {
+        let config = L2ChainConfig {
+            name: rollup.to_string(),
+            rpc_url,
+            poll_interval: Duration::from_millis(100),
+            downtime_threshold: Duration::from_secs(30),
+        };
+        tokio::spawn(sequencer::start_poller(
+            kind,
+            config,
+            self.state.clone(),
+            self.health.clone(),
+            self.cancel_token.child_token(),
+        ));
+    }
+
+    /// GET a REST endpoint, returning its status code and JSON body
+    pub async fn get(&self, path: &str) -> (u16, Value) {
+        let response = self
+            .client
+            .get(format!("http://{}{}", self.addr, path))
+            .send()
+            .await
+            .unwrap();
+        let status = response.status().as_u16();
+        (status, response.json().await.unwrap())
+    }
+
+    /// GET a plain-text endpoint, returning its content type and body
+    pub async fn get_text(&self, path: &str) -> (String, String) {
+        let response = self
+            .client
+            .get(format!("http://{}{}", self.addr, path))
+            .send()
+            .await
+            .unwrap();
+        let content_type = response
+            .headers()
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        (content_type, response.text().await.unwrap())
+    }
+
+    /// POST a JSON body to a REST endpoint as the admin, returning its status
+    /// code and JSON body
+    pub async fn post(&self, path: &str, body: Value) -> (u16, Value) {
+        self.post_as(path, body, Some(ADMIN_TOKEN)).await
+    }
+
+    /// POST a JSON body with the given bearer token, if any
+    pub async fn post_as(&self, path: &str, body: Value, token: Option<&str>) -> (u16, Value) {
+        let mut request = self
+            .client
+            .post(format!("http://{}{}", self.addr, path))
+            .json(&body);
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await.unwrap();
+        let status = response.status().as_u16();
+        (status, response.json().await.unwrap_or(Value::Null))
+    }
+
+    /// DELETE a REST resource as the admin, returning its status code
+    pub async fn delete(&self, path: &str) -> u16 {
+        self.delete_as(path, Some(ADMIN_TOKEN)).await
+    }
+
+    /// DELETE a REST resource with the given bearer token, if any
+    pub async fn delete_as(&self, path: &str, token: Option<&str>) -> u16 {
+        let mut request = self.client.delete(format!("http://{}{}", self.addr, path));
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
+        request.send().await.unwrap().status().as_u16()
+    }
+
+    /// Connect to `/rollups/stream`, returning the client and its `initial` message
+    pub async fn stream(&self) -> (StreamClient, Value) {
+        let url = format!("ws://{}/rollups/stream", self.addr);
+        let (socket, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+        let mut client = StreamClient { socket };
+        let initial = client.next().await;
+        assert_eq!(initial["type"], "initial");
+        (client, initial)
+    }
+}
+
+impl Drop for TestApp {
+    fn drop(&mut self) {
+        self.cancel_token.cancel();
+    }
+}
+
+/// A `/rollups/stream` client
+pub struct StreamClient {
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+}
+
+impl StreamClient {
+    /// Next JSON message, failing the test if none arrives in time
+    pub async fn next(&mut self) -> Value {
+        loop {
+            let message = tokio::time::timeout(TIMEOUT, self.socket.next())
+                .await
+                .expect("timed out waiting for a stream message")
+                .expect("stream closed")
+                .unwrap();
+            match message {
+                Message::Text(text) => return serde_json::from_str(&text).unwrap(),
+                Message::Ping(payload) => self.socket.send(Message::Pong(payload)).await.unwrap(),
+                _ => continue,
+            }
+        }
+    }
+}
+
+/// Poll `check` until it returns a value, failing the test after `TIMEOUT`
+pub async fn wait_for<T, F, Fut>(what: &str, mut check: F) -> T
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Option<T>>,
+{
+    let deadline = tokio::time::Instant::now() + TIMEOUT;
+    loop {
+        if let Some(value) = check().await {
+            return value;
+        }
+        if tokio::time::Instant::now() > deadline {
+            panic!("timed out waiting for {}", what);
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+}
@@ -0,0 +1,74 @@
+use ethers::abi::{self, ParamType, Token};
+use ethers::types::{Address, Bytes, Log, H256};
+use std::fs::File;
+
+/// Build a log for `event` of the contract ABI at `abi_path` (relative to the
+/// crate root), as emitted by `address`.
+///
+/// Fields named in `fields` take the given values; every other field is
+/// zeroed. Indexed fields go to topics, the rest are ABI-encoded into data.
+pub fn encode_log(abi_path: &str, event: &str, address: Address, fields: &[(&str, Token)]) -> Log {
+    let file = File::open(abi_path).unwrap_or_else(|e| panic!("open {}: {}", abi_path, e));
+    let contract =
+        abi::Contract::load(file).unwrap_or_else(|e| panic!("parse {}: {}", abi_path, e));
+    let event = contract
+        .event(event)
+        .unwrap_or_else(|_| panic!("{} has no event {}", abi_path, event));
+
+    for (name, _) in fields {
+        assert!(
+            event.inputs.iter().any(|input| input.name == *name),
+            "{} has no field {}",
+            event.name,
+            name
+        );
+    }
+
+    let mut topics = vec![event.signature()];
+    let mut data = Vec::new();
+    for input in &event.inputs {
+        let token = fields
+            .iter()
+            .find(|(name, _)| *name == input.name)
+            .map(|(_, token)| token.clone())
+            .unwrap_or_else(|| zero(&input.kind));
+        if input.indexed {
+            topics.push(H256::from_slice(&abi::encode(&[token])));
+        } else {
+            data.push(token);
+        }
+    }
+
+    Log {
+        address,
+        topics,
+        data: Bytes::from(abi::encode(&data)),
+        ..Default::default()
+    }
+}
+
+/// Zero value of an ABI type
+fn zero(kind: &ParamType) -> Token {
+    match kind {
+        ParamType::Address => Token::Address(Address::zero()),
+        ParamType::Bytes => Token::Bytes(Vec::new()),
+        ParamType::Int(_) => Token::Int(0.into()),
+        ParamType::Uint(_) => Token::Uint(0.into()),
+        ParamType::Bool => Token::Bool(false),
+        ParamType::String => Token::String(String::new()),
+        ParamType::Array(_) => Token::Array(Vec::new()),
+        ParamType::FixedBytes(size) => Token::FixedBytes(vec![0; *size]),
+        ParamType::FixedArray(kind, size) => Token::FixedArray(vec![zero(kind); *size]),
+        ParamType::Tuple(kinds) => Token::Tuple(kinds.iter().map(zero).collect()),
+    }
+}
+
+/// A `uint256` field value
+pub fn uint(value: u64) -> Token {
+    Token::Uint(value.into())
+}
+
+/// A `bytes32` field value of `byte` repeated
+pub fn bytes32(byte: u8) -> Token {
+    Token::FixedBytes(vec![byte; 32])
+}
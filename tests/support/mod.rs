@@ -0,0 +1,12 @@
+//! End-to-end test harness: in-process L1 and Starknet JSON-RPC nodes, a
+//! webhook receiver, ABI log encoding, and the backend wired against them.
+
+pub mod app;
+pub mod logs;
+pub mod rpc;
+pub mod webhook;
+
+pub use app::{l1_config, reconnect_config, wait_for, TestApp};
+pub use logs::{bytes32, encode_log, uint};
+pub use rpc::{now, MockL1, MockStarknet};
+pub use webhook::MockWebhook;
@@ -0,0 +1,431 @@
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use ethers::types::{Block, Log, TransactionReceipt, H256, U256, U64};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+use tokio::sync::broadcast;
+
+/// Seconds between mock L1 blocks
+const BLOCK_TIME: u64 = 12;
+
+/// Current unix timestamp in seconds
+pub fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Bind an axum router on an ephemeral local port
+pub(super) async fn serve(router: Router) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, router).await.ok();
+    });
+    addr
+}
+
+/// A change pushed to every open WebSocket connection
+#[derive(Debug, Clone)]
+enum ChainEvent {
+    Log(Box<Log>),
+    Head(Box<Block<H256>>),
+}
+
+/// Blocks, logs and request counters behind a mock chain
+#[derive(Default)]
+struct Chain {
+    head: u64,
+    /// Timestamps of blocks mined after start; earlier ones are derived
+    block_times: HashMap<u64, u64>,
+    /// Timestamp of the head the chain started at
+    genesis_time: u64,
+    genesis_head: u64,
+    logs: Vec<Log>,
+    /// Hashes of blocks replaced by `MockL1::reorg`, by number
+    block_hashes: HashMap<u64, H256>,
+    /// Requests served, by JSON-RPC method
+    calls: HashMap<String, usize>,
+    /// `eth_subscribe` calls, by subscription kind
+    subscriptions: HashMap<String, usize>,
+    next_id: u64,
+}
+
+impl Chain {
+    fn block_time(&self, number: u64) -> u64 {
+        self.block_times.get(&number).copied().unwrap_or_else(|| {
+            let behind = self.genesis_head.saturating_sub(number);
+            self.genesis_time.saturating_sub(behind * BLOCK_TIME)
+        })
+    }
+
+    fn block_hash(&self, number: u64) -> H256 {
+        self.block_hashes
+            .get(&number)
+            .copied()
+            .unwrap_or_else(|| H256::from_low_u64_be(number))
+    }
+
+    fn block(&self, number: u64) -> Block<H256> {
+        Block {
+            hash: Some(self.block_hash(number)),
+            parent_hash: H256::from_low_u64_be(number.saturating_sub(1)),
+            number: Some(U64::from(number)),
+            timestamp: U256::from(self.block_time(number)),
+            ..Default::default()
+        }
+    }
+
+    fn mine(&mut self) -> Block<H256> {
+        let previous = self.block_time(self.head);
+        self.head += 1;
+        self.block_times.insert(self.head, now().max(previous + 1));
+        self.block(self.head)
+    }
+
+    fn next_id(&mut self) -> String {
+        self.next_id += 1;
+        format!("0x{:x}", self.next_id)
+    }
+}
+
+/// In-process Ethereum JSON-RPC node.
+///
+/// Serves `eth_subscribe` (`logs`, `newHeads`) over WebSocket and
+/// `eth_blockNumber`, `eth_getBlockByNumber`, `eth_getLogs` and
+/// `eth_getTransactionReceipt` over both
+/// WebSocket and HTTP on the same port. Logs are pushed to every subscription
+/// unfiltered; the watcher's router drops the ones it did not ask for.
+#[derive(Clone)]
+pub struct MockL1 {
+    chain: Arc<Mutex<Chain>>,
+    events: broadcast::Sender<ChainEvent>,
+    addr: SocketAddr,
+}
+
+impl MockL1 {
+    /// Start a chain whose head is `head`, produced just now
+    pub async fn start(head: u64) -> Self {
+        Self::start_at(head, now()).await
+    }
+
+    /// Start a chain whose head `head` was produced at `head_time`
+    pub async fn start_at(head: u64, head_time: u64) -> Self {
+        let chain = Chain {
+            head,
+            genesis_head: head,
+            genesis_time: head_time,
+            ..Default::default()
+        };
+        let (events, _) = broadcast::channel(256);
+        let mut mock = Self {
+            chain: Arc::new(Mutex::new(chain)),
+            events,
+            addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+        };
+
+        let router = Router::new()
+            .route("/", get(ws_handler).post(http_handler))
+            .with_state(mock.clone());
+        mock.addr = serve(router).await;
+        mock
+    }
+
+    pub fn ws_url(&self) -> String {
+        format!("ws://{}", self.addr)
+    }
+
+    pub fn http_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Mine a block containing `log`, pushing the log and the new head to
+    /// subscribers. Returns the log as included in the block.
+    pub fn emit(&self, mut log: Log) -> Log {
+        let (log, block) = {
+            let mut chain = self.chain.lock().unwrap();
+            let block = chain.mine();
+            log.block_number = block.number;
+            log.block_hash = block.hash;
+            log.transaction_hash = Some(H256::from_low_u64_be(0x7000_0000 + chain.head));
+            log.transaction_index = Some(U64::zero());
+            log.log_index = Some(U256::zero());
+            log.removed = Some(false);
+            chain.logs.push(log.clone());
+            (log, block)
+        };
+        self.events
+            .send(ChainEvent::Log(Box::new(log.clone())))
+            .ok();
+        self.events.send(ChainEvent::Head(Box::new(block))).ok();
+        log
+    }
+
+    /// Replace the block holding `log` with one including the same log, as
+    /// after a one-block reorg. Only the new block is reported, so removed
+    /// logs are never pushed. Returns the log as re-included.
+    pub fn reorg(&self, log: &Log) -> Log {
+        let mut chain = self.chain.lock().unwrap();
+        let number = log.block_number.unwrap().as_u64();
+        let hash = H256::from_low_u64_be(number | 1 << 63);
+        chain.block_hashes.insert(number, hash);
+        let reincluded = Log {
+            block_hash: Some(hash),
+            ..log.clone()
+        };
+        for included in chain.logs.iter_mut() {
+            if included.transaction_hash == log.transaction_hash {
+                *included = reincluded.clone();
+            }
+        }
+        reincluded
+    }
+
+    /// Mine `count` empty blocks
+    pub fn mine(&self, count: u64) {
+        for _ in 0..count {
+            let block = self.chain.lock().unwrap().mine();
+            self.events.send(ChainEvent::Head(Box::new(block))).ok();
+        }
+    }
+
+    /// Number of requests served for a JSON-RPC method
+    pub fn calls(&self, method: &str) -> usize {
+        let chain = self.chain.lock().unwrap();
+        chain.calls.get(method).copied().unwrap_or_default()
+    }
+
+    /// Number of `eth_subscribe` calls for a subscription kind
+    pub fn subscriptions(&self, kind: &str) -> usize {
+        let chain = self.chain.lock().unwrap();
+        chain.subscriptions.get(kind).copied().unwrap_or_default()
+    }
+
+    /// Answer a non-subscription JSON-RPC call
+    fn call(&self, method: &str, params: &Value) -> Result<Value, String> {
+        let mut chain = self.chain.lock().unwrap();
+        *chain.calls.entry(method.to_string()).or_default() += 1;
+
+        match method {
+            "eth_chainId" => Ok(json!("0x1")),
+            "eth_blockNumber" => Ok(json!(U64::from(chain.head))),
+            "eth_getBlockByNumber" => {
+                let number = match block_param(&params[0]) {
+                    Some(number) => number,
+                    None => chain.head,
+                };
+                if number > chain.head {
+                    return Ok(Value::Null);
+                }
+                Ok(json!(chain.block(number)))
+            }
+            "eth_getLogs" => {
+                let filter = &params[0];
+                let from = block_param(&filter["fromBlock"]).unwrap_or(chain.head);
+                let to = block_param(&filter["toBlock"]).unwrap_or(chain.head);
+                let addresses = hex_list(&filter["address"]);
+                let topics = hex_list(&filter["topics"][0]);
+
+                let logs: Vec<&Log> = chain
+                    .logs
+                    .iter()
+                    .filter(|log| {
+                        let block = log.block_number.unwrap_or_default().as_u64();
+                        let address = format!("{:?}", log.address);
+                        let topic = log.topics.first().map(|t| format!("{:?}", t));
+                        (from..=to).contains(&block)
+                            && (addresses.is_empty() || addresses.contains(&address))
+                            && (topics.is_empty() || topic.is_some_and(|t| topics.contains(&t)))
+                    })
+                    .collect();
+                Ok(json!(logs))
+            }
+            "eth_getTransactionReceipt" => {
+                let hash = params[0].as_str().unwrap_or_default().to_lowercase();
+                let receipt = chain
+                    .logs
+                    .iter()
+                    .find(|log| format!("{:?}", log.transaction_hash.unwrap_or_default()) == hash)
+                    .map(|log| TransactionReceipt {
+                        transaction_hash: log.transaction_hash.unwrap_or_default(),
+                        block_hash: log.block_hash,
+                        block_number: log.block_number,
+                        logs: vec![log.clone()],
+                        status: Some(U64::one()),
+                        ..Default::default()
+                    });
+                Ok(json!(receipt))
+            }
+            other => Err(format!("the method {} does not exist", other)),
+        }
+    }
+
+    /// Register a subscription, returning its id
+    fn subscribe(&self, kind: &str) -> String {
+        let mut chain = self.chain.lock().unwrap();
+        *chain.calls.entry("eth_subscribe".to_string()).or_default() += 1;
+        *chain.subscriptions.entry(kind.to_string()).or_default() += 1;
+        chain.next_id()
+    }
+}
+
+/// Parse a block tag or hex number; `None` for `latest` and friends
+fn block_param(value: &Value) -> Option<u64> {
+    let value = value.as_str()?;
+    u64::from_str_radix(value.strip_prefix("0x")?, 16).ok()
+}
+
+/// Lowercase hex strings of a filter field that may be a value, list or null
+fn hex_list(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(s) => vec![s.to_lowercase()],
+        Value::Array(values) => values
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_lowercase)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// JSON-RPC response envelope
+fn response(id: &Value, result: Result<Value, String>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(message) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": -32601, "message": message }
+        }),
+    }
+}
+
+async fn http_handler(State(mock): State<MockL1>, Json(request): Json<Value>) -> impl IntoResponse {
+    let method = request["method"].as_str().unwrap_or_default();
+    Json(response(
+        &request["id"],
+        mock.call(method, &request["params"]),
+    ))
+}
+
+async fn ws_handler(ws: WebSocketUpgrade, State(mock): State<MockL1>) -> impl IntoResponse {
+    ws.on_upgrade(|socket| serve_ws(socket, mock))
+}
+
+/// Serve one WebSocket connection until the client goes away
+async fn serve_ws(mut socket: WebSocket, mock: MockL1) {
+    let mut events = mock.events.subscribe();
+    // Subscription id -> kind, for this connection only
+    let mut subscriptions: HashMap<String, String> = HashMap::new();
+
+    loop {
+        tokio::select! {
+            message = socket.recv() => {
+                let request: Value = match message {
+                    Some(Ok(Message::Text(text))) => match serde_json::from_str(&text) {
+                        Ok(request) => request,
+                        Err(_) => continue,
+                    },
+                    Some(Ok(_)) => continue,
+                    Some(Err(_)) | None => return,
+                };
+                let method = request["method"].as_str().unwrap_or_default();
+                let params = &request["params"];
+                let result = match method {
+                    "eth_subscribe" => {
+                        let kind = params[0].as_str().unwrap_or_default().to_string();
+                        let id = mock.subscribe(&kind);
+                        subscriptions.insert(id.clone(), kind);
+                        Ok(json!(id))
+                    }
+                    "eth_unsubscribe" => {
+                        let id = params[0].as_str().unwrap_or_default();
+                        Ok(json!(subscriptions.remove(id).is_some()))
+                    }
+                    _ => mock.call(method, params),
+                };
+                let reply = response(&request["id"], result).to_string();
+                if socket.send(Message::Text(reply.into())).await.is_err() {
+                    return;
+                }
+            }
+            event = events.recv() => {
+                let (kind, result) = match event {
+                    Ok(ChainEvent::Log(log)) => ("logs", json!(log)),
+                    Ok(ChainEvent::Head(block)) => ("newHeads", json!(block)),
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => return,
+                };
+                for (id, _) in subscriptions.iter().filter(|(_, k)| *k == kind) {
+                    let notification = json!({
+                        "jsonrpc": "2.0",
+                        "method": "eth_subscription",
+                        "params": { "subscription": id, "result": result }
+                    });
+                    if socket.send(Message::Text(notification.to_string().into())).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// In-process Starknet JSON-RPC node answering `starknet_getBlockWithTxHashes`
+#[derive(Clone)]
+pub struct MockStarknet {
+    /// Latest block number and timestamp
+    latest: Arc<Mutex<(u64, u64)>>,
+    addr: SocketAddr,
+}
+
+impl MockStarknet {
+    /// Start a node whose latest block is `number`, produced at `timestamp`
+    pub async fn start(number: u64, timestamp: u64) -> Self {
+        let mut mock = Self {
+            latest: Arc::new(Mutex::new((number, timestamp))),
+            addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+        };
+        let router = Router::new()
+            .route("/", post(starknet_handler))
+            .with_state(mock.clone());
+        mock.addr = serve(router).await;
+        mock
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Produce a new latest block
+    pub fn set_latest(&self, number: u64, timestamp: u64) {
+        *self.latest.lock().unwrap() = (number, timestamp);
+    }
+}
+
+async fn starknet_handler(
+    State(mock): State<MockStarknet>,
+    Json(request): Json<Value>,
+) -> impl IntoResponse {
+    let result = match request["method"].as_str() {
+        Some("starknet_getBlockWithTxHashes") => {
+            let (number, timestamp) = *mock.latest.lock().unwrap();
+            Ok(json!({
+                "status": "ACCEPTED_ON_L2",
+                "block_hash": format!("0x{:x}", number),
+                "block_number": number,
+                "timestamp": timestamp,
+                "transactions": []
+            }))
+        }
+        other => Err(format!("the method {:?} does not exist", other)),
+    };
+    Json(response(&request["id"], result))
+}
@@ -0,0 +1,72 @@
+use super::rpc::serve;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::{Json, Router};
+use serde_json::Value;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+/// A request received by a `MockWebhook`
+#[derive(Debug, Clone)]
+pub struct Received {
+    pub headers: HeaderMap,
+    pub body: Value,
+}
+
+#[derive(Default)]
+struct Inbox {
+    received: Vec<Received>,
+    /// Requests still to be answered with a 500
+    failures: usize,
+}
+
+/// Local HTTP stand-in for a webhook receiver, recording every request
+#[derive(Clone)]
+pub struct MockWebhook {
+    inbox: Arc<Mutex<Inbox>>,
+    addr: SocketAddr,
+}
+
+impl MockWebhook {
+    /// Start a receiver answering 200 to every request
+    pub async fn start() -> Self {
+        let mut mock = Self {
+            inbox: Arc::default(),
+            addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+        };
+        let router = Router::new()
+            .route("/hook", post(receive))
+            .with_state(mock.clone());
+        mock.addr = serve(router).await;
+        mock
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}/hook", self.addr)
+    }
+
+    /// Answer the next `count` requests with a 500
+    pub fn fail_next(&self, count: usize) {
+        self.inbox.lock().unwrap().failures = count;
+    }
+
+    /// Every request received so far, failed ones included
+    pub fn received(&self) -> Vec<Received> {
+        self.inbox.lock().unwrap().received.clone()
+    }
+}
+
+async fn receive(
+    State(mock): State<MockWebhook>,
+    headers: HeaderMap,
+    Json(body): Json<Value>,
+) -> StatusCode {
+    let mut inbox = mock.inbox.lock().unwrap();
+    inbox.received.push(Received { headers, body });
+    if inbox.failures > 0 {
+        inbox.failures -= 1;
+        return StatusCode::INTERNAL_SERVER_ERROR;
+    }
+    StatusCode::OK
+}